@@ -254,6 +254,14 @@ pub fn expand_test_or_bench(
                                         "allow_fail",
                                         cx.expr_bool(sp, should_fail(&cx.sess, &item)),
                                     ),
+                                    // timeout: Some(...) | None
+                                    field(
+                                        "timeout",
+                                        match test_timeout(cx, &item) {
+                                            Some(secs) => cx.expr_some(sp, cx.expr_u64(sp, secs)),
+                                            None => cx.expr_none(sp),
+                                        },
+                                    ),
                                     // should_panic: ...
                                     field(
                                         "should_panic",
@@ -356,6 +364,18 @@ fn should_fail(sess: &Session, i: &ast::Item) -> bool {
     sess.contains_name(&i.attrs, sym::allow_fail)
 }
 
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    let attr = cx.sess.find_by_name(&i.attrs, sym::test_timeout)?;
+    let secs = attr.value_str().and_then(|s| s.as_str().parse::<u64>().ok()).filter(|&s| s > 0);
+    if secs.is_none() {
+        cx.sess.parse_sess.span_diagnostic.span_err(
+            attr.span,
+            "argument must be of the form: `test_timeout = \"seconds\"` with a non-zero number",
+        );
+    }
+    secs
+}
+
 fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     match cx.sess.find_by_name(&i.attrs, sym::should_panic) {
         Some(attr) => {
@@ -631,6 +631,9 @@ pub fn set(&self, features: &mut Features, span: Span) {
 
     /// Allows using `pointer` and `reference` in intra-doc links
     (active, intra_doc_pointers, "1.51.0", Some(80896), None),
+
+    /// Allows a test to set a time limit after which it is killed.
+    (active, test_timeout, "1.51.0", None, None),
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
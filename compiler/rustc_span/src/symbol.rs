@@ -1143,6 +1143,7 @@
         test_case,
         test_removed_feature,
         test_runner,
+        test_timeout,
         then_with,
         thread,
         thread_local,
@@ -149,6 +149,7 @@
 use core::ops::{Deref, DerefMut};
 use core::ptr;
 
+use crate::alloc::{Allocator, Global};
 use crate::slice;
 use crate::vec::{self, AsIntoIter, Vec};
 
@@ -247,8 +248,11 @@
 /// [peek]: BinaryHeap::peek
 /// [peek\_mut]: BinaryHeap::peek_mut
 #[stable(feature = "rust1", since = "1.0.0")]
-pub struct BinaryHeap<T> {
-    data: Vec<T>,
+pub struct BinaryHeap<
+    T,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    data: Vec<T, A>,
 }
 
 /// Structure wrapping a mutable reference to the greatest item on a
@@ -259,20 +263,24 @@ pub struct BinaryHeap<T> {
 ///
 /// [`peek_mut`]: BinaryHeap::peek_mut
 #[stable(feature = "binary_heap_peek_mut", since = "1.12.0")]
-pub struct PeekMut<'a, T: 'a + Ord> {
-    heap: &'a mut BinaryHeap<T>,
+pub struct PeekMut<
+    'a,
+    T: 'a + Ord,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    heap: &'a mut BinaryHeap<T, A>,
     sift: bool,
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<T: Ord + fmt::Debug> fmt::Debug for PeekMut<'_, T> {
+impl<T: Ord + fmt::Debug, A: Allocator> fmt::Debug for PeekMut<'_, T, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("PeekMut").field(&self.heap.data[0]).finish()
     }
 }
 
 #[stable(feature = "binary_heap_peek_mut", since = "1.12.0")]
-impl<T: Ord> Drop for PeekMut<'_, T> {
+impl<T: Ord, A: Allocator> Drop for PeekMut<'_, T, A> {
     fn drop(&mut self) {
         if self.sift {
             self.heap.sift_down(0);
@@ -281,7 +289,7 @@ fn drop(&mut self) {
 }
 
 #[stable(feature = "binary_heap_peek_mut", since = "1.12.0")]
-impl<T: Ord> Deref for PeekMut<'_, T> {
+impl<T: Ord, A: Allocator> Deref for PeekMut<'_, T, A> {
     type Target = T;
     fn deref(&self) -> &T {
         debug_assert!(!self.heap.is_empty());
@@ -291,7 +299,7 @@ fn deref(&self) -> &T {
 }
 
 #[stable(feature = "binary_heap_peek_mut", since = "1.12.0")]
-impl<T: Ord> DerefMut for PeekMut<'_, T> {
+impl<T: Ord, A: Allocator> DerefMut for PeekMut<'_, T, A> {
     fn deref_mut(&mut self) -> &mut T {
         debug_assert!(!self.heap.is_empty());
         self.sift = true;
@@ -300,10 +308,10 @@ fn deref_mut(&mut self) -> &mut T {
     }
 }
 
-impl<'a, T: Ord> PeekMut<'a, T> {
+impl<'a, T: Ord, A: Allocator> PeekMut<'a, T, A> {
     /// Removes the peeked value from the heap and returns it.
     #[stable(feature = "binary_heap_peek_mut_pop", since = "1.18.0")]
-    pub fn pop(mut this: PeekMut<'a, T>) -> T {
+    pub fn pop(mut this: PeekMut<'a, T, A>) -> T {
         let value = this.heap.pop().unwrap();
         this.sift = false;
         value
@@ -311,7 +319,7 @@ pub fn pop(mut this: PeekMut<'a, T>) -> T {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Clone> Clone for BinaryHeap<T> {
+impl<T: Clone, A: Allocator + Clone> Clone for BinaryHeap<T, A> {
     fn clone(&self) -> Self {
         BinaryHeap { data: self.data.clone() }
     }
@@ -331,7 +339,7 @@ fn default() -> BinaryHeap<T> {
 }
 
 #[stable(feature = "binaryheap_debug", since = "1.4.0")]
-impl<T: fmt::Debug> fmt::Debug for BinaryHeap<T> {
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for BinaryHeap<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
@@ -372,6 +380,49 @@ pub fn new() -> BinaryHeap<T> {
     pub fn with_capacity(capacity: usize) -> BinaryHeap<T> {
         BinaryHeap { data: Vec::with_capacity(capacity) }
     }
+}
+
+impl<T: Ord, A: Allocator> BinaryHeap<T, A> {
+    /// Creates an empty `BinaryHeap<T, A>` as a max-heap, using `A` as allocator.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::BinaryHeap;
+    /// let mut heap = BinaryHeap::new_in(System);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> BinaryHeap<T, A> {
+        BinaryHeap { data: Vec::new_in(alloc) }
+    }
+
+    /// Creates an empty `BinaryHeap<T, A>` with a specific capacity, using `A` as allocator.
+    /// This preallocates enough memory for `capacity` elements,
+    /// so that the `BinaryHeap` does not have to be reallocated
+    /// until it contains at least that many values.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::BinaryHeap;
+    /// let mut heap = BinaryHeap::with_capacity_in(10, System);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> BinaryHeap<T, A> {
+        BinaryHeap { data: Vec::with_capacity_in(capacity, alloc) }
+    }
 
     /// Returns a mutable reference to the greatest item in the binary heap, or
     /// `None` if it is empty.
@@ -403,7 +454,7 @@ pub fn with_capacity(capacity: usize) -> BinaryHeap<T> {
     /// If the item is modified then the worst case time complexity is *O*(log(*n*)),
     /// otherwise it's *O*(1).
     #[stable(feature = "binary_heap_peek_mut", since = "1.12.0")]
-    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, A>> {
         if self.is_empty() { None } else { Some(PeekMut { heap: self, sift: false }) }
     }
 
@@ -494,7 +545,7 @@ pub fn push(&mut self, item: T) {
     /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
     /// ```
     #[stable(feature = "binary_heap_extras_15", since = "1.5.0")]
-    pub fn into_sorted_vec(mut self) -> Vec<T> {
+    pub fn into_sorted_vec(mut self) -> Vec<T, A> {
         let mut end = self.len();
         while end > 1 {
             end -= 1;
@@ -677,7 +728,7 @@ fn better_to_rebuild(len1: usize, len2: usize) -> bool {
     /// ```
     #[inline]
     #[unstable(feature = "binary_heap_drain_sorted", issue = "59278")]
-    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T> {
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, A> {
         DrainSorted { inner: self }
     }
 
@@ -710,7 +761,7 @@ pub fn retain<F>(&mut self, f: F)
     }
 }
 
-impl<T> BinaryHeap<T> {
+impl<T, A: Allocator> BinaryHeap<T, A> {
     /// Returns an iterator visiting all values in the underlying vector, in
     /// arbitrary order.
     ///
@@ -747,7 +798,7 @@ pub fn iter(&self) -> Iter<'_, T> {
     /// assert_eq!(heap.into_iter_sorted().take(2).collect::<Vec<_>>(), vec![5, 4]);
     /// ```
     #[unstable(feature = "binary_heap_into_iter_sorted", issue = "59278")]
-    pub fn into_iter_sorted(self) -> IntoIterSorted<T> {
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, A> {
         IntoIterSorted { inner: self }
     }
 
@@ -908,10 +959,17 @@ pub fn shrink_to(&mut self, min_capacity: usize) {
     /// }
     /// ```
     #[stable(feature = "binary_heap_extras_15", since = "1.5.0")]
-    pub fn into_vec(self) -> Vec<T> {
+    pub fn into_vec(self) -> Vec<T, A> {
         self.into()
     }
 
+    /// Returns a reference to the underlying allocator.
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.data.allocator()
+    }
+
     /// Returns the length of the binary heap.
     ///
     /// # Examples
@@ -975,7 +1033,7 @@ pub fn is_empty(&self) -> bool {
     /// ```
     #[inline]
     #[stable(feature = "drain", since = "1.6.0")]
-    pub fn drain(&mut self) -> Drain<'_, T> {
+    pub fn drain(&mut self) -> Drain<'_, T, A> {
         Drain { iter: self.data.drain(..) }
     }
 
@@ -1144,19 +1202,22 @@ impl<T> FusedIterator for Iter<'_, T> {}
 /// [`into_iter`]: BinaryHeap::into_iter
 #[stable(feature = "rust1", since = "1.0.0")]
 #[derive(Clone)]
-pub struct IntoIter<T> {
-    iter: vec::IntoIter<T>,
+pub struct IntoIter<
+    T,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    iter: vec::IntoIter<T, A>,
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for IntoIter<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("IntoIter").field(&self.iter.as_slice()).finish()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     #[inline]
@@ -1171,7 +1232,7 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
         self.iter.next_back()
@@ -1179,14 +1240,14 @@ fn next_back(&mut self) -> Option<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {
     fn is_empty(&self) -> bool {
         self.iter.is_empty()
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<T> FusedIterator for IntoIter<T> {}
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
 
 #[unstable(issue = "none", feature = "inplace_iteration")]
 unsafe impl<T> SourceIter for IntoIter<T> {
@@ -1211,12 +1272,15 @@ fn as_into_iter(&mut self) -> &mut vec::IntoIter<Self::Item> {
 
 #[unstable(feature = "binary_heap_into_iter_sorted", issue = "59278")]
 #[derive(Clone, Debug)]
-pub struct IntoIterSorted<T> {
-    inner: BinaryHeap<T>,
+pub struct IntoIterSorted<
+    T,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    inner: BinaryHeap<T, A>,
 }
 
 #[unstable(feature = "binary_heap_into_iter_sorted", issue = "59278")]
-impl<T: Ord> Iterator for IntoIterSorted<T> {
+impl<T: Ord, A: Allocator> Iterator for IntoIterSorted<T, A> {
     type Item = T;
 
     #[inline]
@@ -1232,13 +1296,13 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[unstable(feature = "binary_heap_into_iter_sorted", issue = "59278")]
-impl<T: Ord> ExactSizeIterator for IntoIterSorted<T> {}
+impl<T: Ord, A: Allocator> ExactSizeIterator for IntoIterSorted<T, A> {}
 
 #[unstable(feature = "binary_heap_into_iter_sorted", issue = "59278")]
-impl<T: Ord> FusedIterator for IntoIterSorted<T> {}
+impl<T: Ord, A: Allocator> FusedIterator for IntoIterSorted<T, A> {}
 
 #[unstable(feature = "trusted_len", issue = "37572")]
-unsafe impl<T: Ord> TrustedLen for IntoIterSorted<T> {}
+unsafe impl<T: Ord, A: Allocator> TrustedLen for IntoIterSorted<T, A> {}
 
 /// A draining iterator over the elements of a `BinaryHeap`.
 ///
@@ -1248,12 +1312,16 @@ unsafe impl<T: Ord> TrustedLen for IntoIterSorted<T> {}
 /// [`drain`]: BinaryHeap::drain
 #[stable(feature = "drain", since = "1.6.0")]
 #[derive(Debug)]
-pub struct Drain<'a, T: 'a> {
-    iter: vec::Drain<'a, T>,
+pub struct Drain<
+    'a,
+    T: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + 'a = Global,
+> {
+    iter: vec::Drain<'a, T, A>,
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<T> Iterator for Drain<'_, T> {
+impl<T, A: Allocator> Iterator for Drain<'_, T, A> {
     type Item = T;
 
     #[inline]
@@ -1268,7 +1336,7 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<T> DoubleEndedIterator for Drain<'_, T> {
+impl<T, A: Allocator> DoubleEndedIterator for Drain<'_, T, A> {
     #[inline]
     fn next_back(&mut self) -> Option<T> {
         self.iter.next_back()
@@ -1276,14 +1344,14 @@ fn next_back(&mut self) -> Option<T> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl<T> ExactSizeIterator for Drain<'_, T> {
+impl<T, A: Allocator> ExactSizeIterator for Drain<'_, T, A> {
     fn is_empty(&self) -> bool {
         self.iter.is_empty()
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<T> FusedIterator for Drain<'_, T> {}
+impl<T, A: Allocator> FusedIterator for Drain<'_, T, A> {}
 
 /// A draining iterator over the elements of a `BinaryHeap`.
 ///
@@ -1293,17 +1361,21 @@ impl<T> FusedIterator for Drain<'_, T> {}
 /// [`drain_sorted`]: BinaryHeap::drain_sorted
 #[unstable(feature = "binary_heap_drain_sorted", issue = "59278")]
 #[derive(Debug)]
-pub struct DrainSorted<'a, T: Ord> {
-    inner: &'a mut BinaryHeap<T>,
+pub struct DrainSorted<
+    'a,
+    T: Ord,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    inner: &'a mut BinaryHeap<T, A>,
 }
 
 #[unstable(feature = "binary_heap_drain_sorted", issue = "59278")]
-impl<'a, T: Ord> Drop for DrainSorted<'a, T> {
+impl<'a, T: Ord, A: Allocator> Drop for DrainSorted<'a, T, A> {
     /// Removes heap elements in heap order.
     fn drop(&mut self) {
-        struct DropGuard<'r, 'a, T: Ord>(&'r mut DrainSorted<'a, T>);
+        struct DropGuard<'r, 'a, T: Ord, A: Allocator>(&'r mut DrainSorted<'a, T, A>);
 
-        impl<'r, 'a, T: Ord> Drop for DropGuard<'r, 'a, T> {
+        impl<'r, 'a, T: Ord, A: Allocator> Drop for DropGuard<'r, 'a, T, A> {
             fn drop(&mut self) {
                 while self.0.inner.pop().is_some() {}
             }
@@ -1318,7 +1390,7 @@ fn drop(&mut self) {
 }
 
 #[unstable(feature = "binary_heap_drain_sorted", issue = "59278")]
-impl<T: Ord> Iterator for DrainSorted<'_, T> {
+impl<T: Ord, A: Allocator> Iterator for DrainSorted<'_, T, A> {
     type Item = T;
 
     #[inline]
@@ -1334,20 +1406,20 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[unstable(feature = "binary_heap_drain_sorted", issue = "59278")]
-impl<T: Ord> ExactSizeIterator for DrainSorted<'_, T> {}
+impl<T: Ord, A: Allocator> ExactSizeIterator for DrainSorted<'_, T, A> {}
 
 #[unstable(feature = "binary_heap_drain_sorted", issue = "59278")]
-impl<T: Ord> FusedIterator for DrainSorted<'_, T> {}
+impl<T: Ord, A: Allocator> FusedIterator for DrainSorted<'_, T, A> {}
 
 #[unstable(feature = "trusted_len", issue = "37572")]
-unsafe impl<T: Ord> TrustedLen for DrainSorted<'_, T> {}
+unsafe impl<T: Ord, A: Allocator> TrustedLen for DrainSorted<'_, T, A> {}
 
 #[stable(feature = "binary_heap_extras_15", since = "1.5.0")]
-impl<T: Ord> From<Vec<T>> for BinaryHeap<T> {
+impl<T: Ord, A: Allocator> From<Vec<T, A>> for BinaryHeap<T, A> {
     /// Converts a `Vec<T>` into a `BinaryHeap<T>`.
     ///
     /// This conversion happens in-place, and has *O*(*n*) time complexity.
-    fn from(vec: Vec<T>) -> BinaryHeap<T> {
+    fn from(vec: Vec<T, A>) -> BinaryHeap<T, A> {
         let mut heap = BinaryHeap { data: vec };
         heap.rebuild();
         heap
@@ -1355,12 +1427,12 @@ fn from(vec: Vec<T>) -> BinaryHeap<T> {
 }
 
 #[stable(feature = "binary_heap_extras_15", since = "1.5.0")]
-impl<T> From<BinaryHeap<T>> for Vec<T> {
+impl<T, A: Allocator> From<BinaryHeap<T, A>> for Vec<T, A> {
     /// Converts a `BinaryHeap<T>` into a `Vec<T>`.
     ///
     /// This conversion requires no data movement or allocation, and has
     /// constant time complexity.
-    fn from(heap: BinaryHeap<T>) -> Vec<T> {
+    fn from(heap: BinaryHeap<T, A>) -> Vec<T, A> {
         heap.data
     }
 }
@@ -1373,9 +1445,9 @@ fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BinaryHeap<T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T> IntoIterator for BinaryHeap<T> {
+impl<T, A: Allocator> IntoIterator for BinaryHeap<T, A> {
     type Item = T;
-    type IntoIter = IntoIter<T>;
+    type IntoIter = IntoIter<T, A>;
 
     /// Creates a consuming iterator, that is, one that moves each value out of
     /// the binary heap in arbitrary order. The binary heap cannot be used
@@ -1395,13 +1467,13 @@ impl<T> IntoIterator for BinaryHeap<T> {
     ///     println!("{}", x);
     /// }
     /// ```
-    fn into_iter(self) -> IntoIter<T> {
+    fn into_iter(self) -> IntoIter<T, A> {
         IntoIter { iter: self.data.into_iter() }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T> IntoIterator for &'a BinaryHeap<T> {
+impl<'a, T, A: Allocator> IntoIterator for &'a BinaryHeap<T, A> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -1411,7 +1483,7 @@ fn into_iter(self) -> Iter<'a, T> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord> Extend<T> for BinaryHeap<T> {
+impl<T: Ord, A: Allocator> Extend<T> for BinaryHeap<T, A> {
     #[inline]
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         <Self as SpecExtend<I>>::spec_extend(self, iter);
@@ -1428,19 +1500,19 @@ fn extend_reserve(&mut self, additional: usize) {
     }
 }
 
-impl<T: Ord, I: IntoIterator<Item = T>> SpecExtend<I> for BinaryHeap<T> {
+impl<T: Ord, A: Allocator, I: IntoIterator<Item = T>> SpecExtend<I> for BinaryHeap<T, A> {
     default fn spec_extend(&mut self, iter: I) {
         self.extend_desugared(iter.into_iter());
     }
 }
 
-impl<T: Ord> SpecExtend<BinaryHeap<T>> for BinaryHeap<T> {
-    fn spec_extend(&mut self, ref mut other: BinaryHeap<T>) {
+impl<T: Ord, A: Allocator> SpecExtend<BinaryHeap<T, A>> for BinaryHeap<T, A> {
+    fn spec_extend(&mut self, ref mut other: BinaryHeap<T, A>) {
         self.append(other);
     }
 }
 
-impl<T: Ord> BinaryHeap<T> {
+impl<T: Ord, A: Allocator> BinaryHeap<T, A> {
     fn extend_desugared<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         let iterator = iter.into_iter();
         let (lower, _) = iterator.size_hint();
@@ -1452,7 +1524,7 @@ fn extend_desugared<I: IntoIterator<Item = T>>(&mut self, iter: I) {
 }
 
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for BinaryHeap<T> {
+impl<'a, T: 'a + Ord + Copy, A: Allocator> Extend<&'a T> for BinaryHeap<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
     }
@@ -1467,3 +1539,541 @@ fn extend_reserve(&mut self, additional: usize) {
         self.reserve(additional);
     }
 }
+
+/// A handle to an element of an [`IndexedBinaryHeap`].
+///
+/// Handles are returned by [`IndexedBinaryHeap::push`] and refer to the same
+/// element until it is popped or removed. After that the handle is stale: it
+/// will not match a later element, even one that reuses the same storage.
+///
+/// A handle is only meaningful for the heap that returned it. Passing it to a
+/// different heap is not detected: depending on that heap's contents it may
+/// refer to an unrelated element there, rather than be treated as stale.
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: usize,
+}
+
+/// A priority queue that returns a [`Handle`] for every pushed element, so
+/// that the element can later be reprioritized or removed.
+///
+/// Like [`BinaryHeap`], this is a max-heap. In addition to `push`, `pop` and
+/// `peek`, it supports [`change_priority`] and [`remove`] on any element in
+/// *O*(log(*n*)) time. This makes it suitable for algorithms such as Dijkstra's
+/// shortest path, where the priority of a queued node drops as shorter paths
+/// to it are found.
+///
+/// It is a logic error for an item to be modified in such a way that the
+/// item's ordering relative to any other item, as determined by the `Ord`
+/// trait, changes while it is in the heap, other than through
+/// [`change_priority`]. The behavior resulting from such a logic error is not
+/// specified, but will not result in undefined behavior.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(binary_heap_indexed)]
+/// use std::cmp::Reverse;
+/// use std::collections::binary_heap::IndexedBinaryHeap;
+///
+/// let mut heap = IndexedBinaryHeap::new();
+/// let a = heap.push(Reverse((7, 'a')));
+/// let b = heap.push(Reverse((5, 'b')));
+/// heap.push(Reverse((6, 'c')));
+///
+/// // Found a cheaper way to reach `a`.
+/// heap.change_priority(a, Reverse((1, 'a')));
+/// // `b` is no longer needed.
+/// assert_eq!(heap.remove(b), Some(Reverse((5, 'b'))));
+///
+/// assert_eq!(heap.pop(), Some(Reverse((1, 'a'))));
+/// assert_eq!(heap.pop(), Some(Reverse((6, 'c'))));
+/// assert_eq!(heap.pop(), None);
+///
+/// // Handles to elements that have left the heap are stale.
+/// assert!(!heap.contains(a));
+/// assert_eq!(heap.remove(b), None);
+/// ```
+///
+/// [`change_priority`]: IndexedBinaryHeap::change_priority
+/// [`remove`]: IndexedBinaryHeap::remove
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+pub struct IndexedBinaryHeap<
+    T,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+> {
+    data: Vec<IndexedEntry<T>, A>,
+    slots: Vec<Slot, A>,
+    free: Vec<usize, A>,
+}
+
+/// An element of an `IndexedBinaryHeap` together with the index of the slot
+/// that records its position.
+#[derive(Clone)]
+struct IndexedEntry<T> {
+    item: T,
+    slot: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    /// Position of the element in `data`, or `None` if the slot is free.
+    pos: Option<usize>,
+    /// Bumped whenever the slot is freed, which invalidates outstanding handles.
+    generation: usize,
+}
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+impl<T: Clone, A: Allocator + Clone> Clone for IndexedBinaryHeap<T, A> {
+    fn clone(&self) -> Self {
+        IndexedBinaryHeap {
+            data: self.data.clone(),
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+        }
+    }
+}
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+impl<T: Ord> Default for IndexedBinaryHeap<T> {
+    /// Creates an empty `IndexedBinaryHeap<T>`.
+    #[inline]
+    fn default() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap::new()
+    }
+}
+
+#[unstable(feature = "binary_heap_indexed", issue = "none")]
+impl<T: fmt::Debug, A: Allocator + Clone> fmt::Debug for IndexedBinaryHeap<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter().map(|entry| &entry.item)).finish()
+    }
+}
+
+impl<T: Ord> IndexedBinaryHeap<T> {
+    /// Creates an empty `IndexedBinaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn new() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap::new_in(Global)
+    }
+
+    /// Creates an empty `IndexedBinaryHeap` with space for at least
+    /// `capacity` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn with_capacity(capacity: usize) -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T: Ord, A: Allocator + Clone> IndexedBinaryHeap<T, A> {
+    /// Creates an empty `IndexedBinaryHeap` in the provided allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, binary_heap_indexed)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new_in(System);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn new_in(alloc: A) -> IndexedBinaryHeap<T, A> {
+        IndexedBinaryHeap {
+            data: Vec::new_in(alloc.clone()),
+            slots: Vec::new_in(alloc.clone()),
+            free: Vec::new_in(alloc),
+        }
+    }
+
+    /// Creates an empty `IndexedBinaryHeap` with space for at least
+    /// `capacity` elements in the provided allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, binary_heap_indexed)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::with_capacity_in(10, System);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> IndexedBinaryHeap<T, A> {
+        IndexedBinaryHeap {
+            data: Vec::with_capacity_in(capacity, alloc.clone()),
+            slots: Vec::with_capacity_in(capacity, alloc.clone()),
+            free: Vec::new_in(alloc),
+        }
+    }
+
+    /// Pushes an item onto the heap and returns a handle to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let three = heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.get(three), Some(&3));
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(log(*n*)) in the worst case.
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn push(&mut self, item: T) -> Handle {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot { pos: None, generation: 0 });
+                self.slots.len() - 1
+            }
+        };
+        let pos = self.data.len();
+        self.data.push(IndexedEntry { item, slot: index });
+        let slot = &mut self.slots[index];
+        slot.pos = Some(pos);
+        let handle = Handle { index, generation: slot.generation };
+        self.sift_up(0, pos);
+        handle
+    }
+
+    /// Removes the greatest item from the heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() { None } else { Some(self.remove_at(0)) }
+    }
+
+    /// Replaces the item that `handle` refers to with `item`, moves it to its
+    /// new place in the heap and returns the old item.
+    ///
+    /// The handle remains valid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` does not refer to an element of the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let one = heap.push(1);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.change_priority(one, 8), 1);
+    /// assert_eq!(heap.peek(), Some(&8));
+    /// assert_eq!(heap.get(one), Some(&8));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(log(*n*)) in the worst case.
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn change_priority(&mut self, handle: Handle, item: T) -> T {
+        let pos = match self.position(handle) {
+            Some(pos) => pos,
+            None => panic!("handle does not refer to an element of the heap"),
+        };
+        let old = mem::replace(&mut self.data[pos].item, item);
+        self.restore(pos);
+        old
+    }
+
+    /// Removes the item that `handle` refers to from the heap and returns it,
+    /// or `None` if the handle is stale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let one = heap.push(1);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.remove(one), Some(1));
+    /// assert_eq!(heap.remove(one), None);
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(log(*n*)) in the worst case.
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let pos = self.position(handle)?;
+        Some(self.remove_at(pos))
+    }
+
+    /// Removes the element at `pos`, moving the last element into its place.
+    fn remove_at(&mut self, pos: usize) -> T {
+        let IndexedEntry { item, slot: index } = self.data.swap_remove(pos);
+        let slot = &mut self.slots[index];
+        slot.pos = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+
+        if pos < self.data.len() {
+            let moved = self.data[pos].slot;
+            self.slots[moved].pos = Some(pos);
+            self.restore(pos);
+        }
+        item
+    }
+
+    /// Moves the element at `pos` up or down until the heap property holds.
+    fn restore(&mut self, pos: usize) {
+        if self.sift_up(0, pos) == pos {
+            self.sift_down(pos);
+        }
+    }
+
+    // These mirror `BinaryHeap::sift_up` and `BinaryHeap::sift_down_range`,
+    // but also record the new position of every element the hole moves past.
+    fn sift_up(&mut self, start: usize, pos: usize) -> usize {
+        let IndexedBinaryHeap { data, slots, .. } = self;
+        unsafe {
+            // Take out the value at `pos` and create a hole.
+            let mut hole = Hole::new(data, pos);
+
+            while hole.pos() > start {
+                let parent = (hole.pos() - 1) / 2;
+                if hole.element().item <= hole.get(parent).item {
+                    break;
+                }
+                move_hole(&mut hole, slots, parent);
+            }
+            hole.pos()
+        }
+    }
+
+    fn sift_down(&mut self, pos: usize) {
+        let end = self.data.len();
+        let IndexedBinaryHeap { data, slots, .. } = self;
+        unsafe {
+            let mut hole = Hole::new(data, pos);
+            let mut child = 2 * pos + 1;
+            while child < end - 1 {
+                // compare with the greater of the two children
+                child += (hole.get(child).item <= hole.get(child + 1).item) as usize;
+                // if we are already in order, stop.
+                if hole.element().item >= hole.get(child).item {
+                    return;
+                }
+                move_hole(&mut hole, slots, child);
+                child = 2 * hole.pos() + 1;
+            }
+            if child == end - 1 && hole.element().item < hole.get(child).item {
+                move_hole(&mut hole, slots, child);
+            }
+        }
+    }
+}
+
+impl<T, A: Allocator + Clone> IndexedBinaryHeap<T, A> {
+    /// Returns the greatest item in the heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first().map(|entry| &entry.item)
+    }
+
+    /// Returns a reference to the item that `handle` refers to, or `None` if
+    /// the handle is stale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let two = heap.push(2);
+    /// assert_eq!(heap.get(two), Some(&2));
+    ///
+    /// heap.pop();
+    /// assert_eq!(heap.get(two), None);
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position(handle).map(|pos| &self.data[pos].item)
+    }
+
+    /// Returns `true` if `handle` refers to an element of the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let two = heap.push(2);
+    /// assert!(heap.contains(two));
+    ///
+    /// heap.remove(two);
+    /// assert!(!heap.contains(two));
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Returns the length of the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(1);
+    /// let three = heap.push(3);
+    /// assert_eq!(heap.len(), 2);
+    ///
+    /// heap.remove(three);
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// assert!(heap.is_empty());
+    ///
+    /// let one = heap.push(1);
+    /// assert!(!heap.is_empty());
+    ///
+    /// heap.remove(one);
+    /// assert!(heap.is_empty());
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the heap, invalidating every handle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(binary_heap_indexed)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let one = heap.push(1);
+    /// heap.push(3);
+    ///
+    /// heap.clear();
+    /// assert!(heap.is_empty());
+    /// assert!(!heap.contains(one));
+    /// ```
+    #[unstable(feature = "binary_heap_indexed", issue = "none")]
+    pub fn clear(&mut self) {
+        for entry in self.data.iter() {
+            let slot = &mut self.slots[entry.slot];
+            slot.pos = None;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(entry.slot);
+        }
+        self.data.clear();
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.data.allocator()
+    }
+
+    fn position(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.index)?;
+        if slot.generation == handle.generation { slot.pos } else { None }
+    }
+}
+
+/// Moves `hole` to `index` and records the new positions of both the element
+/// it displaced and the element it carries.
+///
+/// Unsafe because index must be within the data slice and not equal to the
+/// hole's position.
+#[inline]
+unsafe fn move_hole<T>(hole: &mut Hole<'_, IndexedEntry<T>>, slots: &mut [Slot], index: usize) {
+    let from = hole.pos();
+    unsafe {
+        hole.move_to(index);
+        slots[hole.get(from).slot].pos = Some(from);
+    }
+    // Keep the carried element's slot pointing at the hole, where it will be
+    // written back when the hole is dropped, even if a comparison panics.
+    slots[hole.element().slot].pos = Some(index);
+}
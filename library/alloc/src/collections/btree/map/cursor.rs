@@ -0,0 +1,494 @@
+use core::fmt::{self, Debug};
+
+use super::super::borrow::DormantMutRef;
+use super::super::node::{marker, Handle, InsertResult::*, NodeRef, Root};
+use crate::alloc::{Allocator, Global};
+
+/// A cursor over a `BTreeMap`.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `Cursor` is created with the [`BTreeMap::lower_bound`] and [`BTreeMap::upper_bound`] methods.
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct Cursor<'a, K: 'a, V: 'a> {
+    pub(super) current:
+        Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    pub(super) root: Option<&'a Root<K, V>>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K, V> Clone for Cursor<'_, K, V> {
+    fn clone(&self) -> Self {
+        let Cursor { current, root } = *self;
+        Cursor { current, root }
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cursor").field(&self.key_value()).finish()
+    }
+}
+
+/// A cursor over a `BTreeMap` with editing operations.
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the tree during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying tree. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to an element in the tree, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields `None` between the last and
+/// first elements of the tree.
+///
+/// A `CursorMut` is created with the [`BTreeMap::lower_bound_mut`] and
+/// [`BTreeMap::upper_bound_mut`] methods.
+#[unstable(feature = "btree_cursors", issue = "none")]
+pub struct CursorMut<
+    'a,
+    K: 'a,
+    V: 'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+> {
+    pub(super) current:
+        Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>>,
+    pub(super) root: DormantMutRef<'a, Option<Root<K, V>>>,
+    pub(super) length: &'a mut usize,
+    pub(super) alloc: &'a A,
+}
+
+#[unstable(feature = "btree_cursors", issue = "none")]
+impl<K: Debug, V: Debug, A: Allocator + Clone> Debug for CursorMut<'_, K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CursorMut").field(&self.as_cursor().key_value()).finish()
+    }
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.current = self.next_kv();
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.current = self.next_back_kv();
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key(&self) -> Option<&'a K> {
+        self.current.map(|current| current.into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value(&self) -> Option<&'a V> {
+        self.current.map(|current| current.into_kv().1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.current.map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.next_kv().map(Handle::into_kv)
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.next_back_kv().map(Handle::into_kv)
+    }
+
+    fn next_kv(
+        &self,
+    ) -> Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>> {
+        let edge = match self.current {
+            None => self.root?.reborrow().first_leaf_edge(),
+            Some(current) => current.next_leaf_edge(),
+        };
+        edge.next_kv().ok()
+    }
+
+    fn next_back_kv(
+        &self,
+    ) -> Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::KV>> {
+        let edge = match self.current {
+            None => self.root?.reborrow().last_leaf_edge(),
+            Some(current) => current.next_back_leaf_edge(),
+        };
+        edge.next_back_kv().ok()
+    }
+}
+
+impl<'a, K, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
+    /// Moves the cursor to the next element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_next(&mut self) {
+        self.current = match self.current.take() {
+            None => {
+                // SAFETY: the cursor holds no other handle into the tree.
+                let root = unsafe { self.root.reborrow() };
+                root.as_mut().and_then(|root| root.borrow_mut().first_leaf_edge().next_kv().ok())
+            }
+            Some(current) => current.next_leaf_edge().next_kv().ok(),
+        };
+    }
+
+    /// Moves the cursor to the previous element of the `BTreeMap`.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this will move it to the "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current.take() {
+            None => {
+                // SAFETY: the cursor holds no other handle into the tree.
+                let root = unsafe { self.root.reborrow() };
+                root.as_mut()
+                    .and_then(|root| root.borrow_mut().last_leaf_edge().next_back_kv().ok())
+            }
+            Some(current) => current.next_back_leaf_edge().next_back_kv().ok(),
+        };
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key(&self) -> Option<&K> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv().0)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value(&self) -> Option<&V> {
+        self.current.as_ref().map(|current| current.reborrow().into_kv().1)
+    }
+
+    /// Returns a mutable reference to the value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.current.as_mut().map(|current| current.kv_mut().1)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the element that the cursor is currently pointing to.
+    ///
+    /// This returns `None` if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.current.as_mut().map(|current| {
+            let (k, v) = current.kv_mut();
+            (&*k, v)
+        })
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the `BTreeMap`. If it is pointing to the last
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let edge = match self.current {
+            None => {
+                // SAFETY: the cursor holds no other handle into the tree.
+                let root = unsafe { self.root.reborrow() };
+                root.as_mut()?.borrow_mut().first_leaf_edge()
+            }
+            // SAFETY: the reborrowed handle is only used to look up the next element.
+            Some(ref mut current) => unsafe { current.reborrow_mut() }.next_leaf_edge(),
+        };
+        let (k, v) = edge.next_kv().ok()?.into_kv_mut();
+        Some((k, v))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the `BTreeMap`. If it is pointing to the first
+    /// element of the `BTreeMap` then this returns `None`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let edge = match self.current {
+            None => {
+                // SAFETY: the cursor holds no other handle into the tree.
+                let root = unsafe { self.root.reborrow() };
+                root.as_mut()?.borrow_mut().last_leaf_edge()
+            }
+            // SAFETY: the reborrowed handle is only used to look up the previous element.
+            Some(ref mut current) => unsafe { current.reborrow_mut() }.next_back_leaf_edge(),
+        };
+        let (k, v) = edge.next_back_kv().ok()?.into_kv_mut();
+        Some((k, v))
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        // SAFETY: the root is only borrowed shared, and the current handle is
+        // frozen for as long as the returned cursor is around.
+        let root = unsafe { self.root.reborrow_shared() };
+        Cursor {
+            current: self.current.as_ref().map(|current| current.reborrow()),
+            root: root.as_ref(),
+        }
+    }
+}
+
+// Now the tree editing operations
+impl<'a, K, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
+    /// Inserts a new element into the `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeMap`.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
+        let (edge, ghost) = match self.current.take() {
+            None => {
+                let alloc = self.alloc;
+                // SAFETY: the cursor holds no other handle into the tree.
+                let root = unsafe { self.root.reborrow() };
+                let root = root.get_or_insert_with(|| Root::new(alloc));
+                (root.borrow_mut().first_leaf_edge(), true)
+            }
+            Some(current) => (current.next_leaf_edge(), false),
+        };
+        let handle = self.insert_at(edge, key, value);
+        // SAFETY: we have finished growing the tree, and the cursor holds no
+        // other handle into it.
+        let handle = unsafe { handle.awaken() };
+        self.current = if ghost { None } else { handle.left_edge().next_back_kv().ok() };
+    }
+
+    /// Inserts a new element into the `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeMap`.
+    ///
+    /// # Safety
+    ///
+    /// You must ensure that the `BTreeMap` invariants are maintained.
+    /// Specifically:
+    ///
+    /// * The key of the newly inserted element must be unique in the tree.
+    /// * All keys in the tree must remain in sorted order.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
+        let (edge, ghost) = match self.current.take() {
+            None => {
+                let alloc = self.alloc;
+                // SAFETY: the cursor holds no other handle into the tree.
+                let root = unsafe { self.root.reborrow() };
+                let root = root.get_or_insert_with(|| Root::new(alloc));
+                (root.borrow_mut().last_leaf_edge(), true)
+            }
+            Some(current) => (current.next_back_leaf_edge(), false),
+        };
+        let handle = self.insert_at(edge, key, value);
+        // SAFETY: we have finished growing the tree, and the cursor holds no
+        // other handle into it.
+        let handle = unsafe { handle.awaken() };
+        self.current = if ghost { None } else { handle.right_edge().next_kv().ok() };
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        let mut emptied_internal_root = false;
+        let (kv, pos) = current.remove_kv_tracking(|| emptied_internal_root = true, self.alloc);
+        self.current = pos.next_kv().ok();
+        self.finish_removal(emptied_internal_root);
+        Some(kv)
+    }
+
+    /// Removes the current element from the `BTreeMap`.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the previous element in the `BTreeMap`.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and `None` is returned. The cursor is not moved in this case.
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn remove_current_and_move_back(&mut self) -> Option<(K, V)> {
+        let current = self.current.take()?;
+        let mut emptied_internal_root = false;
+        let (kv, pos) = current.remove_kv_tracking(|| emptied_internal_root = true, self.alloc);
+        self.current = pos.next_back_kv().ok();
+        self.finish_removal(emptied_internal_root);
+        Some(kv)
+    }
+}
+
+impl<'a, K: Ord, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
+    /// Inserts a new element into the `BTreeMap` after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the `BTreeMap`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares less than or equal to the current element (unless
+    ///   the cursor is pointing at the "ghost" non-element).
+    /// - the given key compares greater than or equal to the next element (unless
+    ///   the cursor is pointing at the last element in the tree).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_after(&mut self, key: K, value: V) {
+        if let Some(current) = self.key() {
+            if &key <= current {
+                panic!("key must be ordered above the current element");
+            }
+        }
+        if let Some((next, _)) = self.peek_next() {
+            if &key >= next {
+                panic!("key must be ordered below the next element");
+            }
+        }
+        // SAFETY: the key is strictly between its new neighbours.
+        unsafe { self.insert_after_unchecked(key, value) }
+    }
+
+    /// Inserts a new element into the `BTreeMap` before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the `BTreeMap`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if:
+    /// - the given key compares greater than or equal to the current element (unless
+    ///   the cursor is pointing at the "ghost" non-element).
+    /// - the given key compares less than or equal to the previous element (unless
+    ///   the cursor is pointing at the first element in the tree).
+    #[unstable(feature = "btree_cursors", issue = "none")]
+    pub fn insert_before(&mut self, key: K, value: V) {
+        if let Some(current) = self.key() {
+            if &key >= current {
+                panic!("key must be ordered below the current element");
+            }
+        }
+        if let Some((prev, _)) = self.peek_prev() {
+            if &key <= prev {
+                panic!("key must be ordered above the previous element");
+            }
+        }
+        // SAFETY: the key is strictly between its new neighbours.
+        unsafe { self.insert_before_unchecked(key, value) }
+    }
+}
+
+impl<'a, K, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
+    /// Inserts a key-value pair at a leaf edge that the cursor has taken out of
+    /// the tree, growing the tree if necessary, and returns a dormant handle to
+    /// the new pair.
+    fn insert_at(
+        &mut self,
+        edge: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>,
+        key: K,
+        value: V,
+    ) -> Handle<NodeRef<marker::DormantMut, K, V, marker::Leaf>, marker::KV> {
+        let handle = match edge.insert_recursing(key, value, self.alloc) {
+            (Fit(_), handle) => handle,
+            (Split(ins), handle) => {
+                drop(ins.left);
+                // SAFETY: the edge was consumed by the insertion, and the handle
+                // returned is dormant.
+                let root = unsafe { self.root.reborrow() }.as_mut().unwrap();
+                root.push_internal_level(self.alloc).push(ins.kv.0, ins.kv.1, ins.right);
+                handle
+            }
+        };
+        *self.length += 1;
+        handle
+    }
+
+    /// Updates the length and, if needed, the root after removing an element.
+    fn finish_removal(&mut self, emptied_internal_root: bool) {
+        *self.length -= 1;
+        if emptied_internal_root {
+            // SAFETY: popping the root does not invalidate the current handle,
+            // which cannot point into the emptied root node.
+            let root = unsafe { self.root.reborrow() }.as_mut().unwrap();
+            root.pop_internal_level(self.alloc);
+        }
+    }
+}
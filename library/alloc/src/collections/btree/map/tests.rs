@@ -5,6 +5,7 @@
 use crate::fmt::Debug;
 use crate::rc::Rc;
 use crate::string::{String, ToString};
+use crate::testing::alloc::CountingAlloc;
 use crate::vec::Vec;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
@@ -120,8 +121,8 @@ fn compact(&mut self)
         K: Ord,
     {
         let iter = mem::take(self).into_iter();
-        let root = BTreeMap::ensure_is_owned(&mut self.root);
-        root.bulk_push(iter, &mut self.length);
+        let root = BTreeMap::ensure_is_owned(&mut self.root, Global);
+        root.bulk_push(iter, &mut self.length, Global);
     }
 }
 
@@ -1921,6 +1922,40 @@ fn test_split_off_large_random_sorted() {
     assert!(right.into_iter().eq(data.into_iter().filter(|x| x.0 >= key)));
 }
 
+#[test]
+fn test_new_in() {
+    let live = AtomicUsize::new(0);
+    let data = rand_data(if cfg!(miri) { 200 } else { 1000 });
+    {
+        let mut map = BTreeMap::new_in(CountingAlloc(&live));
+        map.extend(data.iter().copied());
+        assert!(live.load(SeqCst) > 1);
+        assert_eq!(map.len(), data.len());
+
+        let clone = map.clone();
+        assert_eq!(clone, map);
+
+        let key = data[data.len() / 2].0;
+        let mut right = map.split_off(&key);
+        assert!(map.keys().all(|&k| k < key));
+        assert!(right.keys().all(|&k| k >= key));
+
+        right.drain_filter(|k, _| k % 2 == 0).for_each(drop);
+        map.append(&mut right);
+        assert!(right.is_empty());
+        assert_eq!(map.len(), data.iter().filter(|&&(k, _)| k < key || k % 2 != 0).count());
+
+        let mut cursor = map.lower_bound_mut(Unbounded);
+        while cursor.remove_current().is_some() {}
+        assert!(map.is_empty());
+
+        let mut iter = clone.into_iter();
+        iter.next();
+        iter.next_back();
+    }
+    assert_eq!(live.load(SeqCst), 0);
+}
+
 #[test]
 fn test_into_iter_drop_leak_height_0() {
     static DROPS: AtomicUsize = AtomicUsize::new(0);
@@ -2024,3 +2059,175 @@ fn test_insert_remove_intertwined_ord_chaos() {
     }
     map.check_invariants();
 }
+
+#[test]
+fn test_cursor() {
+    let map = BTreeMap::from_iter(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+
+    let mut cur = map.lower_bound(Unbounded);
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&2));
+    assert_eq!(cur.peek_next(), Some((&3, &'c')));
+    cur.move_prev();
+    assert_eq!(cur.key(), Some(&1));
+    assert_eq!(cur.peek_prev(), None);
+
+    let mut cur = map.upper_bound(Excluded(&1));
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    cur.move_prev();
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_prev(), Some((&3, &'c')));
+}
+
+#[test]
+fn test_cursor_bounds() {
+    let map: BTreeMap<_, _> = (0..1000).map(|i| (i * 2, i)).collect();
+    for i in -1..2001 {
+        let expected = map.keys().copied().find(|k| *k >= i);
+        assert_eq!(map.lower_bound(Included(&i)).key().copied(), expected);
+        let expected = map.keys().copied().find(|k| *k > i);
+        assert_eq!(map.lower_bound(Excluded(&i)).key().copied(), expected);
+        let expected = map.keys().copied().rev().find(|k| *k <= i);
+        assert_eq!(map.upper_bound(Included(&i)).key().copied(), expected);
+        let expected = map.keys().copied().rev().find(|k| *k < i);
+        assert_eq!(map.upper_bound(Excluded(&i)).key().copied(), expected);
+    }
+    assert_eq!(map.upper_bound(Unbounded).key(), Some(&1998));
+
+    let empty = BTreeMap::<i32, i32>::new();
+    assert_eq!(empty.lower_bound(Included(&0)).key(), None);
+    assert_eq!(empty.upper_bound(Unbounded).peek_next(), None);
+}
+
+#[test]
+fn test_cursor_walk() {
+    let map: BTreeMap<_, _> = (0..1000).map(|i| (i, i)).collect();
+    let mut cur = map.lower_bound(Unbounded);
+    for i in 0..1000 {
+        assert_eq!(cur.key_value(), Some((&i, &i)));
+        cur.move_next();
+    }
+    assert_eq!(cur.key(), None);
+    for i in (0..1000).rev() {
+        cur.move_prev();
+        assert_eq!(cur.key_value(), Some((&i, &i)));
+    }
+}
+
+#[test]
+fn test_cursor_mut() {
+    let mut map = BTreeMap::from_iter(vec![(1, 'a'), (3, 'c'), (5, 'e')]);
+    let mut cur = map.lower_bound_mut(Excluded(&3));
+    assert_eq!(cur.key(), Some(&5));
+    cur.insert_before(4, 'd');
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.peek_prev(), Some((&4, &mut 'd')));
+    cur.move_next();
+    assert_eq!(cur.key(), None);
+    cur.insert_before(6, 'f');
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.remove_current(), None);
+    assert_eq!(cur.key(), None);
+    cur.insert_after(0, '?');
+    assert_eq!(cur.key(), None);
+    assert_eq!(
+        map,
+        BTreeMap::from_iter(vec![(0, '?'), (1, 'a'), (3, 'c'), (4, 'd'), (5, 'e'), (6, 'f')])
+    );
+
+    let mut cur = map.upper_bound_mut(Included(&5));
+    assert_eq!(cur.key(), Some(&5));
+    assert_eq!(cur.remove_current(), Some((5, 'e')));
+    assert_eq!(cur.key(), Some(&6));
+    assert_eq!(cur.remove_current_and_move_back(), Some((6, 'f')));
+    assert_eq!(cur.key(), Some(&4));
+    *cur.value_mut().unwrap() = 'D';
+    drop(cur);
+    assert_eq!(map, BTreeMap::from_iter(vec![(0, '?'), (1, 'a'), (3, 'c'), (4, 'D')]));
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_empty() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    assert_eq!(cur.key(), None);
+    assert_eq!(cur.peek_next(), None);
+    cur.insert_after(1, 'a');
+    assert_eq!(cur.key(), None);
+    cur.move_next();
+    assert_eq!(cur.key(), Some(&1));
+    assert_eq!(cur.remove_current(), Some((1, 'a')));
+    assert_eq!(cur.key(), None);
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_grow_and_shrink() {
+    let mut map = BTreeMap::new();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    for i in 0..1000 {
+        cur.insert_before(i, i);
+        assert_eq!(cur.key(), None);
+        assert_eq!(cur.peek_prev(), Some((&i, &mut i.clone())));
+    }
+    map.check();
+    assert_eq!(map.len(), 1000);
+    assert!(map.height() > Some(1));
+
+    let mut cur = map.lower_bound_mut(Included(&500));
+    for i in 500..1000 {
+        assert_eq!(cur.remove_current(), Some((i, i)));
+    }
+    assert_eq!(cur.key(), None);
+    for i in (0..500).rev() {
+        cur.move_prev();
+        assert_eq!(cur.remove_current_and_move_back(), Some((i, i)));
+        cur.move_next();
+    }
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[test]
+fn test_cursor_mut_insert_interleaved() {
+    let mut map: BTreeMap<_, _> = (0..500).map(|i| (i * 2, ())).collect();
+    let mut cur = map.lower_bound_mut(Unbounded);
+    while let Some(&key) = cur.key() {
+        cur.insert_after(key + 1, ());
+        cur.move_next();
+        assert_eq!(cur.key(), Some(&(key + 1)));
+        cur.move_next();
+    }
+    map.check();
+    assert!(map.keys().copied().eq(0..1000));
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the current element")]
+fn test_cursor_mut_insert_after_below_current() {
+    let mut map = BTreeMap::from_iter(vec![(1, 'a'), (3, 'c')]);
+    let mut cur = map.lower_bound_mut(Included(&3));
+    cur.insert_after(2, 'b');
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered below the next element")]
+fn test_cursor_mut_insert_after_above_next() {
+    let mut map = BTreeMap::from_iter(vec![(1, 'a'), (3, 'c')]);
+    let mut cur = map.lower_bound_mut(Included(&1));
+    cur.insert_after(3, 'b');
+}
+
+#[test]
+#[should_panic(expected = "key must be ordered above the previous element")]
+fn test_cursor_mut_insert_before_below_previous() {
+    let mut map = BTreeMap::from_iter(vec![(1, 'a'), (3, 'c')]);
+    let mut cur = map.lower_bound_mut(Unbounded);
+    cur.move_prev();
+    cur.insert_before(0, 'z');
+}
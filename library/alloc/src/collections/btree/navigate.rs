@@ -1,6 +1,7 @@
+use core::alloc::Allocator;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
-use core::ops::Bound::{Excluded, Included, Unbounded};
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
 use core::ops::RangeBounds;
 use core::ptr;
 
@@ -296,8 +297,9 @@ macro_rules! def_next_kv_uncheched_dealloc {
         /// - The leaf edge must not be the last one in the direction travelled.
         /// - The node carrying the next KV returned must not have been deallocated by a
         ///   previous call on any handle obtained for this tree.
-        unsafe fn $name <K, V>(
+        unsafe fn $name <K, V, A: Allocator + Clone>(
             leaf_edge: Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge>,
+            alloc: A,
         ) -> Handle<NodeRef<marker::Owned, K, V, marker::LeafOrInternal>, marker::KV> {
             let mut edge = leaf_edge.forget_node_type();
             loop {
@@ -305,7 +307,7 @@ unsafe fn $name <K, V>(
                     Ok(internal_kv) => return internal_kv,
                     Err(last_edge) => {
                         unsafe {
-                            let parent_edge = last_edge.into_node().deallocate_and_ascend();
+                            let parent_edge = last_edge.into_node().deallocate_and_ascend(alloc.clone());
                             unwrap_unchecked(parent_edge).forget_node_type()
                         }
                     }
@@ -391,9 +393,9 @@ impl<K, V> Handle<NodeRef<marker::Owned, K, V, marker::Leaf>, marker::Edge> {
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// call this method again subject to its safety conditions, or call counterpart
     /// `next_back_unchecked` subject to its safety conditions.
-    pub unsafe fn next_unchecked(&mut self) -> (K, V) {
+    pub unsafe fn next_unchecked<A: Allocator + Clone>(&mut self, alloc: A) -> (K, V) {
         super::mem::replace(self, |leaf_edge| {
-            let kv = unsafe { next_kv_unchecked_dealloc(leaf_edge) };
+            let kv = unsafe { next_kv_unchecked_dealloc(leaf_edge, alloc) };
             let k = unsafe { ptr::read(kv.reborrow().into_kv().0) };
             let v = unsafe { ptr::read(kv.reborrow().into_kv().1) };
             (kv.next_leaf_edge(), (k, v))
@@ -412,9 +414,9 @@ pub unsafe fn next_unchecked(&mut self) -> (K, V) {
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// call this method again subject to its safety conditions, or call counterpart
     /// `next_unchecked` subject to its safety conditions.
-    pub unsafe fn next_back_unchecked(&mut self) -> (K, V) {
+    pub unsafe fn next_back_unchecked<A: Allocator + Clone>(&mut self, alloc: A) -> (K, V) {
         super::mem::replace(self, |leaf_edge| {
-            let kv = unsafe { next_back_kv_unchecked_dealloc(leaf_edge) };
+            let kv = unsafe { next_back_kv_unchecked_dealloc(leaf_edge, alloc) };
             let k = unsafe { ptr::read(kv.reborrow().into_kv().0) };
             let v = unsafe { ptr::read(kv.reborrow().into_kv().1) };
             (kv.next_back_leaf_edge(), (k, v))
@@ -448,6 +450,90 @@ pub fn last_leaf_edge(self) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, m
             }
         }
     }
+
+    /// Returns the leaf edge right before the first key-value pair in or underneath
+    /// a node that lies above `bound`, or the rightmost leaf edge if there is none.
+    ///
+    /// The result is meaningful only if the tree is ordered by key, like the tree
+    /// in a `BTreeMap` is.
+    pub fn lower_bound<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut found = false;
+        loop {
+            let edge = match (found, bound) {
+                (false, Included(key)) => match node.search_node(key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.left_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (false, Excluded(key)) => match node.search_node(key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.right_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (true, Included(_)) => node.last_edge(),
+                (true, Excluded(_)) => node.first_edge(),
+                (_, Unbounded) => node.first_edge(),
+            };
+            match edge.force() {
+                Leaf(edge) => return edge,
+                Internal(edge) => node = edge.descend(),
+            }
+        }
+    }
+
+    /// Returns the leaf edge right after the last key-value pair in or underneath
+    /// a node that lies below `bound`, or the leftmost leaf edge if there is none.
+    ///
+    /// The result is meaningful only if the tree is ordered by key, like the tree
+    /// in a `BTreeMap` is.
+    pub fn upper_bound<Q: ?Sized>(
+        self,
+        bound: Bound<&Q>,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+    {
+        let mut node = self;
+        let mut found = false;
+        loop {
+            let edge = match (found, bound) {
+                (false, Included(key)) => match node.search_node(key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.right_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (false, Excluded(key)) => match node.search_node(key) {
+                    SearchResult::Found(kv) => {
+                        found = true;
+                        kv.left_edge()
+                    }
+                    SearchResult::GoDown(edge) => edge,
+                },
+                (true, Included(_)) => node.first_edge(),
+                (true, Excluded(_)) => node.last_edge(),
+                (_, Unbounded) => node.last_edge(),
+            };
+            match edge.force() {
+                Leaf(edge) => return edge,
+                Internal(edge) => node = edge.descend(),
+            }
+        }
+    }
 }
 
 pub enum Position<BorrowType, K, V> {
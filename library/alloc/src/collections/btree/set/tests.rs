@@ -1,6 +1,7 @@
 use super::super::DeterministicRng;
 use super::*;
 use crate::vec::Vec;
+use std::alloc::System;
 use std::cmp::Ordering;
 use std::iter::FromIterator;
 use std::panic::{catch_unwind, AssertUnwindSafe};
@@ -16,6 +17,20 @@ fn test_clone_eq() {
     assert_eq!(m.clone(), m);
 }
 
+#[test]
+fn test_new_in() {
+    let mut a = BTreeSet::new_in(System);
+    a.extend(0..100);
+    let mut b = a.split_off(&50);
+    assert_eq!(a.len(), 50);
+    assert!((&a | &b).into_iter().eq(0..100));
+
+    let c: BTreeSet<i32, System> = &a & &b;
+    assert!(c.is_empty());
+    b.append(&mut a);
+    assert!(b.clone().into_iter().eq(0..100));
+}
+
 #[allow(dead_code)]
 fn test_const() {
     const SET: &'static BTreeSet<()> = &BTreeSet::new();
@@ -1,11 +1,16 @@
 use super::map::MIN_LEN;
 use super::node::{ForceResult::*, Root};
 use super::search::SearchResult::*;
+use core::alloc::Allocator;
 use core::borrow::Borrow;
 
 impl<K, V> Root<K, V> {
-    pub fn split_off<Q: ?Sized + Ord>(&mut self, right_root: &mut Self, key: &Q)
-    where
+    pub fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(
+        &mut self,
+        right_root: &mut Self,
+        key: &Q,
+        alloc: A,
+    ) where
         K: Borrow<Q>,
     {
         debug_assert!(right_root.height() == 0);
@@ -13,7 +18,7 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, right_root: &mut Self, key: &Q)
 
         let left_root = self;
         for _ in 0..left_root.height() {
-            right_root.push_internal_level();
+            right_root.push_internal_level(alloc.clone());
         }
 
         {
@@ -42,22 +47,22 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, right_root: &mut Self, key: &Q)
             }
         }
 
-        left_root.fix_right_border();
-        right_root.fix_left_border();
+        left_root.fix_right_border(alloc.clone());
+        right_root.fix_left_border(alloc);
     }
 
     /// Removes empty levels on the top, but keeps an empty leaf if the entire tree is empty.
-    fn fix_top(&mut self) {
+    fn fix_top<A: Allocator + Clone>(&mut self, alloc: A) {
         while self.height() > 0 && self.len() == 0 {
-            self.pop_internal_level();
+            self.pop_internal_level(alloc.clone());
         }
     }
 
     /// Stock up or merge away any underfull nodes on the right border of the
     /// tree. The other nodes, those that are not the root nor a rightmost edge,
     /// must already have at least MIN_LEN elements.
-    fn fix_right_border(&mut self) {
-        self.fix_top();
+    fn fix_right_border<A: Allocator + Clone>(&mut self, alloc: A) {
+        self.fix_top(alloc.clone());
 
         {
             let mut cur_node = self.borrow_mut();
@@ -66,7 +71,7 @@ fn fix_right_border(&mut self) {
                 let mut last_kv = node.last_kv().consider_for_balancing();
 
                 if last_kv.can_merge() {
-                    cur_node = last_kv.merge_tracking_child();
+                    cur_node = last_kv.merge_tracking_child(alloc.clone());
                 } else {
                     let right_len = last_kv.right_child_len();
                     // `MIN_LEN + 1` to avoid readjust if merge happens on the next level.
@@ -79,12 +84,12 @@ fn fix_right_border(&mut self) {
             }
         }
 
-        self.fix_top();
+        self.fix_top(alloc.clone());
     }
 
     /// The symmetric clone of `fix_right_border`.
-    fn fix_left_border(&mut self) {
-        self.fix_top();
+    fn fix_left_border<A: Allocator + Clone>(&mut self, alloc: A) {
+        self.fix_top(alloc.clone());
 
         {
             let mut cur_node = self.borrow_mut();
@@ -93,7 +98,7 @@ fn fix_left_border(&mut self) {
                 let mut first_kv = node.first_kv().consider_for_balancing();
 
                 if first_kv.can_merge() {
-                    cur_node = first_kv.merge_tracking_child();
+                    cur_node = first_kv.merge_tracking_child(alloc.clone());
                 } else {
                     let left_len = first_kv.left_child_len();
                     // `MIN_LEN + 1` to avoid readjust if merge happens on the next level.
@@ -106,6 +111,6 @@ fn fix_left_border(&mut self) {
             }
         }
 
-        self.fix_top();
+        self.fix_top(alloc.clone());
     }
 }
@@ -182,6 +182,8 @@ mod boxed {
 #[cfg(target_has_atomic = "ptr")]
 pub mod task;
 #[cfg(test)]
+mod testing;
+#[cfg(test)]
 mod tests;
 pub mod vec;
 
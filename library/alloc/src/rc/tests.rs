@@ -7,6 +7,9 @@
 use std::mem::drop;
 use std::option::Option::{self, None, Some};
 use std::result::Result::{Err, Ok};
+use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+use crate::testing::alloc::CountingAlloc;
 
 #[test]
 fn test_clone() {
@@ -559,3 +562,32 @@ struct TwoRefs {
     assert_eq!(Rc::strong_count(&two_refs), 3);
     assert_eq!(Rc::weak_count(&two_refs), 2);
 }
+
+#[test]
+fn test_new_in() {
+    let live = AtomicUsize::new(0);
+    let alloc = CountingAlloc(&live);
+
+    let five = Rc::new_in(5, alloc);
+    let also_five = Rc::clone(&five);
+    let weak_five = Rc::downgrade(&five);
+    assert_eq!(live.load(SeqCst), 1);
+    assert_eq!(Rc::strong_count(&five), 2);
+    assert_eq!(*weak_five.upgrade().unwrap(), 5);
+
+    let debug: Rc<dyn fmt::Debug, _> = also_five;
+    assert_eq!(format!("{:?}", debug), "5");
+
+    drop(five);
+    drop(debug);
+    assert!(weak_five.upgrade().is_none());
+    assert_eq!(live.load(SeqCst), 1);
+
+    drop(weak_five);
+    assert_eq!(live.load(SeqCst), 0);
+
+    let unique = Rc::try_new_in(String::from("hello"), alloc).unwrap();
+    assert_eq!(*unique, "hello");
+    drop(unique);
+    assert_eq!(live.load(SeqCst), 0);
+}
@@ -14,6 +14,7 @@
 use std::sync::Mutex;
 use std::thread;
 
+use crate::testing::alloc::CountingAlloc;
 use crate::vec::Vec;
 
 struct Canary(*mut atomic::AtomicUsize);
@@ -618,3 +619,32 @@ struct TwoRefs {
     assert_eq!(Arc::strong_count(&two_refs), 3);
     assert_eq!(Arc::weak_count(&two_refs), 2);
 }
+
+#[test]
+fn test_new_in() {
+    let live = atomic::AtomicUsize::new(0);
+    let alloc = CountingAlloc(&live);
+
+    let five = Arc::new_in(5, alloc);
+    let also_five = Arc::clone(&five);
+    let weak_five = Arc::downgrade(&five);
+    assert_eq!(live.load(SeqCst), 1);
+    assert_eq!(Arc::strong_count(&five), 2);
+    assert_eq!(*weak_five.upgrade().unwrap(), 5);
+
+    let debug: Arc<dyn fmt::Debug, _> = also_five;
+    assert_eq!(format!("{:?}", debug), "5");
+
+    drop(five);
+    drop(debug);
+    assert!(weak_five.upgrade().is_none());
+    assert_eq!(live.load(SeqCst), 1);
+
+    drop(weak_five);
+    assert_eq!(live.load(SeqCst), 0);
+
+    let unique = Arc::try_new_in(String::from("hello"), alloc).unwrap();
+    assert_eq!(*unique, "hello");
+    drop(unique);
+    assert_eq!(live.load(SeqCst), 0);
+}
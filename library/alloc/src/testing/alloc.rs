@@ -0,0 +1,21 @@
+use crate::alloc::{AllocError, Allocator, Global, Layout};
+use core::ptr::NonNull;
+use core::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+/// An allocator that keeps track of how many blocks it has handed out and not
+/// gotten back yet, to check that a collection frees everything it allocated
+/// through its allocator.
+#[derive(Clone, Copy)]
+pub struct CountingAlloc<'a>(pub &'a AtomicUsize);
+
+unsafe impl Allocator for CountingAlloc<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.fetch_add(1, SeqCst);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.fetch_sub(1, SeqCst);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::binary_heap::{Drain, PeekMut};
+use std::alloc::System;
+use std::collections::binary_heap::{Drain, IndexedBinaryHeap, PeekMut};
 use std::collections::BinaryHeap;
 use std::iter::TrustedLen;
 use std::panic::{catch_unwind, AssertUnwindSafe};
@@ -271,6 +272,21 @@ fn test_from_iter() {
     }
 }
 
+#[test]
+fn test_new_in() {
+    let mut heap = BinaryHeap::with_capacity_in(4, System);
+    assert!(heap.capacity() >= 4);
+    heap.extend(vec![2, 8, 5, 1]);
+    heap.push(9);
+
+    let cloned: BinaryHeap<i32, System> = heap.clone();
+    assert_eq!(cloned.into_sorted_vec(), [1, 2, 5, 8, 9]);
+    assert_eq!(heap.drain_sorted().collect::<Vec<_>>(), [9, 8, 5, 2, 1]);
+
+    let vec: Vec<i32, System> = BinaryHeap::new_in(System).into_vec();
+    assert!(vec.is_empty());
+}
+
 #[test]
 fn test_drain() {
     let mut q: BinaryHeap<_> = [9, 8, 7, 6, 5, 4, 3, 2, 1].iter().cloned().collect();
@@ -377,6 +393,82 @@ fn test_extend_specialization() {
     assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
 }
 
+#[test]
+fn test_indexed_push_pop() {
+    let mut heap = IndexedBinaryHeap::new();
+    for &x in &[2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1] {
+        heap.push(x);
+    }
+    assert_eq!(heap.len(), 13);
+    assert_eq!(heap.peek(), Some(&10));
+
+    let mut sorted = Vec::new();
+    while let Some(x) = heap.pop() {
+        sorted.push(x);
+    }
+    assert_eq!(sorted, [10, 9, 8, 7, 6, 5, 4, 3, 2, 2, 1, 1, 0]);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn test_indexed_change_priority_and_remove() {
+    // Keep a model of what every handle should refer to and check the heap
+    // against it after each operation.
+    let mut heap = IndexedBinaryHeap::new();
+    let mut model = Vec::new();
+    for i in 0..100 {
+        let x = (i * 7919) % 101;
+        model.push((heap.push(x), Some(x)));
+    }
+
+    for i in 0..300 {
+        let (handle, expected) = &mut model[(i * 31) % 100];
+        if i % 3 == 0 {
+            assert_eq!(heap.remove(*handle), expected.take());
+        } else if expected.is_some() {
+            let x = (i * 13) % 211;
+            assert_eq!(heap.change_priority(*handle, x), expected.replace(x).unwrap());
+        }
+        for (handle, expected) in &model {
+            assert_eq!(heap.get(*handle), expected.as_ref());
+        }
+        assert_eq!(heap.peek(), model.iter().filter_map(|(_, x)| x.as_ref()).max());
+    }
+
+    let mut remaining: Vec<_> = model.iter().filter_map(|&(_, x)| x).collect();
+    remaining.sort_by(|a, b| b.cmp(a));
+    let mut popped = Vec::new();
+    while let Some(x) = heap.pop() {
+        popped.push(x);
+    }
+    assert_eq!(popped, remaining);
+    assert!(model.iter().all(|(handle, _)| !heap.contains(*handle)));
+}
+
+#[test]
+fn test_indexed_stale_handles() {
+    let mut heap = IndexedBinaryHeap::new();
+    let a = heap.push(1);
+    assert_eq!(heap.pop(), Some(1));
+
+    // `b` reuses the storage freed by `a`, but `a` must not refer to it.
+    let b = heap.push(2);
+    assert_ne!(a, b);
+    assert_eq!(heap.get(a), None);
+    assert_eq!(heap.remove(a), None);
+    assert_eq!(heap.get(b), Some(&2));
+
+    heap.clear();
+    assert!(!heap.contains(b));
+    let c = heap.push(3);
+    assert_eq!(heap.get(b), None);
+    assert_eq!(heap.get(c), Some(&3));
+
+    let result = catch_unwind(AssertUnwindSafe(|| heap.change_priority(b, 4)));
+    assert!(result.is_err());
+    assert_eq!(heap.get(c), Some(&3));
+}
+
 #[allow(dead_code)]
 fn assert_covariance() {
     fn drain<'new>(d: Drain<'static, &'static str>) -> Drain<'new, &'new str> {
@@ -3,6 +3,7 @@
 #![feature(cow_is_borrowed)]
 #![feature(const_cow_is_borrowed)]
 #![feature(drain_filter)]
+#![feature(linked_list_cursors)]
 #![feature(exact_size_is_empty)]
 #![feature(new_uninit)]
 #![feature(pattern)]
@@ -13,6 +14,7 @@
 #![feature(associated_type_bounds)]
 #![feature(binary_heap_into_iter_sorted)]
 #![feature(binary_heap_drain_sorted)]
+#![feature(binary_heap_indexed)]
 #![feature(slice_ptr_get)]
 #![feature(binary_heap_retain)]
 #![feature(inplace_iteration)]
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::collections::LinkedList;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
@@ -356,6 +357,28 @@ fn test_contains() {
     assert!(!l.contains(&3));
 }
 
+#[test]
+fn test_new_in() {
+    let mut l = LinkedList::new_in(System);
+    l.extend(vec![1, 2, 3, 4]);
+    l.push_front(0);
+
+    let tail = l.split_off(3);
+    assert!(l.iter().eq(&[0, 1, 2]));
+    assert!(tail.clone().into_iter().eq(vec![3, 4]));
+
+    let mut cursor = l.cursor_front_mut();
+    cursor.move_next();
+    let front = cursor.split_before();
+    assert!(front.iter().eq(&[0]));
+    assert!(l.iter().eq(&[1, 2]));
+
+    l.clear();
+    assert!(l.is_empty());
+    l.push_back(5);
+    assert_eq!(l.pop_back(), Some(5));
+}
+
 #[test]
 fn drain_filter_empty() {
     let mut list: LinkedList<i32> = LinkedList::new();
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::collections::TryReserveError::*;
@@ -300,6 +301,24 @@ fn test_split_off_unicode() {
     assert_eq!(nihon.capacity(), orig_capacity);
 }
 
+#[test]
+fn test_new_in() {
+    let mut s = String::with_capacity_in(4, System);
+    assert!(s.capacity() >= 4);
+    s.push_str("hello, ");
+    s.push_str("world");
+
+    let cloned: String<System> = s.clone();
+    assert_eq!(cloned.as_str(), "hello, world");
+
+    let world = s.split_off(7);
+    assert_eq!(s.as_str(), "hello, ");
+    assert_eq!(world.into_bytes(), b"world");
+
+    let boxed = String::new_in(System).into_boxed_str();
+    assert_eq!(&*boxed, "");
+}
+
 #[test]
 fn test_str_truncate() {
     let mut s = String::from("12345");
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::collections::TryReserveError::*;
 use std::collections::{vec_deque::Drain, VecDeque};
 use std::fmt::Debug;
@@ -190,6 +191,24 @@ fn test_with_capacity() {
     assert_eq!(d.len(), 1);
 }
 
+#[test]
+fn test_with_capacity_in() {
+    let mut d = VecDeque::with_capacity_in(3, System);
+    d.push_back(2);
+    d.push_front(1);
+    d.extend(vec![3, 4, 5]);
+    assert!(d.capacity() >= 5);
+
+    let tail = d.split_off(3);
+    assert_eq!(d, [1, 2, 3]);
+    assert_eq!(tail.clone(), [4, 5]);
+
+    let v: Vec<i32, System> = d.into();
+    assert_eq!(v, [1, 2, 3]);
+    let d = VecDeque::from(v);
+    assert_eq!(d.into_iter().rev().collect::<Vec<_>>(), [3, 2, 1]);
+}
+
 #[test]
 fn test_with_capacity_non_power_two() {
     let mut d3 = VecDeque::with_capacity(3);
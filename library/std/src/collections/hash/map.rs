@@ -708,6 +708,91 @@ pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
         map_entry(self.base.rustc_entry(key))
     }
 
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation, looking it up by reference.
+    ///
+    /// Unlike [`entry`], this does not need an owned key: `key` is only
+    /// converted into a `K` if the entry is vacant and a value is inserted.
+    /// This avoids allocating, for example, a `String` for every lookup into a
+    /// `HashMap<String, _>` when the key is usually already present.
+    ///
+    /// [`entry`]: HashMap::entry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut words: HashMap<String, usize> = HashMap::new();
+    ///
+    /// for word in "the quick fox jumps over the lazy dog".split(' ') {
+    ///     *words.entry_ref(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(words["the"], 2);
+    /// assert_eq!(words["fox"], 1);
+    /// assert_eq!(words.get("cat"), None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn entry_ref<'a, 'b, Q: ?Sized>(&'a mut self, key: &'b Q) -> EntryRef<'a, 'b, K, Q, V, S>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq,
+    {
+        let hash = make_hash(self.base.hasher(), key);
+        self.entry_ref_hashed_nocheck(hash, key)
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation, using a hash of the key that the caller has already
+    /// computed.
+    ///
+    /// This behaves like [`entry_ref`], but skips hashing `key`. `hash` must
+    /// be the result of hashing `key` with a hasher obtained from this map's
+    /// [`BuildHasher`], as in
+    /// `{ let mut h = map.hasher().build_hasher(); key.hash(&mut h); h.finish() }`.
+    /// Passing any other value will not cause undefined behavior, but the
+    /// lookup may miss an existing entry and later lookups may miss the
+    /// inserted one.
+    ///
+    /// [`entry_ref`]: HashMap::entry_ref
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::hash::{BuildHasher, Hash, Hasher};
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// let mut hasher = map.hasher().build_hasher();
+    /// "poneyland".hash(&mut hasher);
+    /// let hash = hasher.finish();
+    ///
+    /// *map.entry_ref_hashed_nocheck(hash, "poneyland").or_insert(3) += 1;
+    /// *map.entry_ref_hashed_nocheck(hash, "poneyland").or_insert(3) += 1;
+    /// assert_eq!(map["poneyland"], 5);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn entry_ref_hashed_nocheck<'a, 'b, Q: ?Sized>(
+        &'a mut self,
+        hash: u64,
+        key: &'b Q,
+    ) -> EntryRef<'a, 'b, K, Q, V, S>
+    where
+        K: Borrow<Q>,
+        Q: Eq,
+    {
+        match self.base.raw_entry_mut().from_key_hashed_nocheck(hash, key) {
+            base::RawEntryMut::Occupied(base) => EntryRef::Occupied(OccupiedEntryRef { base }),
+            base::RawEntryMut::Vacant(base) => EntryRef::Vacant(VacantEntryRef { hash, key, base }),
+        }
+    }
+
     /// Returns a reference to the value corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type, but
@@ -1852,6 +1937,65 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// A view into a single entry in a map, which may either be vacant or occupied,
+/// looked up by a borrowed form of the key.
+///
+/// This `enum` is constructed from the [`entry_ref`] and
+/// [`entry_ref_hashed_nocheck`] methods on [`HashMap`].
+///
+/// [`entry_ref`]: HashMap::entry_ref
+/// [`entry_ref_hashed_nocheck`]: HashMap::entry_ref_hashed_nocheck
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+pub enum EntryRef<'a, 'b, K: 'a, Q: ?Sized + 'b, V: 'a, S: 'a> {
+    /// An occupied entry.
+    Occupied(OccupiedEntryRef<'a, K, V, S>),
+    /// A vacant entry.
+    Vacant(VacantEntryRef<'a, 'b, K, Q, V, S>),
+}
+
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+impl<K: Debug, Q: ?Sized + Debug, V: Debug, S> Debug for EntryRef<'_, '_, K, Q, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            EntryRef::Vacant(ref v) => f.debug_tuple("EntryRef").field(v).finish(),
+            EntryRef::Occupied(ref o) => f.debug_tuple("EntryRef").field(o).finish(),
+        }
+    }
+}
+
+/// A view into an occupied entry in a `HashMap`.
+/// It is part of the [`EntryRef`] enum.
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+pub struct OccupiedEntryRef<'a, K: 'a, V: 'a, S: 'a> {
+    base: base::RawOccupiedEntryMut<'a, K, V, S>,
+}
+
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+impl<K: Debug, V: Debug, S> Debug for OccupiedEntryRef<'_, K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedEntryRef")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+/// A view into a vacant entry in a `HashMap`.
+/// It is part of the [`EntryRef`] enum.
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+pub struct VacantEntryRef<'a, 'b, K: 'a, Q: ?Sized + 'b, V: 'a, S: 'a> {
+    hash: u64,
+    key: &'b Q,
+    base: base::RawVacantEntryMut<'a, K, V, S>,
+}
+
+#[unstable(feature = "hash_entry_ref", issue = "none")]
+impl<K, Q: ?Sized + Debug, V, S> Debug for VacantEntryRef<'_, '_, K, Q, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VacantEntryRef").field(&self.key).finish()
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
     type Item = (&'a K, &'a V);
@@ -2689,6 +2833,417 @@ fn insert_entry(self, value: V) -> OccupiedEntry<'a, K, V> {
     }
 }
 
+impl<'a, 'b, K, Q: ?Sized, V, S> EntryRef<'a, 'b, K, Q, V, S> {
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// map.entry_ref("poneyland").or_insert(3);
+    /// assert_eq!(map["poneyland"], 3);
+    ///
+    /// *map.entry_ref("poneyland").or_insert(10) *= 2;
+    /// assert_eq!(map["poneyland"], 6);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_insert(self, default: V) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, String> = HashMap::new();
+    /// let s = "hoho".to_string();
+    ///
+    /// map.entry_ref("poneyland").or_insert_with(|| s);
+    ///
+    /// assert_eq!(map["poneyland"], "hoho".to_string());
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function.
+    /// This method allows for generating key-derived values for insertion by providing the default
+    /// function a reference to the key that was passed to the `entry_ref` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, usize> = HashMap::new();
+    ///
+    /// map.entry_ref("poneyland").or_insert_with_key(|key| key.chars().count());
+    ///
+    /// assert_eq!(map["poneyland"], 9);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_insert_with_key<F: FnOnce(&Q) -> V>(self, default: F) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => {
+                let value = default(entry.key);
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// assert_eq!(map.entry_ref("poneyland").key(), "poneyland");
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn key(&self) -> &Q
+    where
+        K: Borrow<Q>,
+    {
+        match *self {
+            EntryRef::Occupied(ref entry) => entry.key().borrow(),
+            EntryRef::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// map.entry_ref("poneyland").and_modify(|e| *e += 1).or_insert(42);
+    /// assert_eq!(map["poneyland"], 42);
+    ///
+    /// map.entry_ref("poneyland").and_modify(|e| *e += 1).or_insert(42);
+    /// assert_eq!(map["poneyland"], 43);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        match self {
+            EntryRef::Occupied(mut entry) => {
+                f(entry.get_mut());
+                EntryRef::Occupied(entry)
+            }
+            EntryRef::Vacant(entry) => EntryRef::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, 'b, K, Q: ?Sized, V: Default, S> EntryRef<'a, 'b, K, Q, V, S> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map: HashMap<String, Option<u32>> = HashMap::new();
+    /// map.entry_ref("poneyland").or_default();
+    ///
+    /// assert_eq!(map["poneyland"], None);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn or_default(self) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(Default::default()),
+        }
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntryRef<'a, K, V, S> {
+    /// Gets a reference to the key in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.key(), "poneyland");
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn key(&self) -> &K {
+        self.base.key()
+    }
+
+    /// Gets a reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.get(), &12);
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn get(&self) -> &V {
+        self.base.get()
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference to the `OccupiedEntryRef` which may outlive the
+    /// destruction of the `EntryRef` value, see [`into_mut`].
+    ///
+    /// [`into_mut`]: Self::into_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// assert_eq!(map["poneyland"], 12);
+    /// if let EntryRef::Occupied(mut o) = map.entry_ref("poneyland") {
+    ///     *o.get_mut() += 10;
+    ///     assert_eq!(*o.get(), 22);
+    ///
+    ///     // We can use the same EntryRef multiple times.
+    ///     *o.get_mut() += 2;
+    /// }
+    ///
+    /// assert_eq!(map["poneyland"], 24);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn get_mut(&mut self) -> &mut V {
+        self.base.get_mut()
+    }
+
+    /// Converts the `OccupiedEntryRef` into a mutable reference to the value in
+    /// the entry with a lifetime bound to the map itself.
+    ///
+    /// If you need multiple references to the `OccupiedEntryRef`, see
+    /// [`get_mut`].
+    ///
+    /// [`get_mut`]: Self::get_mut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// assert_eq!(map["poneyland"], 12);
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     *o.into_mut() += 10;
+    /// }
+    ///
+    /// assert_eq!(map["poneyland"], 22);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn into_mut(self) -> &'a mut V {
+        self.base.into_mut()
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// if let EntryRef::Occupied(mut o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.insert(15), 12);
+    /// }
+    ///
+    /// assert_eq!(map["poneyland"], 15);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn insert(&mut self, value: V) -> V {
+        self.base.insert(value)
+    }
+
+    /// Takes the value out of the entry, and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     assert_eq!(o.remove(), 12);
+    /// }
+    ///
+    /// assert_eq!(map.contains_key("poneyland"), false);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn remove(self) -> V {
+        self.base.remove()
+    }
+
+    /// Take the ownership of the key and value from the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// if let EntryRef::Occupied(o) = map.entry_ref("poneyland") {
+    ///     // We delete the entry from the map.
+    ///     assert_eq!(o.remove_entry(), ("poneyland".to_owned(), 12));
+    /// }
+    ///
+    /// assert_eq!(map.contains_key("poneyland"), false);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn remove_entry(self) -> (K, V) {
+        self.base.remove_entry()
+    }
+}
+
+impl<'a, 'b, K, Q: ?Sized, V, S> VacantEntryRef<'a, 'b, K, Q, V, S> {
+    /// Gets a reference to the key that was used to look up the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// if let EntryRef::Vacant(v) = map.entry_ref("poneyland") {
+    ///     assert_eq!(v.key(), "poneyland");
+    /// }
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn key(&self) -> &'b Q {
+        self.key
+    }
+
+    /// Converts the borrowed key into an owned `K`, inserts it into the map
+    /// with `value`, and returns a mutable reference to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(hash_entry_ref)]
+    /// use std::collections::HashMap;
+    /// use std::collections::hash_map::EntryRef;
+    ///
+    /// let mut map: HashMap<String, u32> = HashMap::new();
+    ///
+    /// if let EntryRef::Vacant(o) = map.entry_ref("poneyland") {
+    ///     o.insert(37);
+    /// }
+    /// assert_eq!(map["poneyland"], 37);
+    /// ```
+    #[inline]
+    #[unstable(feature = "hash_entry_ref", issue = "none")]
+    pub fn insert(self, value: V) -> &'a mut V
+    where
+        K: Hash + From<&'b Q>,
+        S: BuildHasher,
+    {
+        self.base.insert_hashed_nocheck(self.hash, K::from(self.key), value).1
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K, V, S> FromIterator<(K, V)> for HashMap<K, V, S>
 where
@@ -2918,6 +3473,13 @@ fn map_raw_entry<'a, K: 'a, V: 'a, S: 'a>(
     }
 }
 
+#[inline]
+fn make_hash<Q: Hash + ?Sized, S: BuildHasher>(hash_builder: &S, val: &Q) -> u64 {
+    let mut state = hash_builder.build_hasher();
+    val.hash(&mut state);
+    state.finish()
+}
+
 #[allow(dead_code)]
 fn assert_covariance() {
     fn map_key<'new>(v: HashMap<&'static str, u8>) -> HashMap<&'new str, u8> {
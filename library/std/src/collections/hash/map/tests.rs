@@ -804,6 +804,91 @@ fn test_vacant_entry_key() {
     assert_eq!(a[key], value);
 }
 
+#[test]
+fn test_entry_ref() {
+    use super::EntryRef;
+    use crate::sync::atomic::{AtomicUsize, Ordering};
+
+    // Counts how often a borrowed key is converted into an owned one.
+    static CONVERSIONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq, Hash, Debug)]
+    struct Key(String);
+
+    impl crate::borrow::Borrow<str> for Key {
+        fn borrow(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl From<&str> for Key {
+        fn from(s: &str) -> Key {
+            CONVERSIONS.fetch_add(1, Ordering::SeqCst);
+            Key(s.to_owned())
+        }
+    }
+
+    let mut map: HashMap<Key, usize> = HashMap::new();
+    for word in "a b a c a b".split(' ') {
+        *map.entry_ref(word).or_insert(0) += 1;
+    }
+    assert_eq!(CONVERSIONS.load(Ordering::SeqCst), 3);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["a"], 3);
+    assert_eq!(map["b"], 2);
+    assert_eq!(map["c"], 1);
+
+    match map.entry_ref("b") {
+        EntryRef::Vacant(_) => unreachable!(),
+        EntryRef::Occupied(mut e) => {
+            assert_eq!(e.key(), &Key("b".to_owned()));
+            assert_eq!(e.insert(10), 2);
+            assert_eq!(e.remove_entry(), (Key("b".to_owned()), 10));
+        }
+    }
+    assert_eq!(map.get("b"), None);
+
+    match map.entry_ref("d") {
+        EntryRef::Occupied(_) => unreachable!(),
+        EntryRef::Vacant(e) => {
+            assert_eq!(e.key(), "d");
+            *e.insert(4) += 1;
+        }
+    }
+    assert_eq!(map["d"], 5);
+    assert_eq!(CONVERSIONS.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn test_entry_ref_hashed_nocheck() {
+    use crate::hash::{BuildHasher, Hash, Hasher};
+
+    let mut map: HashMap<String, i32> = HashMap::new();
+    let hash = |map: &HashMap<String, i32>, k: &str| -> u64 {
+        let mut hasher = map.hasher().build_hasher();
+        k.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    for i in 0..100 {
+        let key = i.to_string();
+        let h = hash(&map, &key);
+        *map.entry_ref_hashed_nocheck(h, &*key).or_insert_with_key(|k| k.len() as i32) += i;
+    }
+    assert_eq!(map.len(), 100);
+
+    // Entries inserted with a precomputed hash are found by ordinary lookups,
+    // and vice versa.
+    for i in 0..100 {
+        let key = i.to_string();
+        assert_eq!(map.get(&key), Some(&(key.len() as i32 + i)));
+        let h = hash(&map, &key);
+        assert_eq!(map.entry_ref_hashed_nocheck(h, &*key).key(), &*key);
+        assert_eq!(*map.entry_ref_hashed_nocheck(h, &*key).or_default(), key.len() as i32 + i);
+    }
+    assert_eq!(map.len(), 100);
+}
+
 #[test]
 fn test_retain() {
     let mut map: HashMap<i32, i32> = (0..100).map(|x| (x, x * 10)).collect();
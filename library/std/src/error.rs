@@ -113,19 +113,56 @@ fn type_id(&self, _: private::Internal) -> TypeId
         TypeId::of::<Self>()
     }
 
-    /// Returns a stack backtrace, if available, of where this error occurred.
+    /// Provides type based access to context intended for error reports.
     ///
-    /// This function allows inspecting the location, in code, of where an error
-    /// happened. The returned `Backtrace` contains information about the stack
-    /// trace of the OS thread of execution of where the error originated from.
+    /// Used in conjunction with [`Demand::provide_value`] and [`Demand::provide_ref`] to extract
+    /// references to member variables from `dyn Error` trait objects, which callers retrieve
+    /// with [`request_ref`] and [`request_value`]. A [`Backtrace`] of where the error
+    /// occurred is provided this way as well.
     ///
-    /// Note that not all errors contain a `Backtrace`. Also note that a
-    /// `Backtrace` may actually be empty. For more information consult the
-    /// `Backtrace` type itself.
-    #[unstable(feature = "backtrace", issue = "53487")]
-    fn backtrace(&self) -> Option<&Backtrace> {
-        None
-    }
+    /// An implementation offers everything it has; the [`Demand`] only keeps a value
+    /// of the requested type and ignores the others.
+    ///
+    /// [`request_ref`]: ../../std/error/trait.Error.html#method.request_ref
+    /// [`request_value`]: ../../std/error/trait.Error.html#method.request_value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace)]
+    /// #![feature(error_generic_member_access)]
+    /// use std::backtrace::Backtrace;
+    /// use std::error::{Demand, Error};
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct RequestError {
+    ///     status: u16,
+    ///     backtrace: Backtrace,
+    /// }
+    ///
+    /// impl fmt::Display for RequestError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "request failed with status {}", self.status)
+    ///     }
+    /// }
+    ///
+    /// impl Error for RequestError {
+    ///     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+    ///         demand.provide_ref::<Backtrace>(&self.backtrace).provide_value(|| self.status);
+    ///     }
+    /// }
+    ///
+    /// let error = RequestError { status: 404, backtrace: Backtrace::capture() };
+    /// let dyn_error = &error as &dyn Error;
+    ///
+    /// assert_eq!(dyn_error.request_value::<u16>(), Some(404));
+    /// assert!(dyn_error.request_ref::<Backtrace>().is_some());
+    /// assert!(dyn_error.request_ref::<str>().is_none());
+    /// ```
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    #[allow(unused_variables)]
+    fn provide<'a>(&'a self, demand: &mut Demand<'a>) {}
 
     /// ```
     /// if let Err(e) = "xc".parse::<u32>() {
@@ -158,6 +195,122 @@ mod private {
     pub struct Internal;
 }
 
+/// A request for a value of a particular type, answered by [`Error::provide`].
+///
+/// A `Demand` is created by [`request_ref`] or [`request_value`] on a `dyn Error`, which
+/// hand it to the error's `provide` method. It keeps the first value of the type it was
+/// created for and discards everything else.
+///
+/// [`request_ref`]: ../../std/error/trait.Error.html#method.request_ref
+/// [`request_value`]: ../../std/error/trait.Error.html#method.request_value
+#[unstable(feature = "error_generic_member_access", issue = "none")]
+#[repr(transparent)]
+pub struct Demand<'a>(dyn Erased<'a> + 'a);
+
+impl<'a> Demand<'a> {
+    fn new<'b>(erased: &'b mut (dyn Erased<'a> + 'a)) -> &'b mut Demand<'a> {
+        // SAFETY: transmuting `&mut (dyn Erased<'a> + 'a)` to `&mut Demand<'a>` is safe since
+        // `Demand` is repr(transparent).
+        unsafe { &mut *(erased as *mut dyn Erased<'a> as *mut Demand<'a>) }
+    }
+
+    /// Provides a value of type `T`, if one was requested.
+    ///
+    /// `fulfil` is only called when a value of type `T` is actually being requested and
+    /// none was provided before, so computing it can be arbitrarily expensive.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn provide_value<T, F>(&mut self, fulfil: F) -> &mut Demand<'a>
+    where
+        T: 'static,
+        F: FnOnce() -> T,
+    {
+        if let Some(res @ TaggedOption(None)) = self.0.downcast_mut::<tags::Value<T>>() {
+            res.0 = Some(fulfil());
+        }
+        self
+    }
+
+    /// Provides a reference of type `&'a T`, if one was requested.
+    ///
+    /// `T` may be unsized, e.g. `str` or a trait object.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn provide_ref<T: ?Sized + 'static>(&mut self, value: &'a T) -> &mut Demand<'a> {
+        if let Some(res @ TaggedOption(None)) = self.0.downcast_mut::<tags::Ref<T>>() {
+            res.0 = Some(value);
+        }
+        self
+    }
+}
+
+#[unstable(feature = "error_generic_member_access", issue = "none")]
+impl fmt::Debug for Demand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Demand { .. }")
+    }
+}
+
+/// Type tags describing what a `Demand` asks for.
+///
+/// The tags themselves are `'static`, so they have a `TypeId` even when the type they stand
+/// for borrows from the error. A `Demand` and everything provided to it share the lifetime
+/// `'a`, which makes comparing the tags enough to identify the requested type.
+mod tags {
+    use crate::marker::PhantomData;
+
+    /// A type-level tag standing for the type `Self::Reified`.
+    pub trait Type<'a>: Sized + 'static {
+        type Reified: 'a;
+    }
+
+    /// Tag for an owned value of type `T`.
+    pub struct Value<T: 'static>(PhantomData<T>);
+
+    impl<'a, T: 'static> Type<'a> for Value<T> {
+        type Reified = T;
+    }
+
+    /// Tag for a reference `&'a T`.
+    pub struct Ref<T: ?Sized + 'static>(PhantomData<T>);
+
+    impl<'a, T: ?Sized + 'static> Type<'a> for Ref<T> {
+        type Reified = &'a T;
+    }
+}
+
+/// The slot a `Demand` writes the provided value into.
+#[repr(transparent)]
+struct TaggedOption<'a, I: tags::Type<'a>>(Option<I::Reified>);
+
+impl<'a, I: tags::Type<'a>> TaggedOption<'a, I> {
+    fn as_demand(&mut self) -> &mut Demand<'a> {
+        Demand::new(self as &mut (dyn Erased<'a> + 'a))
+    }
+}
+
+/// Object safe version of `TaggedOption`, which is what a `Demand` wraps.
+trait Erased<'a>: 'a {
+    fn tag_id(&self) -> TypeId;
+}
+
+impl<'a, I: tags::Type<'a>> Erased<'a> for TaggedOption<'a, I> {
+    fn tag_id(&self) -> TypeId {
+        TypeId::of::<I>()
+    }
+}
+
+impl<'a> dyn Erased<'a> + 'a {
+    /// Returns the `TaggedOption` behind this trait object if it was created for the tag `I`.
+    #[inline]
+    fn downcast_mut<I: tags::Type<'a>>(&mut self) -> Option<&mut TaggedOption<'a, I>> {
+        if self.tag_id() == TypeId::of::<I>() {
+            // SAFETY: just checked whether we're pointing to a `TaggedOption<'a, I>`.
+            Some(unsafe { &mut *(self as *mut Self as *mut TaggedOption<'a, I>) })
+        } else {
+            None
+        }
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, E: Error + 'a> From<E> for Box<dyn Error + 'a> {
     /// Converts a type of [`Error`] into a box of dyn [`Error`].
@@ -484,6 +637,10 @@ fn cause(&self) -> Option<&dyn Error> {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         Error::source(&**self)
     }
+
+    fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+        Error::provide(&**self, demand)
+    }
 }
 
 #[stable(feature = "fmt_error", since = "1.11.0")]
@@ -568,6 +725,68 @@ pub fn downcast_mut<T: Error + 'static>(&mut self) -> Option<&mut T> {
             None
         }
     }
+
+    /// Requests a reference of type `T` from this error, as offered by [`Error::provide`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(error_generic_member_access)]
+    /// use std::error::{Demand, Error};
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError {
+    ///     path: String,
+    /// }
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "invalid configuration in {}", self.path)
+    ///     }
+    /// }
+    ///
+    /// impl Error for ConfigError {
+    ///     fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+    ///         demand.provide_ref::<str>(&self.path);
+    ///     }
+    /// }
+    ///
+    /// let error: Box<dyn Error> = Box::new(ConfigError { path: "app.toml".to_string() });
+    /// assert_eq!(error.request_ref::<str>(), Some("app.toml"));
+    /// assert_eq!(error.request_ref::<String>(), None);
+    /// ```
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn request_ref<T: ?Sized + 'static>(&self) -> Option<&T> {
+        let mut tagged = TaggedOption::<tags::Ref<T>>(None);
+        self.provide(tagged.as_demand());
+        tagged.0
+    }
+
+    /// Requests a value of type `T` from this error, as offered by [`Error::provide`].
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn request_value<T: 'static>(&self) -> Option<T> {
+        let mut tagged = TaggedOption::<tags::Value<T>>(None);
+        self.provide(tagged.as_demand());
+        tagged.0
+    }
+
+    /// Returns a stack backtrace, if available, of where this error occurred.
+    ///
+    /// This function allows inspecting the location, in code, of where an error
+    /// happened. The returned `Backtrace` contains information about the stack
+    /// trace of the OS thread of execution of where the error originated from.
+    ///
+    /// Errors offer their `Backtrace` through [`Error::provide`], so this is
+    /// the same as `self.request_ref::<Backtrace>()`.
+    ///
+    /// Note that not all errors contain a `Backtrace`. Also note that a
+    /// `Backtrace` may actually be empty. For more information consult the
+    /// `Backtrace` type itself.
+    #[unstable(feature = "backtrace", issue = "53487")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.request_ref::<Backtrace>()
+    }
 }
 
 impl dyn Error + 'static + Send {
@@ -591,6 +810,24 @@ pub fn downcast_ref<T: Error + 'static>(&self) -> Option<&T> {
     pub fn downcast_mut<T: Error + 'static>(&mut self) -> Option<&mut T> {
         <dyn Error + 'static>::downcast_mut::<T>(self)
     }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn request_ref<T: ?Sized + 'static>(&self) -> Option<&T> {
+        <dyn Error + 'static>::request_ref::<T>(self)
+    }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn request_value<T: 'static>(&self) -> Option<T> {
+        <dyn Error + 'static>::request_value::<T>(self)
+    }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "backtrace", issue = "53487")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        <dyn Error + 'static>::backtrace(self)
+    }
 }
 
 impl dyn Error + 'static + Send + Sync {
@@ -614,6 +851,24 @@ pub fn downcast_ref<T: Error + 'static>(&self) -> Option<&T> {
     pub fn downcast_mut<T: Error + 'static>(&mut self) -> Option<&mut T> {
         <dyn Error + 'static>::downcast_mut::<T>(self)
     }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn request_ref<T: ?Sized + 'static>(&self) -> Option<&T> {
+        <dyn Error + 'static>::request_ref::<T>(self)
+    }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "error_generic_member_access", issue = "none")]
+    pub fn request_value<T: 'static>(&self) -> Option<T> {
+        <dyn Error + 'static>::request_value::<T>(self)
+    }
+
+    /// Forwards to the method defined on the type `dyn Error`.
+    #[unstable(feature = "backtrace", issue = "53487")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        <dyn Error + 'static>::backtrace(self)
+    }
 }
 
 impl dyn Error {
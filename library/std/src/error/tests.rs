@@ -1,4 +1,5 @@
-use super::Error;
+use super::{Demand, Error};
+use crate::backtrace::Backtrace;
 use crate::fmt;
 
 #[derive(Debug, PartialEq)]
@@ -35,3 +36,61 @@ fn downcasting() {
         Err(e) => assert_eq!(*e.downcast::<A>().unwrap(), A),
     }
 }
+
+#[derive(Debug)]
+struct Provider {
+    name: String,
+    code: u16,
+    backtrace: Backtrace,
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Provider")
+    }
+}
+
+impl Error for Provider {
+    fn provide<'a>(&'a self, demand: &mut Demand<'a>) {
+        demand
+            .provide_ref::<str>(&self.name)
+            .provide_value(|| self.code)
+            // only the first value of each type is kept
+            .provide_value(|| 0u16)
+            .provide_ref::<Backtrace>(&self.backtrace);
+    }
+}
+
+fn provider() -> Provider {
+    Provider { name: "provider".to_string(), code: 404, backtrace: Backtrace::disabled() }
+}
+
+#[test]
+fn request_ref_and_value() {
+    let provider = provider();
+    let err = &provider as &(dyn Error + 'static);
+    assert_eq!(err.request_ref::<str>(), Some("provider"));
+    assert_eq!(err.request_value::<u16>(), Some(404));
+    assert!(err.request_ref::<Backtrace>().is_some());
+    assert!(err.backtrace().is_some());
+
+    assert_eq!(err.request_ref::<String>(), None);
+    assert_eq!(err.request_ref::<u16>(), None);
+    assert_eq!(err.request_value::<u32>(), None);
+}
+
+#[test]
+fn request_without_provide() {
+    let a = &A as &(dyn Error + 'static);
+    assert_eq!(a.request_ref::<str>(), None);
+    assert_eq!(a.request_value::<u16>(), None);
+    assert!(a.backtrace().is_none());
+}
+
+#[test]
+fn request_through_box() {
+    let err: Box<dyn Error + Send + Sync> = Box::new(Box::new(provider()));
+    assert_eq!(err.request_ref::<str>(), Some("provider"));
+    assert_eq!(err.request_value::<u16>(), Some(404));
+    assert!(err.backtrace().is_some());
+}
@@ -13,7 +13,7 @@
 
 use crate::ffi::OsString;
 use crate::fmt;
-use crate::io::{self, Initializer, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use crate::io::{self, Initializer, IoSlice, IoSliceMut, Read, ReadBuf, Seek, SeekFrom, Write};
 use crate::path::{Path, PathBuf};
 use crate::sys::fs as fs_imp;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
@@ -195,6 +195,47 @@ pub struct DirBuilder {
     recursive: bool,
 }
 
+/// A handle to an open directory, for working with the files inside of it.
+///
+/// Paths passed to the methods of a `Dir` are resolved relative to the
+/// directory itself rather than to the current working directory, using the
+/// descriptor-relative `*at` family of functions on Unix. The handle keeps
+/// referring to the same directory even if that directory is renamed or moved
+/// after it has been opened.
+///
+/// Absolute paths, `..` components and symbolic links can still lead out of
+/// the directory, so a `Dir` does not sandbox anything on its own. Combined
+/// with an operating system facility that forbids path lookups outside of
+/// open directories, it allows programs to confine their file system access
+/// without resorting to raw `libc` calls.
+///
+/// # Platform-specific behavior
+///
+/// This type is currently only implemented on Unix platforms other than
+/// REDOX and VxWorks. Opening a directory fails on all other platforms, as
+/// well as on macOS before version 10.10.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(dir_handle)]
+/// use std::fs::{Dir, OpenOptions};
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let dir = Dir::open("/var/lib/app")?;
+///     dir.create_dir("cache")?;
+///     let mut file = dir.open_file("cache/index", OpenOptions::new().write(true).create(true))?;
+///     file.write_all(b"hello")?;
+///     dir.rename("cache/index", &dir, "index")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "dir_handle", issue = "none")]
+pub struct Dir {
+    inner: fs_imp::Dir,
+}
+
 /// Indicates how large a buffer to pre-allocate before reading the entire file.
 fn initial_buffer_size(file: &File) -> usize {
     // Allocate one extra byte so the buffer doesn't need to grow before the
@@ -616,6 +657,10 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     #[inline]
     fn is_read_vectored(&self) -> bool {
         self.inner.is_read_vectored()
@@ -662,6 +707,10 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     #[inline]
     fn is_read_vectored(&self) -> bool {
         self.inner.is_read_vectored()
@@ -1971,13 +2020,17 @@ pub fn remove_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
 ///
 /// # Platform-specific behavior
 ///
-/// This function currently corresponds to `opendir`, `lstat`, `rm` and `rmdir` functions on Unix
-/// and the `FindFirstFile`, `GetFileAttributesEx`, `DeleteFile`, and `RemoveDirectory` functions
-/// on Windows.
+/// This function currently corresponds to `openat`, `fdopendir`, `unlinkat` and `lstat` functions
+/// on Unix (except for macOS before version 10.10, REDOX and VxWorks) and the `FindFirstFile`,
+/// `GetFileAttributesEx`, `DeleteFile`, and `RemoveDirectory` functions on Windows.
 /// Note that, this [may change in the future][changes].
 ///
 /// [changes]: io#platform-specific-behavior
 ///
+/// On Windows, macOS before version 10.10, REDOX and VxWorks this function is not protected against
+/// time-of-check to time-of-use (TOCTOU) race conditions, and should not be used in
+/// security-sensitive code on those platforms.
+///
 /// # Errors
 ///
 /// See [`fs::remove_file`] and [`fs::remove_dir`].
@@ -2201,3 +2254,97 @@ fn as_inner_mut(&mut self) -> &mut fs_imp::DirBuilder {
         &mut self.inner
     }
 }
+
+impl Dir {
+    /// Opens the directory at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` does not exist, is not a
+    /// directory, or cannot be opened for reading.
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dir> {
+        fs_imp::Dir::open(path.as_ref()).map(|inner| Dir { inner })
+    }
+
+    /// Opens a file relative to this directory with the given options.
+    ///
+    /// This is the equivalent of [`OpenOptions::open`] for a path relative to
+    /// this directory.
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn open_file<P: AsRef<Path>>(&self, path: P, options: &OpenOptions) -> io::Result<File> {
+        self.inner.open_file(path.as_ref(), &options.0).map(|inner| File { inner })
+    }
+
+    /// Creates a new, empty directory relative to this directory.
+    ///
+    /// This is the equivalent of [`fs::create_dir`] for a path relative to
+    /// this directory.
+    ///
+    /// [`fs::create_dir`]: create_dir
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn create_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.create_dir(path.as_ref())
+    }
+
+    /// Removes a file relative to this directory.
+    ///
+    /// This is the equivalent of [`fs::remove_file`] for a path relative to
+    /// this directory.
+    ///
+    /// [`fs::remove_file`]: remove_file
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn remove_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.inner.remove_file(path.as_ref())
+    }
+
+    /// Renames a file or directory relative to this directory to a new name
+    /// relative to `to_dir`, replacing the original file if `to` already
+    /// exists.
+    ///
+    /// `to_dir` may be this directory itself. This is the equivalent of
+    /// [`fs::rename`] for paths relative to directory handles.
+    ///
+    /// [`fs::rename`]: rename
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        from: P,
+        to_dir: &Dir,
+        to: Q,
+    ) -> io::Result<()> {
+        self.inner.rename(from.as_ref(), &to_dir.inner, to.as_ref())
+    }
+
+    /// Queries the metadata of a file relative to this directory, following
+    /// symbolic links.
+    ///
+    /// This is the equivalent of [`fs::metadata`] for a path relative to this
+    /// directory.
+    ///
+    /// [`fs::metadata`]: metadata
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn metadata<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.inner.metadata(path.as_ref()).map(Metadata)
+    }
+
+    /// Returns an iterator over the entries within a directory relative to
+    /// this directory.
+    ///
+    /// This is the equivalent of [`fs::read_dir`] for a path relative to this
+    /// directory. The [`DirEntry::path`] of each entry is `path` joined with
+    /// the entry's file name, so it is relative to this directory as well.
+    ///
+    /// [`fs::read_dir`]: read_dir
+    #[unstable(feature = "dir_handle", issue = "none")]
+    pub fn read_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<ReadDir> {
+        self.inner.read_dir(path.as_ref()).map(ReadDir)
+    }
+}
+
+#[unstable(feature = "dir_handle", issue = "none")]
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
@@ -571,6 +571,136 @@ fn recursive_rmdir() {
     assert!(canary.exists());
 }
 
+#[test]
+// Windows and REDOX fall back to a path-based implementation, which is
+// vulnerable to this race.
+#[cfg(all(unix, not(any(target_os = "redox", target_os = "vxworks"))))]
+fn recursive_rmdir_toctou() {
+    // Test for time-of-check to time-of-use issues.
+    //
+    // Scenario:
+    // The attacker wants to get directory contents deleted, to which they do not have access.
+    // They have a way to get a privileged Rust binary call `std::fs::remove_dir_all()` on a
+    // directory they control, e.g. in their home directory.
+    //
+    // The POC sets up the `attack_dest/attack_file` which the attacker wants to have deleted.
+    // The attacker repeatedly creates a directory and replaces it with a symlink from
+    // `victim_del` to `attack_dest` while the victim code calls `std::fs::remove_dir_all()`
+    // on `victim_del`. Without protection against this race, the attack
+    // succeeds within a few seconds and `attack_dest/attack_file` is deleted.
+    use crate::sync::Arc;
+    use crate::time::{Duration, Instant};
+
+    let tmpdir = tmpdir();
+    let victim_del_path = tmpdir.join("victim_del");
+    let victim_del_path_clone = victim_del_path.clone();
+
+    // setup dest
+    let attack_dest_dir = tmpdir.join("attack_dest");
+    let attack_dest_dir = attack_dest_dir.as_path();
+    fs::create_dir(attack_dest_dir).unwrap();
+    let attack_dest_file = tmpdir.join("attack_dest/attack_file");
+    File::create(&attack_dest_file).unwrap();
+
+    let drop_canary_arc = Arc::new(());
+    let drop_canary_weak = Arc::downgrade(&drop_canary_arc);
+
+    // victim just continuously removes `victim_del`
+    let victim = thread::spawn(move || {
+        while drop_canary_weak.upgrade().is_some() {
+            let _ = fs::remove_dir_all(&victim_del_path_clone);
+        }
+    });
+
+    // attacker (could of course be in a separate process)
+    let start_time = Instant::now();
+    while Instant::now().duration_since(start_time) < Duration::from_millis(1000) {
+        if !attack_dest_file.exists() {
+            panic!(
+                "Victim deleted symlinked file outside of victim_del. Attack succeeded in {:?}.",
+                Instant::now().duration_since(start_time)
+            );
+        }
+        let _ = fs::create_dir(&victim_del_path);
+        let _ = fs::remove_dir(&victim_del_path);
+        let _ = symlink_dir(attack_dest_dir, &victim_del_path);
+    }
+
+    drop(drop_canary_arc);
+    victim.join().unwrap();
+
+    // Whatever the attacker left behind is removed without touching the
+    // symlink's target.
+    if fs::symlink_metadata(&victim_del_path).is_ok() {
+        check!(fs::remove_dir_all(&victim_del_path));
+    }
+    assert!(fs::symlink_metadata(&victim_del_path).is_err());
+    assert!(attack_dest_file.exists());
+}
+
+#[test]
+#[cfg(all(unix, not(any(target_os = "redox", target_os = "vxworks"))))]
+fn recursive_rmdir_toctou_nested() {
+    // Like `recursive_rmdir_toctou`, but the attacker swaps a subdirectory
+    // for a symlink after the victim has read its parent. The replacement
+    // must be removed like any other entry instead of failing the removal.
+    use crate::sync::atomic::{AtomicBool, Ordering};
+    use crate::sync::Arc;
+    use crate::time::{Duration, Instant};
+
+    let tmpdir = tmpdir();
+    let victim_del_path = tmpdir.join("victim_del");
+    let attack_dest_dir = tmpdir.join("attack_dest");
+    fs::create_dir(&attack_dest_dir).unwrap();
+    let attack_dest_file = attack_dest_dir.join("attack_file");
+    File::create(&attack_dest_file).unwrap();
+
+    let done = Arc::new(AtomicBool::new(false));
+    let victim = {
+        let done = done.clone();
+        let victim_del_path = victim_del_path.clone();
+        thread::spawn(move || {
+            while !done.load(Ordering::SeqCst) {
+                if let Err(err) = fs::remove_dir_all(&victim_del_path) {
+                    // Losing a race against the attacker's own changes is
+                    // fine, but a swapped entry must never abort the removal.
+                    let code = err.raw_os_error();
+                    if code == Some(libc::ELOOP) || code == Some(libc::ENOTDIR) {
+                        return Err(err);
+                    }
+                }
+            }
+            Ok(())
+        })
+    };
+
+    // Many subdirectories keep the victim busy after it has read their names,
+    // which gives the attacker time to swap the later ones.
+    let subdirs: Vec<_> = (0..64).map(|i| victim_del_path.join(format!("sub{}", i))).collect();
+    let start_time = Instant::now();
+    while Instant::now().duration_since(start_time) < Duration::from_millis(1000) {
+        assert!(attack_dest_file.exists(), "victim deleted a file outside of victim_del");
+        let _ = fs::create_dir(&victim_del_path);
+        for subdir in &subdirs {
+            let _ = fs::create_dir(subdir);
+        }
+        for subdir in subdirs.iter().rev() {
+            if fs::remove_dir(subdir).is_ok() {
+                let _ = symlink_dir(&attack_dest_dir, subdir);
+            }
+        }
+    }
+
+    done.store(true, Ordering::SeqCst);
+    check!(victim.join().unwrap());
+
+    let _ = fs::create_dir(&victim_del_path);
+    let _ = symlink_dir(&attack_dest_dir, &subdirs[0]);
+    check!(fs::remove_dir_all(&victim_del_path));
+    assert!(fs::symlink_metadata(&victim_del_path).is_err());
+    assert!(attack_dest_file.exists());
+}
+
 #[test]
 fn recursive_rmdir_of_symlink() {
     // test we do not recursively delete a symlink but only dirs.
@@ -1390,3 +1520,64 @@ fn symlink_hard_link() {
     // "hard_link" should still appear as a symlink.
     assert!(check!(fs::symlink_metadata(tmpdir.join("hard_link"))).file_type().is_symlink());
 }
+
+#[test]
+#[cfg(all(unix, not(any(target_os = "redox", target_os = "vxworks"))))]
+fn dir_handle_relative_ops() {
+    use crate::fs::Dir;
+
+    let tmpdir = tmpdir();
+    let dir = check!(Dir::open(tmpdir.path()));
+
+    check!(dir.create_dir("sub"));
+    assert!(tmpdir.join("sub").is_dir());
+    assert_eq!(dir.create_dir("sub").unwrap_err().kind(), ErrorKind::AlreadyExists);
+
+    let mut opts = OpenOptions::new();
+    opts.write(true).create_new(true);
+    let mut file = check!(dir.open_file("sub/file", &opts));
+    check!(file.write_all(b"foo"));
+    drop(file);
+    assert_eq!(check!(fs::read(tmpdir.join("sub/file"))), b"foo");
+    assert!(check!(dir.metadata("sub")).is_dir());
+    assert_eq!(check!(dir.metadata("sub/file")).len(), 3);
+
+    let names: Vec<_> = check!(dir.read_dir("sub")).map(|e| check!(e).file_name()).collect();
+    assert_eq!(names, ["file"]);
+
+    // The entries' paths are relative to `dir`, not to the current directory,
+    // so their metadata has to be looked up through the directory itself.
+    for entry in check!(dir.read_dir("sub")) {
+        let entry = check!(entry);
+        assert!(!entry.path().exists());
+        assert!(check!(entry.file_type()).is_file());
+        assert_eq!(check!(entry.metadata()).len(), 3);
+    }
+
+    let sub = check!(Dir::open(tmpdir.join("sub")));
+    check!(dir.rename("sub/file", &sub, "renamed"));
+    assert!(!tmpdir.join("sub/file").exists());
+    assert!(tmpdir.join("sub/renamed").exists());
+
+    check!(sub.remove_file("renamed"));
+    assert!(!tmpdir.join("sub/renamed").exists());
+    assert_eq!(dir.remove_file("sub/renamed").unwrap_err().kind(), ErrorKind::NotFound);
+    assert!(Dir::open(tmpdir.join("missing")).is_err());
+}
+
+#[test]
+#[cfg(all(unix, not(any(target_os = "redox", target_os = "vxworks"))))]
+fn dir_handle_survives_rename() {
+    use crate::fs::Dir;
+
+    let tmpdir = tmpdir();
+    check!(fs::create_dir(tmpdir.join("a")));
+    let dir = check!(Dir::open(tmpdir.join("a")));
+    check!(fs::rename(tmpdir.join("a"), tmpdir.join("b")));
+    check!(fs::create_dir(tmpdir.join("a")));
+
+    // The handle still refers to the directory now called "b".
+    check!(dir.create_dir("inner"));
+    assert!(tmpdir.join("b/inner").is_dir());
+    assert!(!tmpdir.join("a/inner").exists());
+}
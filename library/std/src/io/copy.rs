@@ -1,4 +1,4 @@
-use crate::io::{self, ErrorKind, Read, Write};
+use crate::io::{self, ErrorKind, Read, ReadBuf, Write};
 use crate::mem::MaybeUninit;
 
 /// Copies the entire contents of a reader into a writer.
@@ -61,28 +61,38 @@ pub(crate) fn generic_copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W)
     R: Read,
     W: Write,
 {
-    let mut buf = MaybeUninit::<[u8; super::DEFAULT_BUF_SIZE]>::uninit();
+    let mut buf = [MaybeUninit::<u8>::uninit(); super::DEFAULT_BUF_SIZE];
+    let mut buf = ReadBuf::uninit(&mut buf);
+
     // FIXME: #42788
     //
-    //   - This creates a (mut) reference to a slice of
-    //     _uninitialized_ integers, which is **undefined behavior**
+    //   - Readers that opted out of initialization but don't implement
+    //     `read_buf` yet get to see the uninitialized bytes as a slice
+    //     of _uninitialized_ integers, which is **undefined behavior**
     //
     //   - Only the standard library gets to soundly "ignore" this,
     //     based on its privileged knowledge of unstable rustc
     //     internals;
     unsafe {
-        reader.initializer().initialize(buf.assume_init_mut());
+        if !reader.initializer().should_initialize() {
+            buf.assume_init(buf.capacity());
+        }
     }
 
     let mut written = 0;
     loop {
-        let len = match reader.read(unsafe { buf.assume_init_mut() }) {
-            Ok(0) => return Ok(written),
-            Ok(len) => len,
+        match reader.read_buf(&mut buf) {
+            Ok(()) => {}
             Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
             Err(e) => return Err(e),
-        };
-        writer.write_all(unsafe { &buf.assume_init_ref()[..len] })?;
-        written += len as u64;
+        }
+
+        if buf.filled().is_empty() {
+            return Ok(written);
+        }
+
+        writer.write_all(buf.filled())?;
+        written += buf.filled_len() as u64;
+        buf.clear();
     }
 }
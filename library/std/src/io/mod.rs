@@ -270,6 +270,8 @@
 pub use self::cursor::Cursor;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::error::{Error, ErrorKind, Result};
+#[unstable(feature = "read_buf", issue = "none")]
+pub use self::readbuf::ReadBuf;
 #[unstable(feature = "internal_output_capture", issue = "none")]
 #[doc(no_inline, hidden)]
 pub use self::stdio::set_output_capture;
@@ -288,6 +290,7 @@
 mod error;
 mod impls;
 pub mod prelude;
+mod readbuf;
 mod stdio;
 mod util;
 
@@ -349,8 +352,10 @@ fn append_to_string<F>(buf: &mut String, f: F) -> Result<usize>
 // time is 4,500 times (!) slower than a default reservation size of 32 if the
 // reader has a very small amount of data to return.
 //
-// Because we're extending the buffer with uninitialized data for trusted
-// readers, we need to make sure to truncate that if any of this panics.
+// The spare capacity of the vector is handed to the reader as a `ReadBuf`, so
+// readers that implement `read_buf` never see the memory zeroed. The length of
+// the vector is only ever extended over bytes the reader has filled, so there's
+// nothing to clean up if the reader panics.
 fn read_to_end<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
     read_to_end_with_reservation(r, buf, |_| 32)
 }
@@ -365,41 +370,61 @@ fn read_to_end_with_reservation<R, F>(
     F: FnMut(&R) -> usize,
 {
     let start_len = buf.len();
-    let mut g = Guard { len: buf.len(), buf };
+    // Bytes of the spare capacity that a previous read initialized but didn't fill.
+    let mut initialized = 0;
     loop {
-        if g.len == g.buf.len() {
-            unsafe {
-                // FIXME(danielhenrymantilla): #42788
-                //
-                //   - This creates a (mut) reference to a slice of
-                //     _uninitialized_ integers, which is **undefined behavior**
-                //
-                //   - Only the standard library gets to soundly "ignore" this,
-                //     based on its privileged knowledge of unstable rustc
-                //     internals;
-                g.buf.reserve(reservation_size(r));
-                let capacity = g.buf.capacity();
-                g.buf.set_len(capacity);
-                r.initializer().initialize(&mut g.buf[g.len..]);
-            }
+        if buf.len() == buf.capacity() {
+            buf.reserve(reservation_size(r));
         }
 
-        let buf = &mut g.buf[g.len..];
-        match r.read(buf) {
-            Ok(0) => return Ok(g.len - start_len),
-            Ok(n) => {
-                // We can't allow bogus values from read. If it is too large, the returned vec could have its length
-                // set past its capacity, or if it overflows the vec could be shortened which could create an invalid
-                // string if this is called via read_to_string.
-                assert!(n <= buf.len());
-                g.len += n;
+        let mut read_buf = ReadBuf::uninit(buf.spare_capacity_mut());
+        unsafe {
+            // SAFETY: these bytes were initialized but not filled in the previous iteration.
+            read_buf.assume_init(initialized);
+
+            // FIXME(danielhenrymantilla): #42788
+            //
+            //   - Readers that opted out of initialization but don't implement
+            //     `read_buf` yet get to see the uninitialized bytes as a slice
+            //     of _uninitialized_ integers, which is **undefined behavior**
+            //
+            //   - Only the standard library gets to soundly "ignore" this,
+            //     based on its privileged knowledge of unstable rustc
+            //     internals;
+            if !r.initializer().should_initialize() {
+                read_buf.assume_init(read_buf.capacity());
             }
-            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+        }
+
+        match r.read_buf(&mut read_buf) {
+            Ok(()) => {}
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
             Err(e) => return Err(e),
         }
+
+        let filled = read_buf.filled_len();
+        if filled == 0 {
+            return Ok(buf.len() - start_len);
+        }
+
+        initialized = read_buf.initialized_len() - filled;
+        let new_len = buf.len() + filled;
+        // SAFETY: `ReadBuf` guarantees that the filled bytes are initialized.
+        unsafe {
+            buf.set_len(new_len);
+        }
     }
 }
 
+pub(crate) fn default_read_buf<F>(read: F, buf: &mut ReadBuf<'_>) -> Result<()>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize>,
+{
+    let n = read(buf.initialize_unfilled())?;
+    buf.add_filled(n);
+    Ok(())
+}
+
 pub(crate) fn default_read_vectored<F>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<usize>
 where
     F: FnOnce(&mut [u8]) -> Result<usize>,
@@ -631,6 +656,52 @@ unsafe fn initializer(&self) -> Initializer {
         Initializer::zeroing()
     }
 
+    /// Pull some bytes from this source into the specified buffer.
+    ///
+    /// This is equivalent to the [`read`](Read::read) method, except that it
+    /// is passed a [`ReadBuf`] rather than `[u8]` to allow use with
+    /// uninitialized buffers. The new data will be appended to any existing
+    /// contents of `buf`.
+    ///
+    /// Implementations that don't need to look at the buffer, such as those
+    /// that read straight from the operating system, should override this
+    /// method and write into [`ReadBuf::unfilled_mut`] directly, so that
+    /// callers like [`read_to_end`] and [`io::copy`] don't have to zero the
+    /// memory first.
+    ///
+    /// The default implementation initializes the unfilled part of `buf` and
+    /// delegates to [`read`](Read::read).
+    ///
+    /// [`read_to_end`]: Read::read_to_end
+    /// [`io::copy`]: copy()
+    ///
+    /// # Errors
+    ///
+    /// If an error is returned then no bytes must have been added to `buf`,
+    /// although the initialized region may have grown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(read_buf)]
+    /// use std::io::{self, Read, ReadBuf};
+    /// use std::mem::MaybeUninit;
+    ///
+    /// fn main() -> io::Result<()> {
+    ///     let mut reader: &[u8] = b"some bytes";
+    ///     let mut storage = [MaybeUninit::uninit(); 4];
+    ///     let mut buf = ReadBuf::uninit(&mut storage);
+    ///
+    ///     reader.read_buf(&mut buf)?;
+    ///     assert_eq!(buf.filled(), b"some");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "read_buf", issue = "none")]
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        default_read_buf(|b| self.read(b), buf)
+    }
+
     /// Read all bytes until EOF in this source, placing them into `buf`.
     ///
     /// All bytes read from this source will be appended to the specified buffer
@@ -2406,6 +2477,47 @@ fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         Ok(n)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> Result<()> {
+        // Don't call into inner reader at all at EOF because it may still block
+        if self.limit == 0 {
+            return Ok(());
+        }
+
+        if self.limit <= buf.remaining() as u64 {
+            // Hand the inner reader a `ReadBuf` that ends at the limit.
+            let limit = self.limit as usize;
+            let extra_init = cmp::min(limit, buf.initialized_len() - buf.filled_len());
+
+            // SAFETY: no uninitialized data is written to `ibuf`
+            let ibuf = unsafe { &mut buf.unfilled_mut()[..limit] };
+            let mut sliced_buf = ReadBuf::uninit(ibuf);
+
+            // SAFETY: `extra_init` bytes of `ibuf` are known to be initialized
+            unsafe {
+                sliced_buf.assume_init(extra_init);
+            }
+
+            self.inner.read_buf(&mut sliced_buf)?;
+
+            let new_init = sliced_buf.initialized_len();
+            let filled = sliced_buf.filled_len();
+
+            // SAFETY: `new_init` bytes of the unfilled part of `buf` have been initialized
+            unsafe {
+                buf.assume_init(new_init);
+            }
+            buf.add_filled(filled);
+
+            self.limit -= filled as u64;
+        } else {
+            let prev_filled = buf.filled_len();
+            self.inner.read_buf(buf)?;
+            self.limit -= buf.filled_len().saturating_sub(prev_filled) as u64;
+        }
+
+        Ok(())
+    }
+
     unsafe fn initializer(&self) -> Initializer {
         self.inner.initializer()
     }
@@ -1,7 +1,8 @@
 use super::{repeat, Cursor, SeekFrom};
 use crate::cmp::{self, min};
 use crate::io::{self, IoSlice, IoSliceMut};
-use crate::io::{BufRead, Read, Seek, Write};
+use crate::io::{BufRead, Read, ReadBuf, Seek, Write};
+use crate::mem::MaybeUninit;
 use crate::ops::Deref;
 
 #[test]
@@ -165,6 +166,61 @@ fn consume(&mut self, _amt: usize) {}
     let mut buf = [0; 1];
     assert_eq!(0, R.take(0).read(&mut buf).unwrap());
     assert_eq!(b"", R.take(0).fill_buf().unwrap());
+
+    let mut buf = [MaybeUninit::uninit(); 1];
+    let mut buf = ReadBuf::uninit(&mut buf);
+    R.take(0).read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled_len(), 0);
+}
+
+#[test]
+fn take_read_buf() {
+    let mut buf = [MaybeUninit::uninit(); 8];
+    let mut buf = ReadBuf::uninit(&mut buf);
+
+    // The limit is smaller than the buffer, so the inner reader only gets to
+    // see and initialize the first part of it.
+    let mut r = (&b"1234567890"[..]).take(5);
+    r.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"12345");
+    assert_eq!(buf.initialized_len(), 5);
+    assert_eq!(r.limit(), 0);
+
+    buf.clear();
+    let mut r = (&b"1234567890"[..]).take(100);
+    r.read_buf(&mut buf).unwrap();
+    assert_eq!(buf.filled(), b"12345678");
+    assert_eq!(r.limit(), 92);
+}
+
+#[test]
+fn read_to_end_read_buf() {
+    // A reader that can only be read from through `read_buf`.
+    struct R(usize);
+
+    impl Read for R {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            panic!("read_to_end should call read_buf");
+        }
+
+        fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+            let n = cmp::min(self.0, cmp::min(buf.remaining(), 100));
+            assert_eq!(buf.filled_len(), 0);
+            buf.append(&vec![7; n]);
+            self.0 -= n;
+            Ok(())
+        }
+    }
+
+    let mut v = vec![1, 2, 3];
+    assert_eq!(R(1000).read_to_end(&mut v).unwrap(), 1000);
+    assert_eq!(v.len(), 1003);
+    assert_eq!(&v[..3], [1, 2, 3]);
+    assert!(v[3..].iter().all(|&b| b == 7));
+
+    let mut v = Vec::new();
+    assert_eq!(R(1000).take(10).read_to_end(&mut v).unwrap(), 10);
+    assert_eq!(v, [7; 10]);
 }
 
 fn cmp_bufread<Br1: BufRead, Br2: BufRead>(mut br1: Br1, mut br2: Br2, exp: &[u8]) {
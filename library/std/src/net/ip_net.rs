@@ -0,0 +1,857 @@
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
+use crate::fmt;
+use crate::io::Write as IoWrite;
+use crate::iter::FusedIterator;
+use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::ops::RangeInclusive;
+
+/// An IP network, either IPv4 or IPv6.
+///
+/// A network is an address together with a prefix length, written in CIDR
+/// notation like `10.0.0.0/8` or `2001:db8::/32`. See [`Ipv4Net`] and
+/// [`Ipv6Net`] for details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+/// use std::net::{IpAddr, IpNet, Ipv4Addr};
+///
+/// let net: IpNet = "10.0.0.0/8".parse().unwrap();
+/// assert!(net.contains(&IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))));
+/// assert!(!net.contains(&"::1".parse().unwrap()));
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IpNet {
+    /// An IPv4 network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    V4(Ipv4Net),
+    /// An IPv6 network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    V6(Ipv6Net),
+}
+
+/// An IPv4 network, made of an address and a prefix length of at most 32.
+///
+/// The prefix length is the number of leading bits of the address that make
+/// up the network part; the remaining bits select a host within it. Like in
+/// the interface configuration `192.168.1.10/24`, the address does not need
+/// to be the network address itself: [`network`] and [`trunc`] clear the
+/// host bits, and all other operations only look at the network part.
+///
+/// Two networks compare equal only if both their addresses and their prefix
+/// lengths are the same, so `192.168.1.10/24` and `192.168.1.0/24` are
+/// different values describing the same network.
+///
+/// [`network`]: Ipv4Net::network
+/// [`trunc`]: Ipv4Net::trunc
+///
+/// # Textual representation
+///
+/// `Ipv4Net` provides a [`FromStr`] implementation, which accepts an IPv4
+/// address in the format of [`Ipv4Addr`], followed by a `/` and the prefix
+/// length in decimal.
+///
+/// [`FromStr`]: crate::str::FromStr
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+/// use std::net::{Ipv4Addr, Ipv4Net};
+///
+/// let net: Ipv4Net = "192.168.1.10/24".parse().unwrap();
+/// assert_eq!(net.network(), Ipv4Addr::new(192, 168, 1, 0));
+/// assert_eq!(net.broadcast(), Ipv4Addr::new(192, 168, 1, 255));
+/// assert!(net.contains(&Ipv4Addr::new(192, 168, 1, 77)));
+/// assert_eq!(net.to_string(), "192.168.1.10/24");
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ipv4Net {
+    addr: Ipv4Addr,
+    prefix_len: u8,
+}
+
+/// An IPv6 network, made of an address and a prefix length of at most 128.
+///
+/// This is the IPv6 counterpart of [`Ipv4Net`], see there for details.
+/// IPv6 has no broadcast address, so [`hosts`] yields every address of the
+/// network.
+///
+/// [`hosts`]: Ipv6Net::hosts
+///
+/// # Textual representation
+///
+/// `Ipv6Net` provides a [`FromStr`] implementation, which accepts an IPv6
+/// address in the format of [`Ipv6Addr`], followed by a `/` and the prefix
+/// length in decimal.
+///
+/// [`FromStr`]: crate::str::FromStr
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+/// use std::net::{Ipv6Addr, Ipv6Net};
+///
+/// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+/// assert!(net.contains(&"2001:db8:1::1".parse().unwrap()));
+/// assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0, 0, 0, 0, 0, 0));
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ipv6Net {
+    addr: Ipv6Addr,
+    prefix_len: u8,
+}
+
+/// An iterator over the host addresses of an [`Ipv4Net`].
+///
+/// This `struct` is created by the [`Ipv4Net::hosts`] method.
+/// See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv4Hosts {
+    range: RangeInclusive<u32>,
+}
+
+/// An iterator over the addresses of an [`Ipv6Net`].
+///
+/// This `struct` is created by the [`Ipv6Net::hosts`] method.
+/// See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv6Hosts {
+    range: RangeInclusive<u128>,
+}
+
+/// An iterator over the subnets of an [`Ipv4Net`].
+///
+/// This `struct` is created by the [`Ipv4Net::subnets`] method.
+/// See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv4Subnets {
+    next: u32,
+    last: u32,
+    prefix_len: u8,
+    done: bool,
+}
+
+/// An iterator over the subnets of an [`Ipv6Net`].
+///
+/// This `struct` is created by the [`Ipv6Net::subnets`] method.
+/// See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct Ipv6Subnets {
+    next: u128,
+    last: u128,
+    prefix_len: u8,
+    done: bool,
+}
+
+impl IpNet {
+    /// Creates a new IP network from an address and a prefix length.
+    ///
+    /// Returns [`None`] if the prefix length is longer than the address,
+    /// that is more than 32 for IPv4 or more than 128 for IPv6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{IpAddr, IpNet, Ipv6Addr};
+    ///
+    /// let addr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+    /// assert!(IpNet::new(addr, 128).is_some());
+    /// assert!(IpNet::new(addr, 129).is_none());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Option<IpNet> {
+        match addr {
+            IpAddr::V4(addr) => Ipv4Net::new(addr, prefix_len).map(IpNet::V4),
+            IpAddr::V6(addr) => Ipv6Net::new(addr, prefix_len).map(IpNet::V6),
+        }
+    }
+
+    /// Returns the address this network was created with.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn addr(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.addr()),
+            IpNet::V6(net) => IpAddr::V6(net.addr()),
+        }
+    }
+
+    /// Returns the prefix length of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn prefix_len(&self) -> u8 {
+        match self {
+            IpNet::V4(net) => net.prefix_len(),
+            IpNet::V6(net) => net.prefix_len(),
+        }
+    }
+
+    /// Returns the netmask, which has the bits of the network part set.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn netmask(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.netmask()),
+            IpNet::V6(net) => IpAddr::V6(net.netmask()),
+        }
+    }
+
+    /// Returns the hostmask, which has the bits of the host part set.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hostmask(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.hostmask()),
+            IpNet::V6(net) => IpAddr::V6(net.hostmask()),
+        }
+    }
+
+    /// Returns the network address, which is the address with all host bits
+    /// cleared.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn network(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.network()),
+            IpNet::V6(net) => IpAddr::V6(net.network()),
+        }
+    }
+
+    /// Returns the same network with the host bits of its address cleared.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn trunc(&self) -> IpNet {
+        match self {
+            IpNet::V4(net) => IpNet::V4(net.trunc()),
+            IpNet::V6(net) => IpNet::V6(net.trunc()),
+        }
+    }
+
+    /// Returns [`true`] if `addr` is part of this network.
+    ///
+    /// An address never belongs to a network of the other IP version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::IpNet;
+    ///
+    /// let net: IpNet = "fe80::/10".parse().unwrap();
+    /// assert!(net.contains(&"fe80::1".parse().unwrap()));
+    /// assert!(!net.contains(&"169.254.0.1".parse().unwrap()));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        match (self, addr) {
+            (IpNet::V4(net), IpAddr::V4(addr)) => net.contains(addr),
+            (IpNet::V6(net), IpAddr::V6(addr)) => net.contains(addr),
+            _ => false,
+        }
+    }
+
+    /// Returns [`true`] if this network lies entirely within `other`.
+    ///
+    /// A network is a subnet of itself, but never of a network of the other
+    /// IP version.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_subnet_of(&self, other: &IpNet) -> bool {
+        match (self, other) {
+            (IpNet::V4(net), IpNet::V4(other)) => net.is_subnet_of(other),
+            (IpNet::V6(net), IpNet::V6(other)) => net.is_subnet_of(other),
+            _ => false,
+        }
+    }
+
+    /// Returns [`true`] if `other` lies entirely within this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_supernet_of(&self, other: &IpNet) -> bool {
+        other.is_subnet_of(self)
+    }
+
+    /// Returns the smallest network that contains this one, which has a
+    /// prefix one bit shorter, or [`None`] for a prefix length of zero.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn supernet(&self) -> Option<IpNet> {
+        match self {
+            IpNet::V4(net) => net.supernet().map(IpNet::V4),
+            IpNet::V6(net) => net.supernet().map(IpNet::V6),
+        }
+    }
+
+    /// Returns [`true`] if this is an IPv4 network, and [`false`] otherwise.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_ipv4(&self) -> bool {
+        matches!(self, IpNet::V4(_))
+    }
+
+    /// Returns [`true`] if this is an IPv6 network, and [`false`] otherwise.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_ipv6(&self) -> bool {
+        matches!(self, IpNet::V6(_))
+    }
+}
+
+impl Ipv4Net {
+    /// Creates a new IPv4 network from an address and a prefix length.
+    ///
+    /// The address is kept as given, including any host bits. Returns
+    /// [`None`] if the prefix length is larger than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net = Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap();
+    /// assert_eq!(net.prefix_len(), 8);
+    /// assert!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 33).is_none());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn new(addr: Ipv4Addr, prefix_len: u8) -> Option<Ipv4Net> {
+        if prefix_len <= 32 { Some(Ipv4Net { addr, prefix_len }) } else { None }
+    }
+
+    /// Returns the address this network was created with.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the netmask, which has the bits of the network part set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "172.16.0.0/12".parse().unwrap();
+    /// assert_eq!(net.netmask(), Ipv4Addr::new(255, 240, 0, 0));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from(v4_mask(self.prefix_len))
+    }
+
+    /// Returns the hostmask, which has the bits of the host part set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "172.16.0.0/12".parse().unwrap();
+    /// assert_eq!(net.hostmask(), Ipv4Addr::new(0, 15, 255, 255));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hostmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from(!v4_mask(self.prefix_len))
+    }
+
+    /// Returns the network address, which is the address with all host bits
+    /// cleared.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn network(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.addr) & v4_mask(self.prefix_len))
+    }
+
+    /// Returns the broadcast address, which is the address with all host
+    /// bits set.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from(u32::from(self.addr) | !v4_mask(self.prefix_len))
+    }
+
+    /// Returns the same network with the host bits of its address cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "192.168.1.10/24".parse().unwrap();
+    /// assert_eq!(net.trunc(), "192.168.1.0/24".parse().unwrap());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn trunc(&self) -> Ipv4Net {
+        Ipv4Net { addr: self.network(), prefix_len: self.prefix_len }
+    }
+
+    /// Returns [`true`] if `addr` is part of this network.
+    ///
+    /// This includes the network and broadcast addresses.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn contains(&self, addr: &Ipv4Addr) -> bool {
+        let mask = v4_mask(self.prefix_len);
+        u32::from(*addr) & mask == u32::from(self.addr) & mask
+    }
+
+    /// Returns [`true`] if this network lies entirely within `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.1.0.0/16".parse().unwrap();
+    /// assert!(net.is_subnet_of(&"10.0.0.0/8".parse().unwrap()));
+    /// assert!(net.is_subnet_of(&net));
+    /// assert!(!net.is_subnet_of(&"10.1.2.0/24".parse().unwrap()));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_subnet_of(&self, other: &Ipv4Net) -> bool {
+        self.prefix_len >= other.prefix_len && other.contains(&self.addr)
+    }
+
+    /// Returns [`true`] if `other` lies entirely within this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_supernet_of(&self, other: &Ipv4Net) -> bool {
+        other.is_subnet_of(self)
+    }
+
+    /// Returns the smallest network that contains this one, which has a
+    /// prefix one bit shorter, or [`None`] for a prefix length of zero.
+    ///
+    /// The address of the returned network has its host bits cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "192.168.1.0/24".parse().unwrap();
+    /// assert_eq!(net.supernet(), Some("192.168.0.0/23".parse().unwrap()));
+    /// assert_eq!("0.0.0.0/0".parse::<Ipv4Net>().unwrap().supernet(), None);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn supernet(&self) -> Option<Ipv4Net> {
+        let prefix_len = self.prefix_len.checked_sub(1)?;
+        Some(Ipv4Net { addr: self.addr, prefix_len }.trunc())
+    }
+
+    /// Returns an iterator over the subnets of this network that have the
+    /// given prefix length, in ascending order.
+    ///
+    /// Returns [`None`] if `prefix_len` is shorter than the prefix of this
+    /// network or larger than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/23".parse().unwrap();
+    /// let subnets: Vec<String> = net.subnets(25).unwrap().map(|n| n.to_string()).collect();
+    /// assert_eq!(subnets, ["10.0.0.0/25", "10.0.0.128/25", "10.0.1.0/25", "10.0.1.128/25"]);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn subnets(&self, prefix_len: u8) -> Option<Ipv4Subnets> {
+        if prefix_len < self.prefix_len || prefix_len > 32 {
+            return None;
+        }
+        Some(Ipv4Subnets {
+            next: u32::from(self.network()),
+            last: u32::from(self.broadcast()) & v4_mask(prefix_len),
+            prefix_len,
+            done: false,
+        })
+    }
+
+    /// Returns an iterator over the addresses of this network that can be
+    /// assigned to hosts, in ascending order.
+    ///
+    /// This leaves out the network and broadcast addresses, except for
+    /// networks with a prefix length of 31 (point-to-point links, see
+    /// [IETF RFC 3021]) and 32, which have no room for them.
+    ///
+    /// [IETF RFC 3021]: https://tools.ietf.org/html/rfc3021
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "192.168.1.0/30".parse().unwrap();
+    /// let hosts: Vec<Ipv4Addr> = net.hosts().collect();
+    /// assert_eq!(hosts, [Ipv4Addr::new(192, 168, 1, 1), Ipv4Addr::new(192, 168, 1, 2)]);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hosts(&self) -> Ipv4Hosts {
+        let start = u32::from(self.network());
+        let end = u32::from(self.broadcast());
+        let range = if self.prefix_len < 31 { start + 1..=end - 1 } else { start..=end };
+        Ipv4Hosts { range }
+    }
+}
+
+impl Ipv6Net {
+    /// Creates a new IPv6 network from an address and a prefix length.
+    ///
+    /// The address is kept as given, including any host bits. Returns
+    /// [`None`] if the prefix length is larger than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net = Ipv6Net::new(Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 0), 8).unwrap();
+    /// assert_eq!(net.prefix_len(), 8);
+    /// assert!(Ipv6Net::new(Ipv6Addr::UNSPECIFIED, 129).is_none());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn new(addr: Ipv6Addr, prefix_len: u8) -> Option<Ipv6Net> {
+        if prefix_len <= 128 { Some(Ipv6Net { addr, prefix_len }) } else { None }
+    }
+
+    /// Returns the address this network was created with.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn addr(&self) -> Ipv6Addr {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the netmask, which has the bits of the network part set.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from(v6_mask(self.prefix_len))
+    }
+
+    /// Returns the hostmask, which has the bits of the host part set.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hostmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from(!v6_mask(self.prefix_len))
+    }
+
+    /// Returns the network address, which is the address with all host bits
+    /// cleared.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn network(&self) -> Ipv6Addr {
+        Ipv6Addr::from(u128::from(self.addr) & v6_mask(self.prefix_len))
+    }
+
+    /// Returns the same network with the host bits of its address cleared.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn trunc(&self) -> Ipv6Net {
+        Ipv6Net { addr: self.network(), prefix_len: self.prefix_len }
+    }
+
+    /// Returns [`true`] if `addr` is part of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn contains(&self, addr: &Ipv6Addr) -> bool {
+        let mask = v6_mask(self.prefix_len);
+        u128::from(*addr) & mask == u128::from(self.addr) & mask
+    }
+
+    /// Returns [`true`] if this network lies entirely within `other`.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_subnet_of(&self, other: &Ipv6Net) -> bool {
+        self.prefix_len >= other.prefix_len && other.contains(&self.addr)
+    }
+
+    /// Returns [`true`] if `other` lies entirely within this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn is_supernet_of(&self, other: &Ipv6Net) -> bool {
+        other.is_subnet_of(self)
+    }
+
+    /// Returns the smallest network that contains this one, which has a
+    /// prefix one bit shorter, or [`None`] for a prefix length of zero.
+    ///
+    /// The address of the returned network has its host bits cleared.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn supernet(&self) -> Option<Ipv6Net> {
+        let prefix_len = self.prefix_len.checked_sub(1)?;
+        Some(Ipv6Net { addr: self.addr, prefix_len }.trunc())
+    }
+
+    /// Returns an iterator over the subnets of this network that have the
+    /// given prefix length, in ascending order.
+    ///
+    /// Returns [`None`] if `prefix_len` is shorter than the prefix of this
+    /// network or larger than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+    /// let mut subnets = net.subnets(48).unwrap();
+    /// assert_eq!(subnets.next(), Some("2001:db8::/48".parse().unwrap()));
+    /// assert_eq!(subnets.next(), Some("2001:db8:1::/48".parse().unwrap()));
+    /// assert_eq!(subnets.last(), Some("2001:db8:ffff::/48".parse().unwrap()));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn subnets(&self, prefix_len: u8) -> Option<Ipv6Subnets> {
+        if prefix_len < self.prefix_len || prefix_len > 128 {
+            return None;
+        }
+        let mask = v6_mask(self.prefix_len);
+        Some(Ipv6Subnets {
+            next: u128::from(self.addr) & mask,
+            last: (u128::from(self.addr) | !mask) & v6_mask(prefix_len),
+            prefix_len,
+            done: false,
+        })
+    }
+
+    /// Returns an iterator over all addresses of this network, in ascending
+    /// order.
+    ///
+    /// Unlike [`Ipv4Net::hosts`], this includes the first and the last
+    /// address, as IPv6 does not reserve them.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hosts(&self) -> Ipv6Hosts {
+        let mask = v6_mask(self.prefix_len);
+        let start = u128::from(self.addr) & mask;
+        Ipv6Hosts { range: start..=start | !mask }
+    }
+}
+
+/// Returns the IPv4 netmask for a prefix length of at most 32.
+fn v4_mask(prefix_len: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix_len)).unwrap_or(0)
+}
+
+/// Returns the IPv6 netmask for a prefix length of at most 128.
+fn v6_mask(prefix_len: u8) -> u128 {
+    u128::MAX.checked_shl(128 - u32::from(prefix_len)).unwrap_or(0)
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for IpNet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpNet::V4(net) => net.fmt(fmt),
+            IpNet::V6(net) => net.fmt(fmt),
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for IpNet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for Ipv4Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Fast Path: if there's no alignment stuff, write directly to the buffer
+        if fmt.precision().is_none() && fmt.width().is_none() {
+            write!(fmt, "{}/{}", self.addr, self.prefix_len)
+        } else {
+            const IPV4_NET_BUF_LEN: usize = 15 + 3; // Long enough for "255.255.255.255/32"
+            let mut buf = [0u8; IPV4_NET_BUF_LEN];
+            let mut buf_slice = &mut buf[..];
+
+            // Note: The call to write should never fail, hence the unwrap
+            write!(buf_slice, "{}/{}", self.addr, self.prefix_len).unwrap();
+            let len = IPV4_NET_BUF_LEN - buf_slice.len();
+
+            // This unsafe is OK because we know what is being written to the buffer
+            let buf = unsafe { crate::str::from_utf8_unchecked(&buf[..len]) };
+            fmt.pad(buf)
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for Ipv4Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for Ipv6Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Fast Path: if there's no alignment stuff, write directly to the buffer
+        if fmt.precision().is_none() && fmt.width().is_none() {
+            write!(fmt, "{}/{}", self.addr, self.prefix_len)
+        } else {
+            const IPV6_NET_BUF_LEN: usize = (4 * 8) + 7 + 4; // Longest address, then "/128"
+            let mut buf = [0u8; IPV6_NET_BUF_LEN];
+            let mut buf_slice = &mut buf[..];
+
+            // Note: The call to write should never fail, hence the unwrap
+            write!(buf_slice, "{}/{}", self.addr, self.prefix_len).unwrap();
+            let len = IPV6_NET_BUF_LEN - buf_slice.len();
+
+            // This unsafe is OK because we know what is being written to the buffer
+            let buf = unsafe { crate::str::from_utf8_unchecked(&buf[..len]) };
+            fmt.pad(buf)
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for Ipv6Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv4Net> for IpNet {
+    /// Copies this network to a new `IpNet::V4`.
+    fn from(net: Ipv4Net) -> IpNet {
+        IpNet::V4(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv6Net> for IpNet {
+    /// Copies this network to a new `IpNet::V6`.
+    fn from(net: Ipv6Net) -> IpNet {
+        IpNet::V6(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<IpAddr> for IpNet {
+    /// Creates a network that contains only the given address, with the
+    /// longest prefix for its IP version.
+    fn from(addr: IpAddr) -> IpNet {
+        match addr {
+            IpAddr::V4(addr) => IpNet::V4(addr.into()),
+            IpAddr::V6(addr) => IpNet::V6(addr.into()),
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv4Addr> for Ipv4Net {
+    /// Creates a `/32` network that contains only the given address.
+    fn from(addr: Ipv4Addr) -> Ipv4Net {
+        Ipv4Net { addr, prefix_len: 32 }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv6Addr> for Ipv6Net {
+    /// Creates a `/128` network that contains only the given address.
+    fn from(addr: Ipv6Addr) -> Ipv6Net {
+        Ipv6Net { addr, prefix_len: 128 }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv4Hosts {
+    type Item = Ipv4Addr;
+
+    fn next(&mut self) -> Option<Ipv4Addr> {
+        self.range.next().map(Ipv4Addr::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl DoubleEndedIterator for Ipv4Hosts {
+    fn next_back(&mut self) -> Option<Ipv4Addr> {
+        self.range.next_back().map(Ipv4Addr::from)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv4Hosts {}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv6Hosts {
+    type Item = Ipv6Addr;
+
+    fn next(&mut self) -> Option<Ipv6Addr> {
+        self.range.next().map(Ipv6Addr::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl DoubleEndedIterator for Ipv6Hosts {
+    fn next_back(&mut self) -> Option<Ipv6Addr> {
+        self.range.next_back().map(Ipv6Addr::from)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv6Hosts {}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv4Subnets {
+    type Item = Ipv4Net;
+
+    fn next(&mut self) -> Option<Ipv4Net> {
+        if self.done {
+            return None;
+        }
+        let net = Ipv4Net { addr: Ipv4Addr::from(self.next), prefix_len: self.prefix_len };
+        if self.next == self.last {
+            self.done = true;
+        } else {
+            // The prefix can't be zero here, as that only has one subnet.
+            self.next += 1 << (32 - self.prefix_len);
+        }
+        Some(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv4Subnets {}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv6Subnets {
+    type Item = Ipv6Net;
+
+    fn next(&mut self) -> Option<Ipv6Net> {
+        if self.done {
+            return None;
+        }
+        let net = Ipv6Net { addr: Ipv6Addr::from(self.next), prefix_len: self.prefix_len };
+        if self.next == self.last {
+            self.done = true;
+        } else {
+            // The prefix can't be zero here, as that only has one subnet.
+            self.next += 1 << (128 - self.prefix_len);
+        }
+        Some(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv6Subnets {}
@@ -0,0 +1,169 @@
+use crate::net::*;
+
+fn v4(s: &str) -> Ipv4Net {
+    s.parse().unwrap()
+}
+
+fn v6(s: &str) -> Ipv6Net {
+    s.parse().unwrap()
+}
+
+#[test]
+fn ipv4_net_masks() {
+    let net = v4("192.168.1.10/24");
+    assert_eq!(net.addr(), Ipv4Addr::new(192, 168, 1, 10));
+    assert_eq!(net.prefix_len(), 24);
+    assert_eq!(net.netmask(), Ipv4Addr::new(255, 255, 255, 0));
+    assert_eq!(net.hostmask(), Ipv4Addr::new(0, 0, 0, 255));
+    assert_eq!(net.network(), Ipv4Addr::new(192, 168, 1, 0));
+    assert_eq!(net.broadcast(), Ipv4Addr::new(192, 168, 1, 255));
+    assert_eq!(net.trunc(), v4("192.168.1.0/24"));
+
+    let net = v4("10.1.2.3/0");
+    assert_eq!(net.netmask(), Ipv4Addr::UNSPECIFIED);
+    assert_eq!(net.hostmask(), Ipv4Addr::BROADCAST);
+    assert_eq!(net.network(), Ipv4Addr::UNSPECIFIED);
+    assert_eq!(net.broadcast(), Ipv4Addr::BROADCAST);
+
+    let net = v4("10.1.2.3/32");
+    assert_eq!(net.netmask(), Ipv4Addr::BROADCAST);
+    assert_eq!(net.hostmask(), Ipv4Addr::UNSPECIFIED);
+    assert_eq!(net.network(), Ipv4Addr::new(10, 1, 2, 3));
+    assert_eq!(net.broadcast(), Ipv4Addr::new(10, 1, 2, 3));
+
+    assert!(Ipv4Net::new(Ipv4Addr::LOCALHOST, 33).is_none());
+}
+
+#[test]
+fn ipv6_net_masks() {
+    let net = v6("2001:db8::1/48");
+    assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0xffff, 0, 0, 0, 0, 0));
+    assert_eq!(net.hostmask(), Ipv6Addr::new(0, 0, 0, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff));
+    assert_eq!(net.network(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    assert_eq!(net.trunc(), v6("2001:db8::/48"));
+
+    assert_eq!(v6("::1/0").netmask(), Ipv6Addr::UNSPECIFIED);
+    assert_eq!(v6("::1/128").hostmask(), Ipv6Addr::UNSPECIFIED);
+    assert!(Ipv6Net::new(Ipv6Addr::LOCALHOST, 129).is_none());
+}
+
+#[test]
+fn contains() {
+    let net = v4("172.16.0.0/12");
+    assert!(net.contains(&Ipv4Addr::new(172, 16, 0, 0)));
+    assert!(net.contains(&Ipv4Addr::new(172, 31, 255, 255)));
+    assert!(!net.contains(&Ipv4Addr::new(172, 32, 0, 0)));
+    assert!(!net.contains(&Ipv4Addr::new(172, 15, 255, 255)));
+    assert!(v4("1.2.3.4/0").contains(&Ipv4Addr::BROADCAST));
+
+    let net = v6("fe80::/10");
+    assert!(net.contains(&"fe80::1".parse().unwrap()));
+    assert!(net.contains(&"febf:ffff::".parse().unwrap()));
+    assert!(!net.contains(&"fec0::".parse().unwrap()));
+
+    let net: IpNet = "10.0.0.0/8".parse().unwrap();
+    assert!(net.contains(&"10.20.30.40".parse().unwrap()));
+    assert!(!net.contains(&"::ffff:10.20.30.40".parse().unwrap()));
+}
+
+#[test]
+fn subnet_and_supernet() {
+    let net = v4("10.1.0.0/16");
+    assert!(net.is_subnet_of(&v4("10.0.0.0/8")));
+    assert!(net.is_subnet_of(&net));
+    assert!(!net.is_subnet_of(&v4("10.1.2.0/24")));
+    assert!(!net.is_subnet_of(&v4("11.0.0.0/8")));
+    assert!(v4("10.0.0.0/8").is_supernet_of(&net));
+
+    assert_eq!(v4("192.168.1.77/24").supernet(), Some(v4("192.168.0.0/23")));
+    assert_eq!(v4("128.0.0.0/1").supernet(), Some(v4("0.0.0.0/0")));
+    assert_eq!(v4("0.0.0.0/0").supernet(), None);
+
+    assert!(v6("2001:db8:1::/48").is_subnet_of(&v6("2001:db8::/32")));
+    assert_eq!(v6("2001:db9::/32").supernet(), Some(v6("2001:db8::/31")));
+    assert_eq!(v6("::/0").supernet(), None);
+
+    let net: IpNet = "10.0.0.0/8".parse().unwrap();
+    assert!(!net.is_subnet_of(&"::/0".parse().unwrap()));
+    assert_eq!(net.supernet(), Some("10.0.0.0/7".parse().unwrap()));
+}
+
+#[test]
+fn subnets() {
+    let net = v4("10.0.0.77/23");
+    let subnets: Vec<_> = net.subnets(25).unwrap().collect();
+    assert_eq!(
+        subnets,
+        [v4("10.0.0.0/25"), v4("10.0.0.128/25"), v4("10.0.1.0/25"), v4("10.0.1.128/25")]
+    );
+    assert_eq!(net.subnets(23).unwrap().collect::<Vec<_>>(), [v4("10.0.0.0/23")]);
+    assert!(net.subnets(22).is_none());
+    assert!(net.subnets(33).is_none());
+
+    // Subnets at the very end of the address space must not overflow.
+    let net = v4("255.255.255.252/30");
+    assert_eq!(net.subnets(32).unwrap().count(), 4);
+    assert_eq!(v4("0.0.0.0/0").subnets(0).unwrap().count(), 1);
+    assert_eq!(v4("0.0.0.0/0").subnets(2).unwrap().last(), Some(v4("192.0.0.0/2")));
+
+    let mut subnets = v6("::/0").subnets(1).unwrap();
+    assert_eq!(subnets.next(), Some(v6("::/1")));
+    assert_eq!(subnets.next(), Some(v6("8000::/1")));
+    assert_eq!(subnets.next(), None);
+    assert_eq!(subnets.next(), None);
+    assert_eq!(
+        v6("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fff0/124").subnets(128).unwrap().last(),
+        Some(v6("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"))
+    );
+}
+
+#[test]
+fn hosts() {
+    let hosts: Vec<_> = v4("192.168.1.5/29").hosts().collect();
+    assert_eq!(hosts.len(), 6);
+    assert_eq!(hosts[0], Ipv4Addr::new(192, 168, 1, 1));
+    assert_eq!(hosts[5], Ipv4Addr::new(192, 168, 1, 6));
+
+    let hosts: Vec<_> = v4("10.0.0.0/31").hosts().collect();
+    assert_eq!(hosts, [Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(10, 0, 0, 1)]);
+    let hosts: Vec<_> = v4("10.0.0.1/32").hosts().collect();
+    assert_eq!(hosts, [Ipv4Addr::new(10, 0, 0, 1)]);
+
+    let mut hosts = v4("0.0.0.0/0").hosts();
+    assert_eq!(hosts.next(), Some(Ipv4Addr::new(0, 0, 0, 1)));
+    assert_eq!(hosts.next_back(), Some(Ipv4Addr::new(255, 255, 255, 254)));
+
+    let hosts: Vec<_> = v6("2001:db8::/126").hosts().collect();
+    assert_eq!(hosts.len(), 4);
+    assert_eq!(hosts[0], "2001:db8::".parse::<Ipv6Addr>().unwrap());
+    assert_eq!(hosts[3], "2001:db8::3".parse::<Ipv6Addr>().unwrap());
+    assert_eq!(v6("::/0").hosts().next_back(), Some(Ipv6Addr::from(u128::MAX)));
+}
+
+#[test]
+fn display() {
+    assert_eq!(v4("10.0.0.1/8").to_string(), "10.0.0.1/8");
+    assert_eq!(format!("{:>20}", v4("10.0.0.1/8")), "          10.0.0.1/8");
+    assert_eq!(format!("{:<20}", v4("255.255.255.255/32")), "255.255.255.255/32  ");
+    assert_eq!(format!("{:?}", v4("10.0.0.1/8")), "10.0.0.1/8");
+
+    assert_eq!(v6("2001:db8::/32").to_string(), "2001:db8::/32");
+    assert_eq!(v6("::ffff:10.0.0.1/128").to_string(), "::ffff:10.0.0.1/128");
+    let long = "1111:2222:3333:4444:5555:6666:7777:8888/128";
+    assert_eq!(format!("{:>44}", v6(long)), format!(" {}", long));
+
+    let net: IpNet = "fe80::/10".parse().unwrap();
+    assert_eq!(format!("{:>12}", net), "   fe80::/10");
+    assert_eq!(format!("{:?}", net), "fe80::/10");
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(Ipv4Net::from(Ipv4Addr::LOCALHOST), v4("127.0.0.1/32"));
+    assert_eq!(Ipv6Net::from(Ipv6Addr::LOCALHOST), v6("::1/128"));
+    assert_eq!(IpNet::from(IpAddr::V4(Ipv4Addr::LOCALHOST)), IpNet::V4(v4("127.0.0.1/32")));
+    assert_eq!(IpNet::from(v6("::/0")), IpNet::V6(v6("::/0")));
+    assert_eq!(IpNet::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 129), None);
+    assert!(IpNet::from(v4("1.2.3.4/5")).is_ipv4());
+    assert!(IpNet::from(v6("::/5")).is_ipv6());
+}
@@ -18,20 +18,28 @@
 #![stable(feature = "rust1", since = "1.0.0")]
 
 use crate::io::{self, Error, ErrorKind};
+use crate::time::Duration;
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::ip::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
+#[unstable(feature = "ip_net", issue = "none")]
+pub use self::ip_net::{IpNet, Ipv4Hosts, Ipv4Net, Ipv4Subnets, Ipv6Hosts, Ipv6Net, Ipv6Subnets};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::parser::AddrParseError;
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub use self::tcp::TcpBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{Incoming, TcpListener, TcpStream};
+#[unstable(feature = "net_socket_builder", issue = "none")]
+pub use self::udp::UdpBuilder;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
 
 mod addr;
 mod ip;
+mod ip_net;
 mod parser;
 mod tcp;
 #[cfg(test)]
@@ -63,6 +71,24 @@ pub enum Shutdown {
     Both,
 }
 
+/// Options collected by [`TcpBuilder`] and [`UdpBuilder`], applied to a new
+/// socket before it is bound or connected.
+///
+/// `None` leaves the platform's default in place.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SocketOptions {
+    pub reuse_address: Option<bool>,
+    pub reuse_port: bool,
+    pub send_buffer_size: Option<usize>,
+    pub recv_buffer_size: Option<usize>,
+    pub only_v6: Option<bool>,
+    pub keepalive: bool,
+    pub keepalive_time: Option<Duration>,
+    pub keepalive_interval: Option<Duration>,
+    pub backlog: Option<u32>,
+    pub local_addr: Option<SocketAddr>,
+}
+
 #[inline]
 const fn htons(i: u16) -> u16 {
     i.to_be()
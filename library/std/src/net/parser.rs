@@ -1,4 +1,5 @@
-//! A private parser implementation of IPv4, IPv6, and socket addresses.
+//! A private parser implementation of IPv4, IPv6, and socket addresses, and
+//! of IP networks.
 //!
 //! This module is "publicly exported" through the `FromStr` implementations
 //! below.
@@ -9,7 +10,9 @@
 use crate::convert::TryInto as _;
 use crate::error::Error;
 use crate::fmt;
-use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use crate::net::{
+    IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr, Ipv6Net, SocketAddr, SocketAddrV4, SocketAddrV6,
+};
 use crate::str::FromStr;
 
 trait ReadNumberHelper: crate::marker::Sized {
@@ -255,6 +258,38 @@ fn read_socket_addr(&mut self) -> Option<SocketAddr> {
             .map(SocketAddr::V4)
             .or_else(|| self.read_socket_addr_v6().map(SocketAddr::V6))
     }
+
+    /// Read a / followed by a prefix length in base 10, no larger than max.
+    fn read_prefix_len(&mut self, max: u8) -> Option<u8> {
+        self.read_atomically(|p| {
+            p.read_given_char('/')?;
+            let prefix_len = p.read_number(10, Some(3))?;
+            if prefix_len <= max { Some(prefix_len) } else { None }
+        })
+    }
+
+    /// Read an IPV4 network in CIDR notation
+    fn read_ipv4_net(&mut self) -> Option<Ipv4Net> {
+        self.read_atomically(|p| {
+            let ip = p.read_ipv4_addr()?;
+            let prefix_len = p.read_prefix_len(32)?;
+            Ipv4Net::new(ip, prefix_len)
+        })
+    }
+
+    /// Read an IPV6 network in CIDR notation
+    fn read_ipv6_net(&mut self) -> Option<Ipv6Net> {
+        self.read_atomically(|p| {
+            let ip = p.read_ipv6_addr()?;
+            let prefix_len = p.read_prefix_len(128)?;
+            Ipv6Net::new(ip, prefix_len)
+        })
+    }
+
+    /// Read an IP network in CIDR notation, either IPV4 or IPV6.
+    fn read_ip_net(&mut self) -> Option<IpNet> {
+        self.read_ipv4_net().map(IpNet::V4).or_else(|| self.read_ipv6_net().map(IpNet::V6))
+    }
 }
 
 #[stable(feature = "ip_addr", since = "1.7.0")]
@@ -305,11 +340,36 @@ fn from_str(s: &str) -> Result<SocketAddr, AddrParseError> {
     }
 }
 
-/// An error which can be returned when parsing an IP address or a socket address.
+#[unstable(feature = "ip_net", issue = "none")]
+impl FromStr for IpNet {
+    type Err = AddrParseError;
+    fn from_str(s: &str) -> Result<IpNet, AddrParseError> {
+        Parser::new(s).parse_with(|p| p.read_ip_net())
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FromStr for Ipv4Net {
+    type Err = AddrParseError;
+    fn from_str(s: &str) -> Result<Ipv4Net, AddrParseError> {
+        Parser::new(s).parse_with(|p| p.read_ipv4_net())
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FromStr for Ipv6Net {
+    type Err = AddrParseError;
+    fn from_str(s: &str) -> Result<Ipv6Net, AddrParseError> {
+        Parser::new(s).parse_with(|p| p.read_ipv6_net())
+    }
+}
+
+/// An error which can be returned when parsing an IP address, a socket address
+/// or an IP network.
 ///
 /// This error is used as the error type for the [`FromStr`] implementation for
-/// [`IpAddr`], [`Ipv4Addr`], [`Ipv6Addr`], [`SocketAddr`], [`SocketAddrV4`], and
-/// [`SocketAddrV6`].
+/// [`IpAddr`], [`Ipv4Addr`], [`Ipv6Addr`], [`SocketAddr`], [`SocketAddrV4`],
+/// [`SocketAddrV6`], [`IpNet`], [`Ipv4Net`], and [`Ipv6Net`].
 ///
 /// # Potential causes
 ///
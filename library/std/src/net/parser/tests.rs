@@ -1,5 +1,7 @@
 // FIXME: These tests are all excellent candidates for AFL fuzz testing
-use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use crate::net::{
+    IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr, Ipv6Net, SocketAddr, SocketAddrV4, SocketAddrV6,
+};
 use crate::str::FromStr;
 
 const PORT: u16 = 8080;
@@ -16,6 +18,9 @@
 const IPV6_STR_PORT: &str = "[2001:db8::c0a8:1]:8080";
 const IPV6_STR_PORT_SCOPE_ID: &str = "[2001:db8::c0a8:1%1337]:8080";
 
+const IPV4_STR_NET: &str = "192.168.0.1/24";
+const IPV6_STR_NET: &str = "2001:db8::c0a8:1/64";
+
 #[test]
 fn parse_ipv4() {
     let result: Ipv4Addr = IPV4_STR.parse().unwrap();
@@ -100,6 +105,59 @@ fn parse_socket() {
     assert!(SocketAddr::from_str(IPV6_STR_V4).is_err());
 }
 
+#[test]
+fn parse_ipv4_net() {
+    let result: Ipv4Net = IPV4_STR_NET.parse().unwrap();
+    assert_eq!(result, Ipv4Net::new(IPV4, 24).unwrap());
+
+    let result: Ipv4Net = "0.0.0.0/0".parse().unwrap();
+    assert_eq!(result, Ipv4Net::new(Ipv4Addr::UNSPECIFIED, 0).unwrap());
+
+    let result: Ipv4Net = "192.168.0.1/32".parse().unwrap();
+    assert_eq!(result, Ipv4Net::new(IPV4, 32).unwrap());
+
+    assert!(Ipv4Net::from_str(IPV4_STR).is_err());
+    assert!(Ipv4Net::from_str(IPV4_STR_PORT).is_err());
+    assert!(Ipv4Net::from_str(IPV6_STR_NET).is_err());
+    assert!(Ipv4Net::from_str("192.168.0.1/33").is_err());
+    assert!(Ipv4Net::from_str("192.168.0.1/256").is_err());
+    assert!(Ipv4Net::from_str("192.168.0.1/").is_err());
+    assert!(Ipv4Net::from_str("192.168.0.1/+8").is_err());
+    assert!(Ipv4Net::from_str("192.168.0.1/24/24").is_err());
+}
+
+#[test]
+fn parse_ipv6_net() {
+    let result: Ipv6Net = IPV6_STR_NET.parse().unwrap();
+    assert_eq!(result, Ipv6Net::new(IPV6, 64).unwrap());
+
+    let result: Ipv6Net = "2001:db8::192.168.0.1/128".parse().unwrap();
+    assert_eq!(result, Ipv6Net::new(IPV6, 128).unwrap());
+
+    let result: Ipv6Net = "::/0".parse().unwrap();
+    assert_eq!(result, Ipv6Net::new(Ipv6Addr::UNSPECIFIED, 0).unwrap());
+
+    assert!(Ipv6Net::from_str(IPV6_STR_FULL).is_err());
+    assert!(Ipv6Net::from_str(IPV6_STR_PORT).is_err());
+    assert!(Ipv6Net::from_str(IPV4_STR_NET).is_err());
+    assert!(Ipv6Net::from_str("2001:db8::/129").is_err());
+    assert!(Ipv6Net::from_str("2001:db8::/1280").is_err());
+    assert!(Ipv6Net::from_str("[2001:db8::]/32").is_err());
+}
+
+#[test]
+fn parse_ip_net() {
+    let result: IpNet = IPV4_STR_NET.parse().unwrap();
+    assert_eq!(result, IpNet::V4(Ipv4Net::new(IPV4, 24).unwrap()));
+
+    let result: IpNet = IPV6_STR_NET.parse().unwrap();
+    assert_eq!(result, IpNet::V6(Ipv6Net::new(IPV6, 64).unwrap()));
+
+    assert!(IpNet::from_str(IPV4_STR).is_err());
+    assert!(IpNet::from_str(IPV6_STR_COMPRESS).is_err());
+    assert!(IpNet::from_str("192.168.0.1/64").is_err());
+}
+
 #[test]
 fn ipv6_corner_cases() {
     let result: Ipv6Addr = "1::".parse().unwrap();
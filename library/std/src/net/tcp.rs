@@ -6,8 +6,8 @@
 use crate::io::prelude::*;
 
 use crate::fmt;
-use crate::io::{self, Initializer, IoSlice, IoSliceMut};
-use crate::net::{Shutdown, SocketAddr, ToSocketAddrs};
+use crate::io::{self, Initializer, IoSlice, IoSliceMut, ReadBuf};
+use crate::net::{Shutdown, SocketAddr, SocketOptions, ToSocketAddrs};
 use crate::sys_common::net as net_imp;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::time::Duration;
@@ -556,6 +556,10 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     #[inline]
     fn is_read_vectored(&self) -> bool {
         self.0.is_read_vectored()
@@ -596,6 +600,10 @@ fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
 
+    fn read_buf(&mut self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     #[inline]
     fn is_read_vectored(&self) -> bool {
         self.0.is_read_vectored()
@@ -940,3 +948,195 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A builder for TCP sockets, used to set options that only take effect
+/// before the socket is bound or connected.
+///
+/// Options are collected in the builder and applied to a fresh socket by
+/// [`connect`], which produces a [`TcpStream`], or [`listen`], which produces
+/// a [`TcpListener`]. Options that can be changed on a live socket, like
+/// [`TcpStream::set_nodelay`], are not part of the builder.
+///
+/// Any option that was not set keeps the platform's default, except that
+/// [`listen`] behaves like [`TcpListener::bind`] with regard to
+/// [`reuse_address`] and the listen backlog.
+///
+/// [`connect`]: TcpBuilder::connect
+/// [`listen`]: TcpBuilder::listen
+/// [`reuse_address`]: TcpBuilder::reuse_address
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket_builder)]
+/// use std::net::TcpBuilder;
+///
+/// let listener = TcpBuilder::new()
+///     .reuse_port(true)
+///     .recv_buffer_size(256 * 1024)
+///     .backlog(1024)
+///     .listen("0.0.0.0:8080")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct TcpBuilder(SocketOptions);
+
+impl TcpBuilder {
+    /// Creates a new builder with every option left at its default.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn new() -> TcpBuilder {
+        TcpBuilder(SocketOptions::default())
+    }
+
+    /// Sets the `SO_REUSEADDR` option.
+    ///
+    /// On Unix this allows binding to an address whose previous socket is
+    /// still in the `TIME_WAIT` state, and [`listen`] enables it unless told
+    /// otherwise, like [`TcpListener::bind`] does. On Windows it allows
+    /// binding to an address that is actively in use, so it is only set if
+    /// requested explicitly.
+    ///
+    /// [`listen`]: TcpBuilder::listen
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuse_address(&mut self, reuse: bool) -> &mut TcpBuilder {
+        self.0.reuse_address = Some(reuse);
+        self
+    }
+
+    /// Sets the `SO_REUSEPORT` option, which allows several sockets to bind
+    /// to the same address and port.
+    ///
+    /// Only some Unix platforms support this option; on the others, creating
+    /// the socket fails if it is requested.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuse_port(&mut self, reuse: bool) -> &mut TcpBuilder {
+        self.0.reuse_port = reuse;
+        self
+    }
+
+    /// Sets the size of the kernel's send buffer (`SO_SNDBUF`), in bytes.
+    ///
+    /// The kernel may adjust the requested size; Linux, for example, doubles
+    /// it to leave room for bookkeeping.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn send_buffer_size(&mut self, size: usize) -> &mut TcpBuilder {
+        self.0.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the kernel's receive buffer (`SO_RCVBUF`), in bytes.
+    ///
+    /// This also determines the largest window the socket can advertise, so
+    /// it needs to be set before connecting or listening to have full effect.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&mut self, size: usize) -> &mut TcpBuilder {
+        self.0.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the `IPV6_V6ONLY` option.
+    ///
+    /// If this is `true`, a socket bound to an IPv6 address only accepts IPv6
+    /// connections, otherwise it can also accept IPv4 connections through
+    /// IPv4-mapped addresses. This option is ignored for IPv4 sockets.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn only_v6(&mut self, only_v6: bool) -> &mut TcpBuilder {
+        self.0.only_v6 = Some(only_v6);
+        self
+    }
+
+    /// Enables TCP keepalive probes (`SO_KEEPALIVE`) with the platform's
+    /// default timing.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn keepalive(&mut self, keepalive: bool) -> &mut TcpBuilder {
+        self.0.keepalive = keepalive;
+        self
+    }
+
+    /// Sets how long the connection has to be idle before the first
+    /// keepalive probe is sent, and enables keepalive.
+    ///
+    /// The time is rounded up to whole seconds on Unix and to milliseconds on
+    /// Windows. Not all platforms support setting it; on those, creating the
+    /// socket fails.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn keepalive_time(&mut self, time: Duration) -> &mut TcpBuilder {
+        self.0.keepalive_time = Some(time);
+        self
+    }
+
+    /// Sets the time between two keepalive probes that went unanswered, and
+    /// enables keepalive.
+    ///
+    /// This is rounded and supported like [`keepalive_time`].
+    ///
+    /// [`keepalive_time`]: TcpBuilder::keepalive_time
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn keepalive_interval(&mut self, interval: Duration) -> &mut TcpBuilder {
+        self.0.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Sets the maximum number of pending connections that [`listen`] asks
+    /// the kernel to queue. Defaults to 128.
+    ///
+    /// The kernel may silently limit the backlog, for example to
+    /// `net.core.somaxconn` on Linux.
+    ///
+    /// [`listen`]: TcpBuilder::listen
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn backlog(&mut self, backlog: u32) -> &mut TcpBuilder {
+        self.0.backlog = Some(backlog);
+        self
+    }
+
+    /// Sets the local address that [`connect`] binds the socket to before
+    /// connecting.
+    ///
+    /// By default the operating system picks the local address and port.
+    /// [`listen`] ignores this option and binds to the address it was given.
+    ///
+    /// [`connect`]: TcpBuilder::connect
+    /// [`listen`]: TcpBuilder::listen
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn local_addr(&mut self, addr: SocketAddr) -> &mut TcpBuilder {
+        self.0.local_addr = Some(addr);
+        self
+    }
+
+    /// Opens a TCP connection to a remote host, using a socket configured
+    /// with the options of this builder.
+    ///
+    /// Addresses are tried in turn like with [`TcpStream::connect`], each
+    /// with a new socket.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(net_socket_builder)]
+    /// use std::net::{SocketAddr, TcpBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpBuilder::new()
+    ///     .local_addr(SocketAddr::from(([192, 168, 0, 2], 0)))
+    ///     .keepalive_time(Duration::from_secs(60))
+    ///     .connect("192.168.0.1:8080")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpStream> {
+        super::each_addr(addr, |addr| net_imp::TcpStream::connect_with(addr, &self.0))
+            .map(TcpStream)
+    }
+
+    /// Creates a [`TcpListener`] bound to `addr`, using a socket configured
+    /// with the options of this builder.
+    ///
+    /// Addresses are tried in turn like with [`TcpListener::bind`].
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn listen<A: ToSocketAddrs>(&self, addr: A) -> io::Result<TcpListener> {
+        super::each_addr(addr, |addr| net_imp::TcpListener::bind_with(addr, &self.0))
+            .map(TcpListener)
+    }
+}
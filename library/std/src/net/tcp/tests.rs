@@ -860,3 +860,47 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn builder_smoke() {
+    each_ip(&mut |addr| {
+        let listener = t!(TcpBuilder::new()
+            .backlog(16)
+            .recv_buffer_size(64 * 1024)
+            .only_v6(true)
+            .listen(&addr));
+
+        // Bind the connecting side to a known port to see that `local_addr`
+        // is used.
+        let local = match addr {
+            SocketAddr::V4(..) => next_test_ip4(),
+            SocketAddr::V6(..) => next_test_ip6(),
+        };
+        let _t = thread::spawn(move || {
+            let mut stream = t!(TcpBuilder::new()
+                .local_addr(local)
+                .keepalive_time(Duration::from_secs(60))
+                .connect(&addr));
+            assert_eq!(t!(stream.local_addr()), local);
+            t!(stream.write(&[7]));
+        });
+
+        let (mut stream, peer) = t!(listener.accept());
+        assert_eq!(peer, local);
+        let mut buf = [0];
+        t!(stream.read(&mut buf));
+        assert_eq!(buf[0], 7);
+    })
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos"))]
+fn builder_reuse_port() {
+    each_ip(&mut |addr| {
+        let mut builder = TcpBuilder::new();
+        builder.reuse_port(true);
+        let _listener1 = t!(builder.listen(&addr));
+        let _listener2 = t!(builder.listen(&addr));
+    })
+}
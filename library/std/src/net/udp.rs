@@ -3,7 +3,7 @@
 
 use crate::fmt;
 use crate::io::{self, Error, ErrorKind};
-use crate::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+use crate::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketOptions, ToSocketAddrs};
 use crate::sys_common::net as net_imp;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::time::Duration;
@@ -801,3 +801,94 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A builder for UDP sockets, used to set options that only take effect
+/// before the socket is bound.
+///
+/// This is the UDP counterpart of [`TcpBuilder`]: the options are applied to
+/// a fresh socket by [`bind`], and any option that was not set keeps the
+/// platform's default.
+///
+/// [`TcpBuilder`]: crate::net::TcpBuilder
+/// [`bind`]: UdpBuilder::bind
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_socket_builder)]
+/// use std::net::UdpBuilder;
+///
+/// // Several processes can share the port, the kernel spreads the
+/// // incoming datagrams between them.
+/// let socket = UdpBuilder::new()
+///     .reuse_port(true)
+///     .recv_buffer_size(4 * 1024 * 1024)
+///     .bind("0.0.0.0:5353")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[unstable(feature = "net_socket_builder", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct UdpBuilder(SocketOptions);
+
+impl UdpBuilder {
+    /// Creates a new builder with every option left at its default.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn new() -> UdpBuilder {
+        UdpBuilder(SocketOptions::default())
+    }
+
+    /// Sets the `SO_REUSEADDR` option.
+    ///
+    /// This is commonly needed by multicast receivers, so that several of
+    /// them can bind to the group's port on the same host.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuse_address(&mut self, reuse: bool) -> &mut UdpBuilder {
+        self.0.reuse_address = Some(reuse);
+        self
+    }
+
+    /// Sets the `SO_REUSEPORT` option, which allows several sockets to bind
+    /// to the same address and port.
+    ///
+    /// See [`TcpBuilder::reuse_port`] for platform support.
+    ///
+    /// [`TcpBuilder::reuse_port`]: crate::net::TcpBuilder::reuse_port
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn reuse_port(&mut self, reuse: bool) -> &mut UdpBuilder {
+        self.0.reuse_port = reuse;
+        self
+    }
+
+    /// Sets the size of the kernel's send buffer (`SO_SNDBUF`), in bytes.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn send_buffer_size(&mut self, size: usize) -> &mut UdpBuilder {
+        self.0.send_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the size of the kernel's receive buffer (`SO_RCVBUF`), in bytes.
+    ///
+    /// Datagrams that arrive while this buffer is full are dropped, so
+    /// servers with bursty traffic usually want to raise it.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn recv_buffer_size(&mut self, size: usize) -> &mut UdpBuilder {
+        self.0.recv_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the `IPV6_V6ONLY` option, which is ignored for IPv4 sockets.
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn only_v6(&mut self, only_v6: bool) -> &mut UdpBuilder {
+        self.0.only_v6 = Some(only_v6);
+        self
+    }
+
+    /// Creates a [`UdpSocket`] bound to `addr`, using a socket configured
+    /// with the options of this builder.
+    ///
+    /// Addresses are tried in turn like with [`UdpSocket::bind`].
+    #[unstable(feature = "net_socket_builder", issue = "none")]
+    pub fn bind<A: ToSocketAddrs>(&self, addr: A) -> io::Result<UdpSocket> {
+        super::each_addr(addr, |addr| net_imp::UdpSocket::bind_with(addr, &self.0)).map(UdpSocket)
+    }
+}
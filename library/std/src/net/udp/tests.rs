@@ -364,3 +364,33 @@ fn set_nonblocking() {
         }
     })
 }
+
+#[test]
+fn builder_smoke() {
+    each_ip(&mut |server_ip, client_ip| {
+        let server = t!(UdpBuilder::new()
+            .send_buffer_size(64 * 1024)
+            .recv_buffer_size(64 * 1024)
+            .bind(&server_ip));
+        let client = t!(UdpBuilder::new().only_v6(true).bind(&client_ip));
+        assert_eq!(t!(server.local_addr()), server_ip);
+
+        t!(client.send_to(&[99], &server_ip));
+        let mut buf = [0];
+        let (nread, src) = t!(server.recv_from(&mut buf));
+        assert_eq!(nread, 1);
+        assert_eq!(buf[0], 99);
+        assert_eq!(src, client_ip);
+    })
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos"))]
+fn builder_reuse_port() {
+    each_ip(&mut |addr, _| {
+        let mut builder = UdpBuilder::new();
+        builder.reuse_port(true);
+        let _socket1 = t!(builder.bind(&addr));
+        let _socket2 = t!(builder.bind(&addr));
+    })
+}
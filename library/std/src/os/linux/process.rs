@@ -0,0 +1,164 @@
+//! Linux-specific extensions to primitives in the `std::process` module.
+
+#![unstable(feature = "linux_pidfd", issue = "none")]
+
+use crate::io;
+use crate::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use crate::process;
+use crate::sys::fd::FileDesc;
+use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+
+/// A file descriptor that refers to a process.
+///
+/// Unlike a process ID, a pidfd keeps referring to the same process after it
+/// has exited and been reaped, so it can never be used to signal or wait on an
+/// unrelated process that happened to be assigned the same ID later.
+///
+/// A `PidFd` is created by enabling [`create_pidfd`] on a [`Command`] before
+/// spawning it, and can then be retrieved from the [`Child`] with [`pidfd`] or
+/// [`take_pidfd`]. The file descriptor becomes readable once the process has
+/// exited, so it can be included in a `poll` or `epoll` loop.
+///
+/// Refer to the man page of [`pidfd_open(2)`] for further details.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(linux_pidfd)]
+/// use std::os::linux::process::{ChildExt, CommandExt};
+/// use std::os::unix::io::AsRawFd;
+/// use std::process::Command;
+///
+/// let mut child = Command::new("echo")
+///     .create_pidfd(true)
+///     .spawn()
+///     .expect("failed to spawn child");
+///
+/// let pidfd = child.pidfd().expect("failed to retrieve pidfd");
+/// println!("pidfd: {}", pidfd.as_raw_fd());
+/// ```
+///
+/// [`Command`]: process::Command
+/// [`create_pidfd`]: CommandExt::create_pidfd
+/// [`Child`]: process::Child
+/// [`pidfd`]: ChildExt::pidfd
+/// [`take_pidfd`]: ChildExt::take_pidfd
+/// [`pidfd_open(2)`]: https://man7.org/linux/man-pages/man2/pidfd_open.2.html
+#[derive(Debug)]
+pub struct PidFd {
+    inner: FileDesc,
+}
+
+impl AsInner<FileDesc> for PidFd {
+    fn as_inner(&self) -> &FileDesc {
+        &self.inner
+    }
+}
+
+impl FromInner<FileDesc> for PidFd {
+    fn from_inner(inner: FileDesc) -> PidFd {
+        PidFd { inner }
+    }
+}
+
+impl IntoInner<FileDesc> for PidFd {
+    fn into_inner(self) -> FileDesc {
+        self.inner
+    }
+}
+
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.raw()
+    }
+}
+
+impl FromRawFd for PidFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> PidFd {
+        PidFd { inner: FileDesc::new(fd) }
+    }
+}
+
+impl IntoRawFd for PidFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw()
+    }
+}
+
+mod private {
+    /// This trait being unreachable from outside the crate prevents other
+    /// implementations of the extension traits in this module, which allows
+    /// adding more methods to them in the future.
+    #[unstable(feature = "linux_pidfd", issue = "none")]
+    pub trait Sealed {}
+}
+
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl private::Sealed for process::Child {}
+#[unstable(feature = "linux_pidfd", issue = "none")]
+impl private::Sealed for process::Command {}
+
+/// Linux-specific extensions to [`process::Child`].
+pub trait ChildExt: private::Sealed {
+    /// Obtains a reference to the [`PidFd`] created for this [`Child`], if
+    /// available.
+    ///
+    /// A pidfd is only created if [`create_pidfd`] was enabled before
+    /// spawning, and the kernel supports using pidfds to wait on processes,
+    /// which requires Linux 5.4 or later. An error is returned otherwise, or
+    /// if the pidfd has been taken out with [`take_pidfd`]. If creating the
+    /// pidfd failed, the error returned is the one it failed with.
+    ///
+    /// While the child holds its pidfd, [`Child::kill`], [`Child::wait`] and
+    /// [`Child::try_wait`] use it in place of the process ID.
+    ///
+    /// [`Child`]: process::Child
+    /// [`Child::kill`]: process::Child::kill
+    /// [`Child::wait`]: process::Child::wait
+    /// [`Child::try_wait`]: process::Child::try_wait
+    /// [`create_pidfd`]: CommandExt::create_pidfd
+    /// [`take_pidfd`]: ChildExt::take_pidfd
+    fn pidfd(&self) -> io::Result<&PidFd>;
+
+    /// Takes ownership of the [`PidFd`] created for this [`Child`], if
+    /// available.
+    ///
+    /// See [`pidfd`] for when a pidfd is available. Afterwards, the child is
+    /// managed through its process ID again.
+    ///
+    /// [`Child`]: process::Child
+    /// [`pidfd`]: ChildExt::pidfd
+    fn take_pidfd(&mut self) -> io::Result<PidFd>;
+}
+
+/// Linux-specific extensions to [`process::Command`].
+pub trait CommandExt: private::Sealed {
+    /// Sets whether a [`PidFd`] should be created for the [`Child`] spawned
+    /// by this [`Command`]. By default, no pidfd is created.
+    ///
+    /// The pidfd can be retrieved from the child with [`pidfd`] or
+    /// [`take_pidfd`]. Failing to create it does not fail the spawn.
+    ///
+    /// [`Command`]: process::Command
+    /// [`Child`]: process::Child
+    /// [`pidfd`]: ChildExt::pidfd
+    /// [`take_pidfd`]: ChildExt::take_pidfd
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+}
+
+impl CommandExt for process::Command {
+    fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
+        self.as_inner_mut().create_pidfd(val);
+        self
+    }
+}
+
+impl ChildExt for process::Child {
+    fn pidfd(&self) -> io::Result<&PidFd> {
+        self.as_inner().pidfd()
+    }
+
+    fn take_pidfd(&mut self) -> io::Result<PidFd> {
+        self.as_inner_mut().take_pidfd()
+    }
+}
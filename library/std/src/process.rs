@@ -102,17 +102,20 @@
 
 use crate::io::prelude::*;
 
+use crate::cmp;
 use crate::ffi::OsStr;
 use crate::fmt;
 use crate::fs;
 use crate::io::{self, Initializer, IoSlice, IoSliceMut};
 use crate::path::Path;
 use crate::str;
-use crate::sys::pipe::{read2, AnonPipe};
+use crate::sys::pipe::{read2, read2_until, AnonPipe};
 use crate::sys::process as imp;
 #[unstable(feature = "command_access", issue = "44434")]
 pub use crate::sys_common::process::CommandEnvs;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::thread;
+use crate::time::{Duration, Instant};
 
 /// Representation of a running or exited child process.
 ///
@@ -206,6 +209,12 @@ fn as_inner(&self) -> &imp::Process {
     }
 }
 
+impl AsInnerMut<imp::Process> for Child {
+    fn as_inner_mut(&mut self) -> &mut imp::Process {
+        &mut self.handle
+    }
+}
+
 impl FromInner<(imp::Process, imp::StdioPipes)> for Child {
     fn from_inner((handle, io): (imp::Process, imp::StdioPipes)) -> Child {
         Child {
@@ -880,6 +889,45 @@ pub fn output(&mut self) -> io::Result<Output> {
             .and_then(|p| p.wait_with_output())
     }
 
+    /// Executes the command as a child process like [`output`], but kills it
+    /// if it is still running once `timeout` has elapsed.
+    ///
+    /// Stdout and stderr are captured the same way as with [`output`]. If the
+    /// timeout elapses, whatever the child wrote up to that point is returned
+    /// together with the exit status of the killed child, and
+    /// [`OutputTimeoutResult::timed_out`] returns `true`.
+    ///
+    /// The output is collected on the calling thread, without spawning any
+    /// helper threads. See [`Child::wait_with_output_timeout`] for details.
+    ///
+    /// [`output`]: Command::output
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_output_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let (output, result) = Command::new("make")
+    ///     .output_timeout(Duration::from_secs(60))
+    ///     .expect("failed to execute process");
+    ///
+    /// if result.timed_out() {
+    ///     println!("make took too long, partial output: {:?}", output.stdout);
+    /// }
+    /// ```
+    #[unstable(feature = "process_output_timeout", issue = "none")]
+    pub fn output_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> io::Result<(Output, OutputTimeoutResult)> {
+        self.inner
+            .spawn(imp::Stdio::MakePipe, false)
+            .map(Child::from_inner)
+            .and_then(|p| p.wait_with_output_timeout(timeout))
+    }
+
     /// Executes a command as a child process, waiting for it to finish and
     /// collecting its exit status.
     ///
@@ -1664,6 +1712,121 @@ pub fn wait_with_output(mut self) -> io::Result<Output> {
         let status = self.wait()?;
         Ok(Output { status, stdout, stderr })
     }
+
+    /// Collects the output of the child like [`wait_with_output`], but kills
+    /// it if it has not exited once `timeout` has elapsed.
+    ///
+    /// The stdout and stderr pipes are read on the calling thread until both
+    /// are closed or the timeout elapses, after which the child is waited
+    /// for until the same deadline. If the deadline is reached, the child is
+    /// killed with [`kill`] and reaped, and the output read so far is
+    /// returned along with its exit status.
+    ///
+    /// Note that the deadline only covers the direct child: a grandchild that
+    /// inherited the pipes can keep them open after the child was killed, but
+    /// it no longer delays this function.
+    ///
+    /// [`wait_with_output`]: Child::wait_with_output
+    /// [`kill`]: Child::kill
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_output_timeout)]
+    /// use std::process::{Command, Stdio};
+    /// use std::time::Duration;
+    ///
+    /// let child = Command::new("sh")
+    ///     .arg("-c")
+    ///     .arg("echo started; sleep 100")
+    ///     .stdout(Stdio::piped())
+    ///     .spawn()
+    ///     .expect("failed to execute child");
+    ///
+    /// let (output, result) = child
+    ///     .wait_with_output_timeout(Duration::from_millis(500))
+    ///     .expect("failed to wait on child");
+    ///
+    /// assert!(result.timed_out());
+    /// assert_eq!(output.stdout, b"started\n");
+    /// assert!(!output.status.success());
+    /// ```
+    #[unstable(feature = "process_output_timeout", issue = "none")]
+    pub fn wait_with_output_timeout(
+        mut self,
+        timeout: Duration,
+    ) -> io::Result<(Output, OutputTimeoutResult)> {
+        drop(self.stdin.take());
+
+        // A timeout too large to represent is the same as no timeout at all.
+        let deadline = Instant::now().checked_add(timeout);
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let finished = read2_until(
+            self.stdout.take().map(ChildStdout::into_inner),
+            &mut stdout,
+            self.stderr.take().map(ChildStderr::into_inner),
+            &mut stderr,
+            deadline,
+        )?;
+
+        let status = match deadline {
+            Some(deadline) if finished => self.wait_deadline(deadline)?,
+            Some(_) => None,
+            None => Some(self.wait()?),
+        };
+        let (status, timed_out) = match status {
+            Some(status) => (status, false),
+            None => (self.kill_and_wait()?, true),
+        };
+        Ok((Output { status, stdout, stderr }, OutputTimeoutResult(timed_out)))
+    }
+
+    /// Waits for the child to exit until `deadline`, returning `None` if it
+    /// is still running by then.
+    ///
+    /// There is no portable way to block on a child with a timeout, so this
+    /// polls `try_wait` with an increasing interval.
+    fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        let mut interval = Duration::from_millis(1);
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(Some(status));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            thread::sleep(cmp::min(interval, deadline - now));
+            interval = cmp::min(interval * 2, Duration::from_millis(50));
+        }
+    }
+
+    fn kill_and_wait(&mut self) -> io::Result<ExitStatus> {
+        if let Err(e) = self.kill() {
+            // The child may have exited on its own in the meantime, which
+            // some platforms report as a failure to kill it.
+            if self.try_wait()?.is_none() {
+                return Err(e);
+            }
+        }
+        self.wait()
+    }
+}
+
+/// Whether [`Command::output_timeout`] or [`Child::wait_with_output_timeout`]
+/// had to kill the child.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[unstable(feature = "process_output_timeout", issue = "none")]
+pub struct OutputTimeoutResult(bool);
+
+impl OutputTimeoutResult {
+    /// Returns `true` if the timeout elapsed and the child was killed.
+    ///
+    /// The output returned alongside this result is then incomplete.
+    #[unstable(feature = "process_output_timeout", issue = "none")]
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
 }
 
 /// Terminates the current process with the specified exit code.
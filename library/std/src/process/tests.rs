@@ -3,6 +3,7 @@
 use super::{Command, Output, Stdio};
 use crate::io::ErrorKind;
 use crate::str;
+use crate::time::{Duration, Instant};
 
 // FIXME(#10380) these tests should not all be ignored on android.
 
@@ -203,6 +204,43 @@ fn test_wait_with_output_once() {
     assert_eq!(stderr, Vec::new());
 }
 
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_output_timeout_finished() {
+    let (output, result) = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "echo hello"]).output_timeout(Duration::from_secs(60))
+    } else {
+        Command::new("echo").arg("hello").output_timeout(Duration::from_secs(60))
+    }
+    .unwrap();
+
+    assert!(!result.timed_out());
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "hello");
+    assert_eq!(output.stderr, Vec::new());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks", target_os = "android"), ignore)]
+fn test_output_timeout_kills_child() {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "echo started& ping -n 100 127.0.0.1 >NUL"]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(&["-c", "echo started; exec sleep 100"]);
+        cmd
+    };
+
+    let start = Instant::now();
+    let (output, result) = cmd.output_timeout(Duration::from_millis(500)).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(50));
+    assert!(result.timed_out());
+    assert!(!output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "started");
+}
+
 #[cfg(all(unix, not(target_os = "android")))]
 pub fn env_cmd() -> Command {
     Command::new("env")
@@ -399,3 +437,82 @@ fn test_command_implements_send_sync() {
     fn take_send_sync_type<T: Send + Sync>(_: T) {}
     take_send_sync_type(Command::new(""))
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_pidfd() {
+    use crate::os::linux::process::{ChildExt, CommandExt};
+    use crate::os::unix::process::ExitStatusExt;
+
+    let mut child = Command::new("true").spawn().unwrap();
+    assert!(child.pidfd().unwrap_err().raw_os_error().is_none());
+    child.wait().unwrap();
+
+    let mut child = Command::new("sleep").arg("1000").create_pidfd(true).spawn().unwrap();
+    if let Err(err) = child.pidfd() {
+        // The kernel is too old to wait on pidfds, which is reported with the
+        // error from trying to create one.
+        assert!(err.raw_os_error().is_some());
+        child.kill().unwrap();
+        child.wait().unwrap();
+        return;
+    }
+    assert!(child.try_wait().unwrap().is_none());
+    child.kill().unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+    assert!(child.kill().is_err());
+
+    let mut child = Command::new("sh").arg("-c").arg("exit 3").create_pidfd(true).spawn().unwrap();
+    let pidfd = child.take_pidfd();
+    assert!(child.pidfd().is_err());
+    assert!(child.take_pidfd().is_err());
+    assert_eq!(child.wait().unwrap().code(), Some(3));
+    drop(pidfd);
+
+    let mut child = Command::new("sh").arg("-c").arg("exit 5").create_pidfd(true).spawn().unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(5));
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "fuchsia"), ignore)]
+fn test_process_group() {
+    use crate::os::unix::process::CommandExt;
+
+    // Run once on the `posix_spawn` path where available, and once on the
+    // fork/exec path, which a `pre_exec` closure forces.
+    for force_fork in [false, true].iter().copied() {
+        let mut cmd = Command::new("sleep");
+        cmd.arg("1000").process_group(0);
+        if force_fork {
+            unsafe {
+                cmd.pre_exec(|| Ok(()));
+            }
+        }
+        let mut child = cmd.spawn().unwrap();
+        let pid = child.id() as libc::pid_t;
+        assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+        assert_ne!(unsafe { libc::getpgid(0) }, pid);
+        // Signal the whole group.
+        assert_eq!(unsafe { libc::kill(-pid, libc::SIGKILL) }, 0);
+        child.wait().unwrap();
+    }
+}
+
+#[test]
+#[cfg(unix)]
+#[cfg_attr(any(target_os = "vxworks", target_os = "fuchsia"), ignore)]
+fn test_setsid() {
+    use crate::os::unix::process::CommandExt;
+
+    let mut child = Command::new("sleep").arg("1000").setsid(true).spawn().unwrap();
+    let pid = child.id() as libc::pid_t;
+    assert_eq!(unsafe { libc::getsid(pid) }, pid);
+    assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    // A session leader can't change its process group.
+    assert!(Command::new("true").setsid(true).process_group(0).spawn().is_err());
+}
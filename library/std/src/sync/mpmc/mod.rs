@@ -0,0 +1,693 @@
+//! Multi-producer, multi-consumer FIFO queue communication primitives.
+//!
+//! This module provides channels that work like the ones in [`mpsc`], except
+//! that the receiving half can be cloned and shared between threads as well.
+//! Every message sent on a channel is received by exactly one [`Receiver`],
+//! which makes these channels a natural fit for distributing work among a pool
+//! of threads.
+//!
+//! Both halves of a channel are created by one of two functions:
+//!
+//! 1. The [`channel`] function creates an asynchronous, infinitely buffered
+//!    channel, where sends never block.
+//!
+//! 2. The [`sync_channel`] function creates a synchronous, bounded channel,
+//!    where sends block until there is room in the buffer. A bound of 0 makes
+//!    the channel a "rendezvous" channel, where each message is handed off
+//!    directly to a waiting receiver.
+//!
+//! A channel disconnects when all of the [`Sender`]s or all of the
+//! [`Receiver`]s have been dropped, at which point the remaining half will
+//! report errors in the same way as the [`mpsc`] channels do.
+//!
+//! [`mpsc`]: crate::sync::mpsc
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(mpmc_channel)]
+//! use std::sync::mpmc::channel;
+//! use std::thread;
+//!
+//! let (tx, rx) = channel();
+//! let workers: Vec<_> = (0..4)
+//!     .map(|_| {
+//!         let rx = rx.clone();
+//!         thread::spawn(move || rx.iter().map(|job: u32| job * 2).sum::<u32>())
+//!     })
+//!     .collect();
+//!
+//! for job in 0..100 {
+//!     tx.send(job).unwrap();
+//! }
+//! drop(tx);
+//!
+//! let total: u32 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+//! assert_eq!(total, 9900);
+//! ```
+
+#![unstable(feature = "mpmc_channel", issue = "none")]
+
+#[cfg(all(test, not(target_os = "emscripten")))]
+mod tests;
+
+use crate::collections::VecDeque;
+use crate::fmt;
+use crate::mem;
+use crate::sync::mpsc::blocking::{self, SignalToken};
+use crate::sync::{Arc, Mutex, MutexGuard};
+use crate::time::{Duration, Instant};
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub use crate::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+
+/// The receiving half of an [`mpmc`](self) channel.
+///
+/// Unlike [`mpsc::Receiver`], this half can be cloned and used from several
+/// threads at once. Each message is received by only one of the receivers.
+///
+/// [`mpsc::Receiver`]: crate::sync::mpsc::Receiver
+///
+/// # Examples
+///
+/// ```rust
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpmc::channel;
+/// use std::thread;
+///
+/// let (tx, rx) = channel();
+/// let rx2 = rx.clone();
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+///
+/// let t = thread::spawn(move || rx2.recv().unwrap());
+/// let a = rx.recv().unwrap();
+/// let b = t.join().unwrap();
+/// assert_eq!(a + b, 3);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub struct Receiver<T> {
+    inner: Arc<Channel<T>>,
+}
+
+/// The sending half of an [`mpmc`](self) channel.
+///
+/// Messages are sent with [`send`], which blocks if the channel was created
+/// by [`sync_channel`] and its buffer is full, or with [`try_send`], which
+/// never blocks.
+///
+/// [`send`]: Sender::send
+/// [`try_send`]: Sender::try_send
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub struct Sender<T> {
+    inner: Arc<Channel<T>>,
+}
+
+/// An iterator over messages on a [`Receiver`], created by [`iter`].
+///
+/// This iterator will block whenever [`next`] is called,
+/// waiting for a new message, and [`None`] will be returned
+/// when the corresponding channel has hung up.
+///
+/// [`iter`]: Receiver::iter
+/// [`next`]: Iterator::next
+#[unstable(feature = "mpmc_channel", issue = "none")]
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An iterator that attempts to yield all pending values for a [`Receiver`],
+/// created by [`try_iter`].
+///
+/// [`None`] will be returned when there are no pending values remaining or
+/// if the corresponding channel has hung up.
+///
+/// [`try_iter`]: Receiver::try_iter
+#[unstable(feature = "mpmc_channel", issue = "none")]
+#[derive(Debug)]
+pub struct TryIter<'a, T: 'a> {
+    rx: &'a Receiver<T>,
+}
+
+/// An owning iterator over messages on a [`Receiver`],
+/// created by **Receiver::into_iter**.
+///
+/// This iterator will block whenever [`next`]
+/// is called, waiting for a new message, and [`None`] will be
+/// returned if the corresponding channel has hung up.
+///
+/// [`next`]: Iterator::next
+#[unstable(feature = "mpmc_channel", issue = "none")]
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    rx: Receiver<T>,
+}
+
+struct Channel<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    cap: Option<usize>, // `None` for channels without a bound
+    senders: usize,
+    receivers: usize,
+
+    blocked_senders: Waiters,
+    blocked_receivers: Waiters,
+    /// How many receivers are currently blocked in `recv`, including the ones
+    /// that have been woken up but haven't picked up their message yet.
+    waiting_receivers: usize,
+}
+
+/// Threads blocked on a channel, in the order in which they started waiting.
+///
+/// Every waiter is woken up at most once. A thread that stops waiting on its
+/// own (because it timed out) removes itself, so that no wakeup is wasted on
+/// a thread that isn't waiting anymore.
+struct Waiters {
+    next_id: usize,
+    tokens: VecDeque<(usize, SignalToken)>,
+}
+
+impl Waiters {
+    fn new() -> Waiters {
+        Waiters { next_id: 0, tokens: VecDeque::new() }
+    }
+
+    fn push(&mut self, token: SignalToken) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.tokens.push_back((id, token));
+        id
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.tokens.retain(|&(waiter, _)| waiter != id);
+    }
+
+    /// Takes the token of the longest waiting thread, to be signaled once the
+    /// channel's lock has been released.
+    fn take_one(&mut self) -> Option<SignalToken> {
+        self.tokens.pop_front().map(|(_, token)| token)
+    }
+
+    fn take_all(&mut self) -> VecDeque<(usize, SignalToken)> {
+        mem::take(&mut self.tokens)
+    }
+}
+
+impl<T> State<T> {
+    fn is_full(&self) -> bool {
+        match self.cap {
+            None => false,
+            // Without a buffer, a message can only be accepted if there's a
+            // receiver waiting to take it.
+            Some(0) => self.queue.len() >= self.waiting_receivers,
+            Some(cap) => self.queue.len() >= cap,
+        }
+    }
+}
+
+enum Failure {
+    Timeout,
+    Disconnected,
+}
+
+/// Creates a new asynchronous multi-consumer channel, returning the
+/// sender/receiver halves.
+///
+/// All data sent on the [`Sender`] will become available on one of the
+/// [`Receiver`]s in the same order as it was sent, and no [`send`] will block
+/// the calling thread. Both halves can be cloned.
+///
+/// [`send`]: Sender::send
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpmc::channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = channel();
+/// let receiver2 = receiver.clone();
+///
+/// thread::spawn(move || {
+///     sender.send(1).unwrap();
+///     sender.send(2).unwrap();
+/// });
+///
+/// assert_eq!(receiver.recv().unwrap() + receiver2.recv().unwrap(), 3);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(None)
+}
+
+/// Creates a new synchronous, bounded multi-consumer channel.
+///
+/// Like [`mpsc::sync_channel`], the channel buffers at most `bound` messages
+/// and [`send`] blocks while the buffer is full. With a `bound` of 0, a
+/// message is only accepted once a receiver is waiting for it.
+///
+/// [`mpsc::sync_channel`]: crate::sync::mpsc::sync_channel
+/// [`send`]: Sender::send
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpmc_channel)]
+/// use std::sync::mpmc::sync_channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = sync_channel(1);
+///
+/// // this returns immediately
+/// sender.send(1).unwrap();
+///
+/// thread::spawn(move || {
+///     // this will block until the previous message has been received
+///     sender.send(2).unwrap();
+/// });
+///
+/// assert_eq!(receiver.recv().unwrap(), 1);
+/// assert_eq!(receiver.recv().unwrap(), 2);
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "none")]
+pub fn sync_channel<T>(bound: usize) -> (Sender<T>, Receiver<T>) {
+    new_channel(Some(bound))
+}
+
+fn new_channel<T>(cap: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Channel {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            cap,
+            senders: 1,
+            receivers: 1,
+            blocked_senders: Waiters::new(),
+            blocked_receivers: Waiters::new(),
+            waiting_receivers: 0,
+        }),
+    });
+    (Sender { inner: inner.clone() }, Receiver { inner })
+}
+
+/// Wakes up a thread, dropping the lock at the correct time
+fn wakeup<T>(token: Option<SignalToken>, guard: MutexGuard<'_, State<T>>) {
+    // We need to be careful to wake up the waiting thread *outside* of the mutex
+    // in case it incurs a context switch.
+    drop(guard);
+    if let Some(token) = token {
+        token.signal();
+    }
+}
+
+impl<T> Channel<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    fn send(&self, t: T) -> Result<(), T> {
+        let mut guard = self.lock();
+        loop {
+            if guard.receivers == 0 {
+                return Err(t);
+            }
+            if !guard.is_full() {
+                guard.queue.push_back(t);
+                let token = guard.blocked_receivers.take_one();
+                wakeup(token, guard);
+                return Ok(());
+            }
+
+            // No room; wait for a receiver to make some.
+            let (wait_token, signal_token) = blocking::tokens();
+            guard.blocked_senders.push(signal_token);
+            drop(guard);
+            wait_token.wait();
+            guard = self.lock();
+        }
+    }
+
+    fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        let mut guard = self.lock();
+        if guard.receivers == 0 {
+            Err(TrySendError::Disconnected(t))
+        } else if guard.is_full() {
+            Err(TrySendError::Full(t))
+        } else {
+            guard.queue.push_back(t);
+            let token = guard.blocked_receivers.take_one();
+            wakeup(token, guard);
+            Ok(())
+        }
+    }
+
+    fn recv(&self, deadline: Option<Instant>) -> Result<T, Failure> {
+        let mut guard = self.lock();
+        loop {
+            if let Some(t) = guard.queue.pop_front() {
+                // There's room for another message now.
+                let token = guard.blocked_senders.take_one();
+                wakeup(token, guard);
+                return Ok(t);
+            }
+            if guard.senders == 0 {
+                return Err(Failure::Disconnected);
+            }
+            if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                return Err(Failure::Timeout);
+            }
+
+            let (wait_token, signal_token) = blocking::tokens();
+            let id = guard.blocked_receivers.push(signal_token);
+            guard.waiting_receivers += 1;
+            // A receiver waiting on a rendezvous channel makes room for a
+            // message, so let a blocked sender know.
+            let token = if guard.cap == Some(0) { guard.blocked_senders.take_one() } else { None };
+            wakeup(token, guard);
+
+            match deadline {
+                Some(deadline) => {
+                    wait_token.wait_max_until(deadline);
+                }
+                None => wait_token.wait(),
+            }
+
+            guard = self.lock();
+            guard.waiting_receivers -= 1;
+            // If we timed out, our token is still in the queue and needs to be
+            // removed. Otherwise this is a no-op.
+            guard.blocked_receivers.remove(id);
+        }
+    }
+
+    fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut guard = self.lock();
+        match guard.queue.pop_front() {
+            Some(t) => {
+                let token = guard.blocked_senders.take_one();
+                wakeup(token, guard);
+                Ok(t)
+            }
+            None if guard.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn drop_chan(&self) {
+        let mut guard = self.lock();
+        guard.senders -= 1;
+        if guard.senders == 0 {
+            let waiters = guard.blocked_receivers.take_all();
+            drop(guard);
+            for (_, token) in waiters {
+                token.signal();
+            }
+        }
+    }
+
+    fn drop_port(&self) {
+        let mut guard = self.lock();
+        guard.receivers -= 1;
+        if guard.receivers == 0 {
+            let waiters = guard.blocked_senders.take_all();
+            // Nobody can receive the buffered messages anymore. They're
+            // destroyed outside of the lock, as their destructors may run
+            // arbitrary code.
+            let _data = mem::take(&mut guard.queue);
+            drop(guard);
+            for (_, token) in waiters {
+                token.signal();
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Sender
+////////////////////////////////////////////////////////////////////////////////
+
+impl<T> Sender<T> {
+    /// Sends a value on this channel, returning it back if it could not be
+    /// sent.
+    ///
+    /// A send fails only if all receivers have been dropped. For channels
+    /// created by [`sync_channel`], this blocks until there is room in the
+    /// buffer (or, with a bound of 0, until a receiver is waiting).
+    ///
+    /// A successful send does not guarantee that the value will be received,
+    /// as all receivers may be dropped before picking it up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::channel;
+    ///
+    /// let (tx, rx) = channel();
+    ///
+    /// // This send is always successful
+    /// tx.send(1).unwrap();
+    ///
+    /// // This send will fail because the receiver is gone
+    /// drop(rx);
+    /// assert_eq!(tx.send(1).unwrap_err().0, 1);
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn send(&self, t: T) -> Result<(), SendError<T>> {
+        self.inner.send(t).map_err(SendError)
+    }
+
+    /// Attempts to send a value on this channel without blocking.
+    ///
+    /// This fails with [`TrySendError::Full`] if the channel was created by
+    /// [`sync_channel`] and its buffer is full, and with
+    /// [`TrySendError::Disconnected`] if all receivers have been dropped.
+    /// Channels created by [`channel`] are never full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::{sync_channel, TrySendError};
+    ///
+    /// let (tx, rx) = sync_channel(1);
+    /// tx.try_send(1).unwrap();
+    /// assert_eq!(tx.try_send(2), Err(TrySendError::Full(2)));
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.try_send(3), Err(TrySendError::Disconnected(3)));
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn try_send(&self, t: T) -> Result<(), TrySendError<T>> {
+        self.inner.try_send(t)
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.inner.lock().senders += 1;
+        Sender { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.inner.drop_chan();
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Receiver
+////////////////////////////////////////////////////////////////////////////////
+
+impl<T> Receiver<T> {
+    /// Attempts to return a pending value on this channel without blocking.
+    ///
+    /// Compared with [`recv`], this function has two failure cases instead of
+    /// one (one for disconnection, one for an empty buffer).
+    ///
+    /// [`recv`]: Self::recv
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::{channel, Receiver, TryRecvError};
+    ///
+    /// let (_tx, rx): (_, Receiver<i32>) = channel();
+    ///
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.inner.try_recv()
+    }
+
+    /// Attempts to wait for a value on this channel, returning an error if
+    /// the corresponding channel has hung up.
+    ///
+    /// This function will always block the current thread if there is no data
+    /// available and it's possible for more data to be sent. Messages that
+    /// were sent before all senders disconnected are still received.
+    ///
+    /// When several receivers are waiting, each new message wakes up only one
+    /// of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::{channel, RecvError};
+    /// use std::thread;
+    ///
+    /// let (tx, rx) = channel();
+    /// let handle = thread::spawn(move || {
+    ///     tx.send(1u8).unwrap();
+    /// });
+    ///
+    /// handle.join().unwrap();
+    ///
+    /// assert_eq!(Ok(1), rx.recv());
+    /// assert_eq!(Err(RecvError), rx.recv());
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.inner.recv(None).map_err(|_| RecvError)
+    }
+
+    /// Attempts to wait for a value on this channel, returning an error if
+    /// the corresponding channel has hung up, or if it waits more than
+    /// `timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpmc_channel)]
+    /// use std::sync::mpmc::{channel, RecvTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = channel::<i32>();
+    ///
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+    ///
+    /// tx.send(1).unwrap();
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Ok(1));
+    /// ```
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.recv_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => self.recv().map_err(RecvTimeoutError::from),
+        }
+    }
+
+    /// Attempts to wait for a value on this channel, returning an error if
+    /// the corresponding channel has hung up, or if `deadline` is reached.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        self.inner.recv(Some(deadline)).map_err(|failure| match failure {
+            Failure::Timeout => RecvTimeoutError::Timeout,
+            Failure::Disconnected => RecvTimeoutError::Disconnected,
+        })
+    }
+
+    /// Returns an iterator that will block waiting for messages, but never
+    /// [`panic!`]. It will return [`None`] when the channel has hung up.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { rx: self }
+    }
+
+    /// Returns an iterator that will attempt to yield all pending values.
+    /// It will return `None` if there are no more pending values or if the
+    /// channel has hung up. The iterator will never [`panic!`] or block the
+    /// user by waiting for values.
+    #[unstable(feature = "mpmc_channel", issue = "none")]
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        TryIter { rx: self }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        self.inner.lock().receivers += 1;
+        Receiver { inner: self.inner.clone() }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<'a, T> Iterator for TryIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.rx.try_recv().ok()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<'a, T> IntoIterator for &'a Receiver<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.rx.recv().ok()
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> IntoIterator for Receiver<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { rx: self }
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.inner.drop_port();
+    }
+}
+
+#[unstable(feature = "mpmc_channel", issue = "none")]
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish()
+    }
+}
@@ -0,0 +1,243 @@
+use super::*;
+use crate::env;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::thread;
+use crate::time::{Duration, Instant};
+
+pub fn stress_factor() -> usize {
+    match env::var("RUST_TEST_STRESS") {
+        Ok(val) => val.parse().unwrap(),
+        Err(..) => 1,
+    }
+}
+
+#[test]
+fn smoke() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv().unwrap(), 1);
+}
+
+#[test]
+fn smoke_cloned_receivers() {
+    let (tx, rx) = channel::<i32>();
+    let rx2 = rx.clone();
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(rx2.recv().unwrap(), 1);
+    assert_eq!(rx.recv().unwrap(), 2);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(rx2.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn drop_full() {
+    let (tx, _rx) = channel::<Box<isize>>();
+    tx.send(Box::new(1)).unwrap();
+}
+
+#[test]
+fn port_gone() {
+    let (tx, rx) = channel::<i32>();
+    let rx2 = rx.clone();
+    drop(rx);
+    tx.send(1).unwrap();
+    drop(rx2);
+    assert_eq!(tx.send(2), Err(SendError(2)));
+}
+
+#[test]
+fn chan_gone() {
+    let (tx, rx) = channel::<i32>();
+    let tx2 = tx.clone();
+    drop(tx);
+    tx2.send(1).unwrap();
+    drop(tx2);
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.recv(), Err(RecvError));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    assert_eq!(rx.recv_timeout(Duration::from_millis(1)), Err(RecvTimeoutError::Disconnected));
+}
+
+#[test]
+fn chan_gone_wakes_all_receivers() {
+    let (tx, rx) = channel::<i32>();
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || rx.recv())
+        })
+        .collect();
+    for _ in 0..100 {
+        thread::yield_now();
+    }
+    drop(tx);
+    for t in threads {
+        assert_eq!(t.join().unwrap(), Err(RecvError));
+    }
+}
+
+#[test]
+fn port_gone_wakes_senders() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    let t = thread::spawn(move || tx.send(1));
+    for _ in 0..100 {
+        thread::yield_now();
+    }
+    drop(rx);
+    assert_eq!(t.join().unwrap(), Err(SendError(1)));
+}
+
+#[test]
+fn recv_timeout() {
+    let (tx, rx) = channel::<i32>();
+    let start = Instant::now();
+    assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+    assert!(start.elapsed() >= Duration::from_millis(10));
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Ok(1));
+}
+
+#[test]
+fn timed_out_receiver_does_not_lose_messages() {
+    let (tx, rx) = channel::<i32>();
+    let rx2 = rx.clone();
+    let t = thread::spawn(move || rx2.recv());
+    // This receiver times out while the other one keeps waiting; the message
+    // sent afterwards must wake up the remaining receiver.
+    assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+    tx.send(1).unwrap();
+    assert_eq!(t.join().unwrap(), Ok(1));
+}
+
+#[test]
+fn bounded() {
+    let (tx, rx) = sync_channel::<i32>(2);
+    tx.send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(tx.try_send(3), Err(TrySendError::Full(3)));
+    assert_eq!(rx.recv().unwrap(), 1);
+    tx.try_send(3).unwrap();
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), [2, 3]);
+    drop(rx);
+    assert_eq!(tx.try_send(4), Err(TrySendError::Disconnected(4)));
+}
+
+#[test]
+fn bounded_send_blocks() {
+    let (tx, rx) = sync_channel::<i32>(1);
+    tx.send(1).unwrap();
+    let t = thread::spawn(move || {
+        tx.send(2).unwrap();
+    });
+    for _ in 0..100 {
+        thread::yield_now();
+    }
+    assert_eq!(rx.recv().unwrap(), 1);
+    t.join().unwrap();
+    assert_eq!(rx.recv().unwrap(), 2);
+}
+
+#[test]
+fn rendezvous() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    assert_eq!(tx.try_send(1), Err(TrySendError::Full(1)));
+    let rx2 = rx.clone();
+    let t1 = thread::spawn(move || rx.recv().unwrap());
+    let t2 = thread::spawn(move || rx2.recv().unwrap());
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(t1.join().unwrap() + t2.join().unwrap(), 3);
+}
+
+#[test]
+fn iter_and_into_iter() {
+    let (tx, rx) = channel::<i32>();
+    for i in 0..3 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+    let rx2 = rx.clone();
+    assert_eq!(rx.iter().next(), Some(0));
+    assert_eq!(rx2.into_iter().collect::<Vec<_>>(), [1, 2]);
+}
+
+#[test]
+fn stress_workers() {
+    const AMT: usize = 10000;
+    const NSENDERS: usize = 4;
+    const NRECEIVERS: usize = 4;
+    for cap in [None, Some(0), Some(1), Some(16)].iter().copied() {
+        for _ in 0..stress_factor() {
+            let (tx, rx) = match cap {
+                None => channel::<usize>(),
+                Some(bound) => sync_channel::<usize>(bound),
+            };
+            let sum = Arc::new(AtomicUsize::new(0));
+            let count = Arc::new(AtomicUsize::new(0));
+
+            let receivers: Vec<_> = (0..NRECEIVERS)
+                .map(|_| {
+                    let rx = rx.clone();
+                    let sum = sum.clone();
+                    let count = count.clone();
+                    thread::spawn(move || {
+                        for n in rx {
+                            sum.fetch_add(n, Ordering::Relaxed);
+                            count.fetch_add(1, Ordering::Relaxed);
+                        }
+                    })
+                })
+                .collect();
+            drop(rx);
+
+            let senders: Vec<_> = (0..NSENDERS)
+                .map(|_| {
+                    let tx = tx.clone();
+                    thread::spawn(move || {
+                        for i in 0..AMT {
+                            tx.send(i).unwrap();
+                        }
+                    })
+                })
+                .collect();
+            drop(tx);
+
+            for t in senders.into_iter().chain(receivers) {
+                t.join().unwrap();
+            }
+            assert_eq!(count.load(Ordering::Relaxed), NSENDERS * AMT);
+            assert_eq!(sum.load(Ordering::Relaxed), NSENDERS * AMT * (AMT - 1) / 2);
+        }
+    }
+}
+
+#[test]
+fn stress_recv_timeout() {
+    let (tx, rx) = channel::<usize>();
+    let receivers: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || {
+                let mut received = 0;
+                loop {
+                    match rx.recv_timeout(Duration::from_micros(50)) {
+                        Ok(_) => received += 1,
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => return received,
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(rx);
+    for i in 0..1000 * stress_factor() {
+        tx.send(i).unwrap();
+        if i % 100 == 0 {
+            thread::sleep(Duration::from_micros(100));
+        }
+    }
+    drop(tx);
+    let total: usize = receivers.into_iter().map(|t| t.join().unwrap()).sum();
+    assert_eq!(total, 1000 * stress_factor());
+}
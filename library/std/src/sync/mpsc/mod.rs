@@ -273,9 +273,10 @@
 use crate::sync::Arc;
 use crate::time::{Duration, Instant};
 
-mod blocking;
+pub(super) mod blocking;
 mod mpsc_queue;
 mod oneshot;
+mod select;
 mod shared;
 mod spsc_queue;
 mod stream;
@@ -283,6 +284,9 @@
 
 mod cache_aligned;
 
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub use self::select::Select;
+
 /// The receiving half of Rust's [`channel`] (or [`sync_channel`]) type.
 /// This half can only be owned by one thread.
 ///
@@ -0,0 +1,300 @@
+//! Selection over a set of receivers.
+//!
+//! Selection is built on the same blocking protocol that a single receiver
+//! uses in `recv`: the selecting thread installs one signal token on every
+//! port, blocks until any of them signals it, and then takes the token back
+//! out of each port again. Each flavor provides three hooks for this:
+//!
+//! * `can_recv` checks whether a port is ready without blocking,
+//! * `start_selection` installs the token, failing if the port is ready, and
+//! * `abort_selection` removes the token, reporting whether the port is ready.
+//!
+//! Oneshot and stream ports may be upgraded while selecting, in which case the
+//! receiver swaps in the upgraded port and the hook is retried on that one.
+
+use crate::fmt;
+use crate::mem;
+use crate::ptr;
+use crate::time::{Duration, Instant};
+
+use super::blocking::{self, SignalToken};
+use super::{oneshot, stream, Flavor, Receiver, UnsafeFlavor};
+
+#[derive(PartialEq, Eq)]
+pub enum StartResult {
+    Installed,
+    Abort,
+}
+
+/// A port that can take part in a selection.
+trait Packet {
+    fn can_recv(&self) -> bool;
+    fn start_selection(&self, token: SignalToken) -> StartResult;
+    fn abort_selection(&self) -> bool;
+}
+
+/// A set of [`Receiver`]s to wait on at the same time.
+///
+/// Receivers are added with [`add`], which returns the index that the
+/// waiting methods use to report that receiver as ready. A receiver is ready
+/// when a message has been sent to it or when its channel has disconnected,
+/// so that [`Receiver::recv`] on it returns without waiting for a sender.
+/// Selecting does not receive the message itself.
+///
+/// If several receivers are ready, it is unspecified which one is reported.
+///
+/// [`add`]: Select::add
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, Select};
+/// use std::thread;
+///
+/// let (tx1, rx1) = channel::<i32>();
+/// let (tx2, rx2) = channel::<&str>();
+///
+/// thread::spawn(move || {
+///     tx2.send("hello").unwrap();
+///     drop(tx1);
+/// });
+///
+/// let mut sel = Select::new();
+/// let numbers = sel.add(&rx1);
+/// let words = sel.add(&rx2);
+///
+/// let index = sel.ready();
+/// if index == numbers {
+///     println!("numbers: {:?}", rx1.recv());
+/// } else if index == words {
+///     println!("words: {:?}", rx2.recv());
+/// }
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+pub struct Select<'a> {
+    receivers: Vec<&'a dyn Packet>,
+}
+
+impl<'a> Select<'a> {
+    /// Creates a new selection without any receivers.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn new() -> Select<'a> {
+        Select { receivers: Vec::new() }
+    }
+
+    /// Adds a receiver to the selection and returns its index.
+    ///
+    /// Indices are handed out in order, starting at zero. Adding a receiver
+    /// that is already part of the selection returns its existing index.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn add<T>(&mut self, rx: &'a Receiver<T>) -> usize {
+        let addr = rx as *const Receiver<T> as *const u8;
+        let existing = self
+            .receivers
+            .iter()
+            .position(|&packet| ptr::eq(packet as *const dyn Packet as *const u8, addr));
+        existing.unwrap_or_else(|| {
+            self.receivers.push(rx);
+            self.receivers.len() - 1
+        })
+    }
+
+    /// Returns the index of a ready receiver, if there is one, without
+    /// blocking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx1, rx1) = channel::<i32>();
+    /// let (_tx2, rx2) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.add(&rx1);
+    /// sel.add(&rx2);
+    /// assert_eq!(sel.try_ready(), None);
+    ///
+    /// tx1.send(1).unwrap();
+    /// assert_eq!(sel.try_ready(), Some(0));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn try_ready(&mut self) -> Option<usize> {
+        self.receivers.iter().position(|packet| packet.can_recv())
+    }
+
+    /// Blocks until one of the receivers is ready and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no receivers have been added, as this would block forever.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn ready(&mut self) -> usize {
+        assert!(!self.receivers.is_empty(), "no receivers were added to the selection");
+        loop {
+            if let Some(index) = self.wait(None) {
+                return index;
+            }
+        }
+    }
+
+    /// Blocks until one of the receivers is ready, or until `timeout` has
+    /// elapsed.
+    ///
+    /// Returns the index of the ready receiver, or [`None`] if the timeout
+    /// elapsed first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::time::Duration;
+    ///
+    /// let (_tx, rx) = channel::<i32>();
+    ///
+    /// let mut sel = Select::new();
+    /// sel.add(&rx);
+    /// assert_eq!(sel.ready_timeout(Duration::from_millis(10)), None);
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn ready_timeout(&mut self, timeout: Duration) -> Option<usize> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.ready_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => Some(self.ready()),
+        }
+    }
+
+    /// Blocks until one of the receivers is ready, or until `deadline` is
+    /// reached.
+    ///
+    /// Returns the index of the ready receiver, or [`None`] if the deadline
+    /// was reached first.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn ready_deadline(&mut self, deadline: Instant) -> Option<usize> {
+        self.wait(Some(deadline))
+    }
+
+    fn wait(&self, deadline: Option<Instant>) -> Option<usize> {
+        // Stage 1: preflight checks. Look for any receivers that are ready
+        // already, as installing the token everywhere is comparatively costly.
+        if let Some(index) = self.receivers.iter().position(|packet| packet.can_recv()) {
+            return Some(index);
+        }
+
+        // Stage 2: install the same signal token on every receiver. If one of
+        // them turns out to be ready after all, take the token back out of the
+        // receivers it was already installed on.
+        let (wait_token, signal_token) = blocking::tokens();
+        for (i, packet) in self.receivers.iter().enumerate() {
+            if packet.start_selection(signal_token.clone()) == StartResult::Abort {
+                for packet in &self.receivers[..i] {
+                    packet.abort_selection();
+                }
+                return Some(i);
+            }
+        }
+
+        // Stage 3: nothing is ready, actually block.
+        match deadline {
+            Some(deadline) => {
+                wait_token.wait_max_until(deadline);
+            }
+            None => wait_token.wait(),
+        }
+
+        // Stage 4: remove the token from every receiver, which also tells us
+        // which of them are ready. This has to happen for all of them, even
+        // after finding a ready one, so that no sender can signal this thread
+        // once the selection is over.
+        let mut ready = None;
+        for (i, packet) in self.receivers.iter().enumerate() {
+            if packet.abort_selection() && ready.is_none() {
+                ready = Some(i);
+            }
+        }
+        ready
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl Default for Select<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Select { .. }")
+    }
+}
+
+impl<T> Packet for Receiver<T> {
+    fn can_recv(&self) -> bool {
+        loop {
+            let new_port = match *unsafe { self.inner() } {
+                Flavor::Oneshot(ref p) => match p.can_recv() {
+                    Ok(ret) => return ret,
+                    Err(upgrade) => upgrade,
+                },
+                Flavor::Stream(ref p) => match p.can_recv() {
+                    Ok(ret) => return ret,
+                    Err(upgrade) => upgrade,
+                },
+                Flavor::Shared(ref p) => return p.can_recv(),
+                Flavor::Sync(ref p) => return p.can_recv(),
+            };
+            unsafe {
+                mem::swap(self.inner_mut(), new_port.inner_mut());
+            }
+        }
+    }
+
+    fn start_selection(&self, mut token: SignalToken) -> StartResult {
+        loop {
+            let (t, new_port) = match *unsafe { self.inner() } {
+                Flavor::Oneshot(ref p) => match p.start_selection(token) {
+                    oneshot::SelSuccess => return StartResult::Installed,
+                    oneshot::SelCanceled => return StartResult::Abort,
+                    oneshot::SelUpgraded(t, rx) => (t, rx),
+                },
+                Flavor::Stream(ref p) => match p.start_selection(token) {
+                    stream::SelSuccess => return StartResult::Installed,
+                    stream::SelCanceled => return StartResult::Abort,
+                    stream::SelUpgraded(t, rx) => (t, rx),
+                },
+                Flavor::Shared(ref p) => return p.start_selection(token),
+                Flavor::Sync(ref p) => return p.start_selection(token),
+            };
+            token = t;
+            unsafe {
+                mem::swap(self.inner_mut(), new_port.inner_mut());
+            }
+        }
+    }
+
+    fn abort_selection(&self) -> bool {
+        let mut was_upgrade = false;
+        loop {
+            let result = match *unsafe { self.inner() } {
+                Flavor::Oneshot(ref p) => p.abort_selection(),
+                Flavor::Stream(ref p) => p.abort_selection(was_upgrade),
+                Flavor::Shared(ref p) => return p.abort_selection(was_upgrade),
+                Flavor::Sync(ref p) => return p.abort_selection(),
+            };
+            let new_port = match result {
+                Ok(b) => return b,
+                Err(p) => p,
+            };
+            was_upgrade = true;
+            unsafe {
+                mem::swap(self.inner_mut(), new_port.inner_mut());
+            }
+        }
+    }
+}
@@ -645,3 +645,21 @@ fn repro() {
         repro()
     }
 }
+
+#[test]
+fn select_rendezvous() {
+    let (tx1, rx1) = sync_channel::<i32>(0);
+    let (_tx2, rx2) = sync_channel::<i32>(1);
+    let t = thread::spawn(move || {
+        tx1.send(1).unwrap();
+    });
+    let mut sel = Select::new();
+    sel.add(&rx1);
+    sel.add(&rx2);
+    assert_eq!(sel.ready(), 0);
+    assert_eq!(rx1.recv().unwrap(), 1);
+    t.join().unwrap();
+    assert_eq!(sel.ready(), 0);
+    assert!(rx1.recv().is_err());
+    assert_eq!(sel.try_ready(), Some(0));
+}
@@ -704,3 +704,143 @@ fn issue_32114() {
     let _ = tx.send(123);
     assert_eq!(tx.send(123), Err(SendError(123)));
 }
+
+#[test]
+fn select_smoke() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    let mut sel = Select::new();
+    assert_eq!(sel.add(&rx1), 0);
+    assert_eq!(sel.add(&rx2), 1);
+    assert_eq!(sel.add(&rx1), 0);
+    assert_eq!(sel.try_ready(), None);
+
+    tx2.send(2).unwrap();
+    assert_eq!(sel.ready(), 1);
+    assert_eq!(rx2.recv().unwrap(), 2);
+
+    tx1.send(1).unwrap();
+    assert_eq!(sel.ready(), 0);
+    assert_eq!(rx1.recv().unwrap(), 1);
+    assert_eq!(sel.try_ready(), None);
+}
+
+#[test]
+fn select_disconnected() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    drop(tx1);
+    let mut sel = Select::new();
+    sel.add(&rx1);
+    sel.add(&rx2);
+    assert_eq!(sel.ready(), 0);
+    assert_eq!(rx1.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    let mut sel = Select::new();
+    sel.add(&rx1);
+    sel.add(&rx2);
+    assert_eq!(sel.ready_timeout(Duration::from_millis(1)), None);
+
+    tx2.send(2).unwrap();
+    assert_eq!(sel.ready_timeout(Duration::from_millis(1)), Some(1));
+    assert_eq!(rx2.recv().unwrap(), 2);
+    assert_eq!(sel.ready_deadline(Instant::now()), None);
+}
+
+#[test]
+fn select_blocks_until_ready() {
+    let (_tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    let _t = thread::spawn(move || {
+        for _ in 0..100 {
+            thread::yield_now();
+        }
+        tx2.send(2).unwrap();
+    });
+    let mut sel = Select::new();
+    sel.add(&rx1);
+    sel.add(&rx2);
+    assert_eq!(sel.ready(), 1);
+    assert_eq!(rx2.recv().unwrap(), 2);
+}
+
+#[test]
+fn select_upgrades() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    let t = thread::spawn(move || {
+        for _ in 0..100 {
+            thread::yield_now();
+        }
+        // oneshot, then upgraded to a stream and to a shared channel
+        tx1.send(1).unwrap();
+        tx1.send(2).unwrap();
+        let tx3 = tx1.clone();
+        tx3.send(3).unwrap();
+    });
+    let mut sel = Select::new();
+    sel.add(&rx1);
+    sel.add(&rx2);
+    for i in 1..4 {
+        assert_eq!(sel.ready(), 0);
+        assert_eq!(rx1.recv().unwrap(), i);
+    }
+    assert_eq!(sel.ready(), 0);
+    assert!(rx1.recv().is_err());
+    t.join().unwrap();
+}
+
+#[test]
+fn select_stress() {
+    const AMT: usize = 1000;
+    for _ in 0..stress_factor() {
+        let (tx1, rx1) = channel::<usize>();
+        let (tx2, rx2) = channel::<usize>();
+        let t1 = thread::spawn(move || {
+            for i in 0..AMT {
+                tx1.send(i).unwrap();
+            }
+        });
+        let t2 = thread::spawn(move || {
+            for i in 0..AMT {
+                let tx = tx2.clone();
+                tx.send(i).unwrap();
+            }
+        });
+
+        let rxs = [&rx1, &rx2];
+        let mut sel = Select::new();
+        sel.add(&rx1);
+        sel.add(&rx2);
+        // Maps the indices returned by `sel` back to `rxs`.
+        let mut live = vec![0, 1];
+        let mut received = [0, 0];
+        while !live.is_empty() {
+            let index = live[sel.ready()];
+            match rxs[index].recv() {
+                Ok(n) => {
+                    assert_eq!(n, received[index]);
+                    received[index] += 1;
+                }
+                Err(RecvError) => {
+                    assert_eq!(received[index], AMT);
+                    // Disconnected receivers stay ready, so wait on the
+                    // remaining ones only.
+                    live.retain(|&i| i != index);
+                    sel = Select::new();
+                    for &i in &live {
+                        sel.add(rxs[i]);
+                    }
+                }
+            }
+        }
+        assert_eq!(received, [AMT, AMT]);
+        t1.join().unwrap();
+        t2.join().unwrap();
+    }
+}
@@ -236,3 +236,110 @@ fn test_mutex_unsized() {
     let comp: &[i32] = &[4, 2, 5];
     assert_eq!(&*mutex.lock().unwrap(), comp);
 }
+
+#[bench]
+fn bench_lock_unlock(b: &mut test::Bencher) {
+    let m = Mutex::new(0usize);
+    b.iter(|| *m.lock().unwrap() += 1);
+}
+
+#[bench]
+fn bench_contended(b: &mut test::Bencher) {
+    const THREADS: usize = 4;
+    const ITERS: usize = 10_000;
+
+    let m = Arc::new(Mutex::new(0usize));
+    b.iter(|| {
+        let threads: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let m = m.clone();
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        *m.lock().unwrap() += 1;
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+    });
+}
+
+// The same workload as `bench_contended` on the bare futex mutex and on the pthread
+// mutex it replaced, to keep the two comparable.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys_contended {
+    use crate::cell::UnsafeCell;
+    use crate::sync::Arc;
+    use crate::sys::mutex::Mutex as FutexMutex;
+    use crate::sys::pthread_mutex::Mutex as PthreadMutex;
+    use crate::thread;
+
+    struct Counter<M> {
+        lock: M,
+        count: UnsafeCell<usize>,
+    }
+
+    unsafe impl<M: Sync> Sync for Counter<M> {}
+
+    fn bench<M: Send + Sync + 'static>(
+        b: &mut test::Bencher,
+        lock: M,
+        init: unsafe fn(&mut M),
+        acquire: unsafe fn(&M),
+        release: unsafe fn(&M),
+        destroy: unsafe fn(&M),
+    ) {
+        const THREADS: usize = 4;
+        const ITERS: usize = 10_000;
+
+        // Initialize the lock at its final address: a pthread mutex must not move once initialized.
+        let mut c = Arc::new(Counter { lock, count: UnsafeCell::new(0) });
+        unsafe { init(&mut Arc::get_mut(&mut c).unwrap().lock) };
+        b.iter(|| {
+            let threads: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let c = c.clone();
+                    thread::spawn(move || {
+                        for _ in 0..ITERS {
+                            unsafe {
+                                acquire(&c.lock);
+                                *c.count.get() += 1;
+                                release(&c.lock);
+                            }
+                        }
+                    })
+                })
+                .collect();
+            for t in threads {
+                t.join().unwrap();
+            }
+        });
+        unsafe { destroy(&c.lock) };
+    }
+
+    #[bench]
+    fn bench_contended_futex(b: &mut test::Bencher) {
+        bench(
+            b,
+            FutexMutex::new(),
+            FutexMutex::init,
+            FutexMutex::lock,
+            FutexMutex::unlock,
+            FutexMutex::destroy,
+        );
+    }
+
+    #[bench]
+    fn bench_contended_pthread(b: &mut test::Bencher) {
+        bench(
+            b,
+            PthreadMutex::new(),
+            PthreadMutex::init,
+            PthreadMutex::lock,
+            PthreadMutex::unlock,
+            PthreadMutex::destroy,
+        );
+    }
+}
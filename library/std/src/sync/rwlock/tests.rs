@@ -245,3 +245,136 @@ fn test_get_mut_poison() {
         Ok(x) => panic!("get_mut of poisoned RwLock is Ok: {:?}", x),
     }
 }
+
+#[bench]
+fn bench_read_unlock(b: &mut test::Bencher) {
+    let l = RwLock::new(0usize);
+    b.iter(|| *l.read().unwrap());
+}
+
+#[bench]
+fn bench_contended(b: &mut test::Bencher) {
+    const READERS: usize = 3;
+    const WRITERS: usize = 1;
+    const ITERS: usize = 10_000;
+
+    let l = Arc::new(RwLock::new(0usize));
+    b.iter(|| {
+        let readers = (0..READERS).map(|_| {
+            let l = l.clone();
+            thread::spawn(move || {
+                for _ in 0..ITERS {
+                    test::black_box(*l.read().unwrap());
+                }
+            })
+        });
+        let writers = (0..WRITERS).map(|_| {
+            let l = l.clone();
+            thread::spawn(move || {
+                for _ in 0..ITERS {
+                    *l.write().unwrap() += 1;
+                }
+            })
+        });
+        let threads: Vec<_> = readers.chain(writers).collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+    });
+}
+
+// The same workload as `bench_contended` on the bare futex rwlock and on the pthread
+// rwlock it replaced, to keep the two comparable.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys_contended {
+    use crate::cell::UnsafeCell;
+    use crate::sync::Arc;
+    use crate::sys::pthread_rwlock::RWLock as PthreadRWLock;
+    use crate::sys::rwlock::RWLock as FutexRWLock;
+    use crate::thread;
+
+    struct Counter<L> {
+        lock: L,
+        count: UnsafeCell<usize>,
+    }
+
+    unsafe impl<L: Sync> Sync for Counter<L> {}
+
+    struct Ops<L> {
+        read: unsafe fn(&L),
+        read_unlock: unsafe fn(&L),
+        write: unsafe fn(&L),
+        write_unlock: unsafe fn(&L),
+        destroy: unsafe fn(&L),
+    }
+
+    fn bench<L: Send + Sync + 'static>(b: &mut test::Bencher, lock: L, ops: Ops<L>) {
+        const READERS: usize = 3;
+        const WRITERS: usize = 1;
+        const ITERS: usize = 10_000;
+
+        let Ops { read, read_unlock, write, write_unlock, destroy } = ops;
+        let c = Arc::new(Counter { lock, count: UnsafeCell::new(0) });
+        b.iter(|| {
+            let readers = (0..READERS).map(|_| {
+                let c = c.clone();
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        unsafe {
+                            read(&c.lock);
+                            test::black_box(*c.count.get());
+                            read_unlock(&c.lock);
+                        }
+                    }
+                })
+            });
+            let writers = (0..WRITERS).map(|_| {
+                let c = c.clone();
+                thread::spawn(move || {
+                    for _ in 0..ITERS {
+                        unsafe {
+                            write(&c.lock);
+                            *c.count.get() += 1;
+                            write_unlock(&c.lock);
+                        }
+                    }
+                })
+            });
+            let threads: Vec<_> = readers.chain(writers).collect();
+            for t in threads {
+                t.join().unwrap();
+            }
+        });
+        unsafe { destroy(&c.lock) };
+    }
+
+    #[bench]
+    fn bench_contended_futex(b: &mut test::Bencher) {
+        bench(
+            b,
+            FutexRWLock::new(),
+            Ops {
+                read: FutexRWLock::read,
+                read_unlock: FutexRWLock::read_unlock,
+                write: FutexRWLock::write,
+                write_unlock: FutexRWLock::write_unlock,
+                destroy: FutexRWLock::destroy,
+            },
+        );
+    }
+
+    #[bench]
+    fn bench_contended_pthread(b: &mut test::Bencher) {
+        bench(
+            b,
+            PthreadRWLock::new(),
+            Ops {
+                read: PthreadRWLock::read,
+                read_unlock: PthreadRWLock::read_unlock,
+                write: PthreadRWLock::write,
+                write_unlock: PthreadRWLock::write_unlock,
+                destroy: PthreadRWLock::destroy,
+            },
+        );
+    }
+}
@@ -2,7 +2,7 @@
 use crate::fmt;
 use crate::hash::{Hash, Hasher};
 use crate::io::{self, Error, ErrorKind};
-use crate::io::{IoSlice, IoSliceMut, SeekFrom};
+use crate::io::{IoSlice, IoSliceMut, ReadBuf, SeekFrom};
 use crate::path::{Path, PathBuf};
 use crate::sys::cvt;
 use crate::sys::hermit::abi;
@@ -28,6 +28,8 @@ fn cstr(path: &Path) -> io::Result<CString> {
 
 pub struct DirEntry(Void);
 
+pub struct Dir(Void);
+
 #[derive(Clone, Debug)]
 pub struct OpenOptions {
     // generic
@@ -301,6 +303,10 @@ pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         crate::io::default_read_vectored(|buf| self.read(buf), bufs)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        crate::io::default_read_buf(|buf| self.read(buf), buf)
+    }
+
     #[inline]
     pub fn is_read_vectored(&self) -> bool {
         false
@@ -346,6 +352,42 @@ pub fn mkdir(&self, _p: &Path) -> io::Result<()> {
     }
 }
 
+impl Dir {
+    pub fn open(_p: &Path) -> io::Result<Dir> {
+        unsupported()
+    }
+
+    pub fn open_file(&self, _p: &Path, _opts: &OpenOptions) -> io::Result<File> {
+        match self.0 {}
+    }
+
+    pub fn create_dir(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn remove_file(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &Dir, _to: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn metadata(&self, _p: &Path) -> io::Result<FileAttr> {
+        match self.0 {}
+    }
+
+    pub fn read_dir(&self, _p: &Path) -> io::Result<ReadDir> {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
 pub fn readdir(_p: &Path) -> io::Result<ReadDir> {
     unsupported()
 }
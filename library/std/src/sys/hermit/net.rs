@@ -1,7 +1,7 @@
 use crate::convert::TryFrom;
 use crate::fmt;
-use crate::io::{self, ErrorKind, IoSlice, IoSliceMut};
-use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
+use crate::io::{self, ErrorKind, IoSlice, IoSliceMut, ReadBuf};
+use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketOptions};
 use crate::str;
 use crate::sync::Arc;
 use crate::sys::hermit::abi;
@@ -53,6 +53,10 @@ pub fn connect(addr: io::Result<&SocketAddr>) -> io::Result<TcpStream> {
         }
     }
 
+    pub fn connect_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<TcpStream> {
+        unsupported()
+    }
+
     pub fn connect_timeout(saddr: &SocketAddr, duration: Duration) -> io::Result<TcpStream> {
         match abi::tcpstream::connect(
             saddr.ip().to_string().as_bytes(),
@@ -117,6 +121,10 @@ pub fn read_vectored(&self, ioslice: &mut [IoSliceMut<'_>]) -> io::Result<usize>
         Ok(size)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        io::default_read_buf(|buf| self.read(buf), buf)
+    }
+
     #[inline]
     pub fn is_read_vectored(&self) -> bool {
         true
@@ -216,6 +224,10 @@ pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
         Ok(TcpListener(*addr))
     }
 
+    pub fn bind_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<TcpListener> {
+        unsupported()
+    }
+
     pub fn socket_addr(&self) -> io::Result<SocketAddr> {
         Ok(self.0)
     }
@@ -276,6 +288,10 @@ pub fn bind(_: io::Result<&SocketAddr>) -> io::Result<UdpSocket> {
         Err(io::Error::new(ErrorKind::Other, "not supported"))
     }
 
+    pub fn bind_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<UdpSocket> {
+        unsupported()
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         Err(io::Error::new(ErrorKind::Other, "not supported"))
     }
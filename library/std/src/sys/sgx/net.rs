@@ -1,8 +1,8 @@
 use crate::convert::TryFrom;
 use crate::error;
 use crate::fmt;
-use crate::io::{self, IoSlice, IoSliceMut};
-use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, ToSocketAddrs};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
+use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketOptions, ToSocketAddrs};
 use crate::sync::Arc;
 use crate::sys::fd::FileDesc;
 use crate::sys::{sgx_ineffective, unsupported, AsInner, FromInner, IntoInner, TryIntoInner, Void};
@@ -95,6 +95,10 @@ pub fn connect(addr: io::Result<&SocketAddr>) -> io::Result<TcpStream> {
         Ok(TcpStream { inner: Socket::new(fd, local_addr), peer_addr: Some(peer_addr) })
     }
 
+    pub fn connect_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<TcpStream> {
+        unsupported()
+    }
+
     pub fn connect_timeout(addr: &SocketAddr, dur: Duration) -> io::Result<TcpStream> {
         if dur == Duration::default() {
             return Err(io::Error::new(
@@ -149,6 +153,10 @@ pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.inner.read_vectored(bufs)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        io::default_read_buf(|buf| self.read(buf), buf)
+    }
+
     #[inline]
     pub fn is_read_vectored(&self) -> bool {
         self.inner.inner.is_read_vectored()
@@ -252,6 +260,10 @@ pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
         Ok(TcpListener { inner: Socket::new(fd, local_addr) })
     }
 
+    pub fn bind_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<TcpListener> {
+        unsupported()
+    }
+
     pub fn socket_addr(&self) -> io::Result<SocketAddr> {
         addr_to_sockaddr(&self.inner.local_addr)
     }
@@ -317,6 +329,10 @@ pub fn bind(_: io::Result<&SocketAddr>) -> io::Result<UdpSocket> {
         unsupported()
     }
 
+    pub fn bind_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<UdpSocket> {
+        unsupported()
+    }
+
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         match self.0 {}
     }
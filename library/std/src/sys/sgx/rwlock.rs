@@ -8,6 +8,8 @@
 };
 use crate::mem;
 
+pub type MovableRWLock = Box<RWLock>;
+
 pub struct RWLock {
     readers: SpinMutex<WaitVariable<Option<NonZeroUsize>>>,
     writer: SpinMutex<WaitVariable<bool>>,
@@ -136,6 +136,73 @@ fn before_exec<F>(&mut self, f: F) -> &mut process::Command
     fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     where
         S: AsRef<OsStr>;
+
+    /// Sets the process group ID (PGID) of the child process. Equivalent to a
+    /// `setpgid(0, pgroup)` call in the child process, but it may be more
+    /// efficient, as it doesn't prevent spawning with `posix_spawn`.
+    ///
+    /// A `pgroup` of 0 puts the child into a new process group whose ID is the
+    /// child's process ID. The child has joined its process group by the time
+    /// `spawn` returns, so the group can be signaled right away.
+    ///
+    /// Failure to join the process group will cause the spawn to fail.
+    ///
+    /// # Examples
+    ///
+    /// Pressing Ctrl-C in a terminal sends `SIGINT` to the foreground process
+    /// group, which doesn't include a child in a process group of its own:
+    ///
+    /// ```no_run
+    /// #![feature(process_session)]
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// Command::new("sleep")
+    ///     .arg("10")
+    ///     .process_group(0)
+    ///     .spawn()
+    ///     .unwrap();
+    /// ```
+    #[unstable(feature = "process_session", issue = "none")]
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process starts a new session, like a
+    /// `setsid()` call in the child process.
+    ///
+    /// The child becomes the leader of a new session and of a new process
+    /// group within it, without a controlling terminal. This can't be
+    /// combined with [`process_group`], as a session leader can't change its
+    /// process group, so spawning fails if both are set.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    #[unstable(feature = "process_session", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Sets whether the terminal on the child's standard input becomes the
+    /// controlling terminal of the child's session, using the `TIOCSCTTY`
+    /// ioctl in the child process.
+    ///
+    /// Only a session leader can acquire a controlling terminal, so this
+    /// requires [`setsid`] as well, and the terminal must not be the
+    /// controlling terminal of another session. Failure to acquire the
+    /// terminal will cause the spawn to fail.
+    ///
+    /// [`setsid`]: CommandExt::setsid
+    #[unstable(feature = "process_session", issue = "none")]
+    fn controlling_tty(&mut self, controlling_tty: bool) -> &mut process::Command;
+
+    /// Sets whether the child's process group becomes the foreground process
+    /// group of the terminal on its standard input, like a
+    /// `tcsetpgrp(0, getpgrp())` call in the child process.
+    ///
+    /// This is how a shell hands the terminal over to a job, usually combined
+    /// with [`process_group`]. The terminal must be the controlling terminal
+    /// of the child's session. Failure to change the foreground process group
+    /// will cause the spawn to fail.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    #[unstable(feature = "process_session", issue = "none")]
+    fn foreground(&mut self, foreground: bool) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -186,6 +253,26 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
         self.as_inner_mut().set_arg_0(arg.as_ref());
         self
     }
+
+    fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
+        self.as_inner_mut().pgroup(pgroup);
+        self
+    }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn controlling_tty(&mut self, controlling_tty: bool) -> &mut process::Command {
+        self.as_inner_mut().controlling_tty(controlling_tty);
+        self
+    }
+
+    fn foreground(&mut self, foreground: bool) -> &mut process::Command {
+        self.as_inner_mut().foreground(foreground);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`].
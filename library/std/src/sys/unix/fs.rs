@@ -2,7 +2,7 @@
 
 use crate::ffi::{CStr, CString, OsStr, OsString};
 use crate::fmt;
-use crate::io::{self, Error, ErrorKind, IoSlice, IoSliceMut, SeekFrom};
+use crate::io::{self, Error, ErrorKind, IoSlice, IoSliceMut, ReadBuf, SeekFrom};
 use crate::mem;
 use crate::path::{Path, PathBuf};
 use crate::ptr;
@@ -16,7 +16,7 @@
 
 #[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "android"))]
 use libc::dirfd;
-#[cfg(any(target_os = "linux", target_os = "emscripten"))]
+#[cfg(any(target_os = "linux", target_os = "emscripten", target_os = "l4re"))]
 use libc::fstatat64;
 #[cfg(not(any(
     target_os = "linux",
@@ -48,10 +48,43 @@
     dirent64, fstat64, ftruncate64, lseek64, lstat64, off64_t, open64, readdir64_r, stat64,
 };
 
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "emscripten",
+    target_os = "l4re",
+    target_os = "android",
+    target_os = "redox",
+    target_os = "vxworks",
+    all(target_os = "macos", target_arch = "x86_64")
+)))]
+use libc::fstatat as fstatat64;
+#[cfg(not(any(
+    target_os = "redox",
+    target_os = "vxworks",
+    all(target_os = "macos", target_arch = "x86_64")
+)))]
+use libc::{fdopendir, mkdirat, openat, renameat, unlinkat};
+#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+use macos_weak::{fdopendir, fstatat64, mkdirat, openat, renameat, unlinkat};
+
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
 pub use crate::sys_common::fs::remove_dir_all;
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+pub use remove_dir_impl::remove_dir_all;
 
 pub struct File(FileDesc);
 
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+pub struct Dir(FileDesc);
+
+// The `*at` functions aren't available on REDOX and VxWorks, so a `Dir` can't
+// be opened.
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
+pub struct Dir(Void);
+
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
+enum Void {}
+
 // FIXME: This should be available on Linux with all `target_env`.
 // But currently only glibc exposes `statx` fn and structs.
 // We don't want to import unverified raw C structs here directly.
@@ -179,7 +212,7 @@ pub struct FileAttr {
 
 // all DirEntry's will have a reference to this struct
 struct InnerReadDir {
-    dirp: Dir,
+    dirp: DirStream,
     root: PathBuf,
 }
 
@@ -194,10 +227,10 @@ pub struct ReadDir {
     end_of_stream: bool,
 }
 
-struct Dir(*mut libc::DIR);
+struct DirStream(*mut libc::DIR);
 
-unsafe impl Send for Dir {}
-unsafe impl Sync for Dir {}
+unsafe impl Send for DirStream {}
+unsafe impl Sync for DirStream {}
 
 pub struct DirEntry {
     entry: dirent64,
@@ -421,6 +454,21 @@ fn from_inner(mode: u32) -> FilePermissions {
     }
 }
 
+impl ReadDir {
+    fn new(inner: InnerReadDir) -> ReadDir {
+        ReadDir {
+            inner: Arc::new(inner),
+            #[cfg(not(any(
+                target_os = "solaris",
+                target_os = "illumos",
+                target_os = "fuchsia",
+                target_os = "redox",
+            )))]
+            end_of_stream: false,
+        }
+    }
+}
+
 impl fmt::Debug for ReadDir {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // This will only be called from std::fs::ReadDir, which will add a "ReadDir()" frame.
@@ -511,7 +559,7 @@ fn next(&mut self) -> Option<io::Result<DirEntry>> {
     }
 }
 
-impl Drop for Dir {
+impl Drop for DirStream {
     fn drop(&mut self) {
         let r = unsafe { libc::closedir(self.0) };
         debug_assert_eq!(r, 0);
@@ -548,7 +596,30 @@ pub fn metadata(&self) -> io::Result<FileAttr> {
         Ok(FileAttr::from_stat64(stat))
     }
 
-    #[cfg(not(any(target_os = "linux", target_os = "emscripten", target_os = "android")))]
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "emscripten",
+        target_os = "android",
+        target_os = "redox",
+        target_os = "vxworks"
+    )))]
+    pub fn metadata(&self) -> io::Result<FileAttr> {
+        // Look the entry up relative to the directory it was read from, as
+        // the root of a `ReadDir` opened through a `Dir` is relative to that
+        // handle and not to the current directory.
+        #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+        if !macos_weak::available() {
+            return lstat(&self.path());
+        }
+
+        let fd = cvt(unsafe { libc::dirfd(self.dir.dirp.0) })?;
+        let name = CString::new(self.name_bytes())?;
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe { fstatat64(fd, name.as_ptr(), &mut stat, libc::AT_SYMLINK_NOFOLLOW) })?;
+        Ok(FileAttr::from_stat64(stat))
+    }
+
+    #[cfg(any(target_os = "redox", target_os = "vxworks"))]
     pub fn metadata(&self) -> io::Result<FileAttr> {
         lstat(&self.path())
     }
@@ -560,7 +631,7 @@ pub fn metadata(&self) -> io::Result<FileAttr> {
         target_os = "vxworks"
     ))]
     pub fn file_type(&self) -> io::Result<FileType> {
-        lstat(&self.path()).map(|m| m.file_type())
+        self.metadata().map(|m| m.file_type())
     }
 
     #[cfg(not(any(
@@ -578,7 +649,7 @@ pub fn file_type(&self) -> io::Result<FileType> {
             libc::DT_SOCK => Ok(FileType { mode: libc::S_IFSOCK }),
             libc::DT_DIR => Ok(FileType { mode: libc::S_IFDIR }),
             libc::DT_BLK => Ok(FileType { mode: libc::S_IFBLK }),
-            _ => lstat(&self.path()).map(|m| m.file_type()),
+            _ => self.metadata().map(|m| m.file_type()),
         }
     }
 
@@ -725,6 +796,13 @@ fn get_creation_mode(&self) -> io::Result<c_int> {
             (_, _, true) => libc::O_CREAT | libc::O_EXCL,
         })
     }
+
+    fn get_flags(&self) -> io::Result<c_int> {
+        Ok(libc::O_CLOEXEC
+            | self.get_access_mode()?
+            | self.get_creation_mode()?
+            | (self.custom_flags as c_int & !libc::O_ACCMODE))
+    }
 }
 
 impl File {
@@ -734,10 +812,7 @@ pub fn open(path: &Path, opts: &OpenOptions) -> io::Result<File> {
     }
 
     pub fn open_c(path: &CStr, opts: &OpenOptions) -> io::Result<File> {
-        let flags = libc::O_CLOEXEC
-            | opts.get_access_mode()?
-            | opts.get_creation_mode()?
-            | (opts.custom_flags as c_int & !libc::O_ACCMODE);
+        let flags = opts.get_flags()?;
         // The third argument of `open64` is documented to have type `mode_t`. On
         // some platforms (like macOS, where `open64` is actually `open`), `mode_t` is `u16`.
         // However, since this is a variadic function, C integer promotion rules mean that on
@@ -832,6 +907,10 @@ pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.0.read_vectored(bufs)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.0.read_buf(buf)
+    }
+
     #[inline]
     pub fn is_read_vectored(&self) -> bool {
         self.0.is_read_vectored()
@@ -912,6 +991,200 @@ fn cstr(path: &Path) -> io::Result<CString> {
     Ok(CString::new(path.as_os_str().as_bytes())?)
 }
 
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+impl Dir {
+    pub fn open(p: &Path) -> io::Result<Dir> {
+        #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+        if !macos_weak::available() {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                "directory handles require macOS 10.10 or later",
+            ));
+        }
+
+        Dir::open_at(libc::AT_FDCWD, &cstr(p)?)
+    }
+
+    fn open_at(dirfd: c_int, p: &CStr) -> io::Result<Dir> {
+        let flags = libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY;
+        let fd = cvt_r(|| unsafe { openat(dirfd, p.as_ptr(), flags, 0) })?;
+        Ok(Dir(FileDesc::new(fd)))
+    }
+
+    pub fn open_file(&self, p: &Path, opts: &OpenOptions) -> io::Result<File> {
+        let p = cstr(p)?;
+        let flags = opts.get_flags()?;
+        // See `File::open_c` for why the mode is passed as a `c_int`.
+        let fd = cvt_r(|| unsafe { openat(self.0.raw(), p.as_ptr(), flags, opts.mode as c_int) })?;
+        Ok(File(FileDesc::new(fd)))
+    }
+
+    pub fn create_dir(&self, p: &Path) -> io::Result<()> {
+        let p = cstr(p)?;
+        cvt(unsafe { mkdirat(self.0.raw(), p.as_ptr(), 0o777) })?;
+        Ok(())
+    }
+
+    pub fn remove_file(&self, p: &Path) -> io::Result<()> {
+        let p = cstr(p)?;
+        cvt(unsafe { unlinkat(self.0.raw(), p.as_ptr(), 0) })?;
+        Ok(())
+    }
+
+    pub fn rename(&self, from: &Path, to_dir: &Dir, to: &Path) -> io::Result<()> {
+        let from = cstr(from)?;
+        let to = cstr(to)?;
+        cvt(unsafe { renameat(self.0.raw(), from.as_ptr(), to_dir.0.raw(), to.as_ptr()) })?;
+        Ok(())
+    }
+
+    pub fn metadata(&self, p: &Path) -> io::Result<FileAttr> {
+        let p = cstr(p)?;
+
+        cfg_has_statx! {
+            if let Some(ret) = unsafe { try_statx(
+                self.0.raw(),
+                p.as_ptr(),
+                libc::AT_STATX_SYNC_AS_STAT,
+                libc::STATX_ALL,
+            ) } {
+                return ret;
+            }
+        }
+
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe { fstatat64(self.0.raw(), p.as_ptr(), &mut stat, 0) })?;
+        Ok(FileAttr::from_stat64(stat))
+    }
+
+    pub fn read_dir(&self, p: &Path) -> io::Result<ReadDir> {
+        let root = p.to_path_buf();
+        let dir = Dir::open_at(self.0.raw(), &cstr(p)?)?;
+        let ptr = unsafe { fdopendir(dir.0.raw()) };
+        if ptr.is_null() {
+            return Err(Error::last_os_error());
+        }
+        // The file descriptor is owned by the stream now and closed by `closedir`.
+        dir.0.into_raw();
+        Ok(ReadDir::new(InnerReadDir { dirp: DirStream(ptr), root }))
+    }
+}
+
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
+impl Dir {
+    pub fn open(_p: &Path) -> io::Result<Dir> {
+        Err(io::Error::new(
+            ErrorKind::Other,
+            "directory handles are not supported on this platform",
+        ))
+    }
+
+    pub fn open_file(&self, _p: &Path, _opts: &OpenOptions) -> io::Result<File> {
+        match self.0 {}
+    }
+
+    pub fn create_dir(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn remove_file(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &Dir, _to: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn metadata(&self, _p: &Path) -> io::Result<FileAttr> {
+        match self.0 {}
+    }
+
+    pub fn read_dir(&self, _p: &Path) -> io::Result<ReadDir> {
+        match self.0 {}
+    }
+}
+
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dir").field("fd", &self.0.raw()).finish()
+    }
+}
+
+#[cfg(any(target_os = "redox", target_os = "vxworks"))]
+impl fmt::Debug for Dir {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
+// `openat`, `fdopendir` and the other `*at` functions are only available on
+// macOS 10.10+, so they have to be looked up at runtime.
+#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+mod macos_weak {
+    use libc::{c_char, c_int, mode_t, stat, DIR};
+
+    pub fn available() -> bool {
+        weak!(fn openat(c_int, *const c_char, c_int, c_int) -> c_int);
+        weak!(fn unlinkat(c_int, *const c_char, c_int) -> c_int);
+        weak!(fn mkdirat(c_int, *const c_char, mode_t) -> c_int);
+        weak!(fn renameat(c_int, *const c_char, c_int, *const c_char) -> c_int);
+        weak!(fn fstatat(c_int, *const c_char, *mut stat, c_int) -> c_int, "fstatat$INODE64");
+        weak!(fn fdopendir(c_int) -> *mut DIR, "fdopendir$INODE64");
+        openat.get().is_some()
+            && unlinkat.get().is_some()
+            && mkdirat.get().is_some()
+            && renameat.get().is_some()
+            && fstatat.get().is_some()
+            && fdopendir.get().is_some()
+    }
+
+    pub unsafe fn openat(
+        dirfd: c_int,
+        pathname: *const c_char,
+        flags: c_int,
+        mode: c_int,
+    ) -> c_int {
+        weak!(fn openat(c_int, *const c_char, c_int, c_int) -> c_int);
+        openat.get().unwrap()(dirfd, pathname, flags, mode)
+    }
+
+    pub unsafe fn unlinkat(dirfd: c_int, pathname: *const c_char, flags: c_int) -> c_int {
+        weak!(fn unlinkat(c_int, *const c_char, c_int) -> c_int);
+        unlinkat.get().unwrap()(dirfd, pathname, flags)
+    }
+
+    pub unsafe fn mkdirat(dirfd: c_int, pathname: *const c_char, mode: mode_t) -> c_int {
+        weak!(fn mkdirat(c_int, *const c_char, mode_t) -> c_int);
+        mkdirat.get().unwrap()(dirfd, pathname, mode)
+    }
+
+    pub unsafe fn renameat(
+        olddirfd: c_int,
+        oldpath: *const c_char,
+        newdirfd: c_int,
+        newpath: *const c_char,
+    ) -> c_int {
+        weak!(fn renameat(c_int, *const c_char, c_int, *const c_char) -> c_int);
+        renameat.get().unwrap()(olddirfd, oldpath, newdirfd, newpath)
+    }
+
+    pub unsafe fn fstatat64(
+        dirfd: c_int,
+        pathname: *const c_char,
+        buf: *mut stat,
+        flags: c_int,
+    ) -> c_int {
+        weak!(fn fstatat(c_int, *const c_char, *mut stat, c_int) -> c_int, "fstatat$INODE64");
+        fstatat.get().unwrap()(dirfd, pathname, buf, flags)
+    }
+
+    pub unsafe fn fdopendir(fd: c_int) -> *mut DIR {
+        weak!(fn fdopendir(c_int) -> *mut DIR, "fdopendir$INODE64");
+        fdopendir.get().unwrap()(fd)
+    }
+}
+
 impl FromInner<c_int> for File {
     fn from_inner(fd: c_int) -> File {
         File(FileDesc::new(fd))
@@ -1004,17 +1277,7 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
         if ptr.is_null() {
             Err(Error::last_os_error())
         } else {
-            let inner = InnerReadDir { dirp: Dir(ptr), root };
-            Ok(ReadDir {
-                inner: Arc::new(inner),
-                #[cfg(not(any(
-                    target_os = "solaris",
-                    target_os = "illumos",
-                    target_os = "fuchsia",
-                    target_os = "redox",
-                )))]
-                end_of_stream: false,
-            })
+            Ok(ReadDir::new(InnerReadDir { dirp: DirStream(ptr), root }))
         }
     }
 }
@@ -1330,3 +1593,134 @@ fn fclonefileat(
     })?;
     Ok(bytes_copied as u64)
 }
+
+// Implementation that never follows a symlink, even if one is swapped in for a
+// directory during the traversal: every directory is opened relative to its
+// parent's file descriptor with `O_NOFOLLOW`, and entries are removed relative
+// to the file descriptor of the directory they were read from.
+#[cfg(not(any(target_os = "redox", target_os = "vxworks")))]
+mod remove_dir_impl {
+    use super::{cstr, fdopendir, lstat, openat, unlinkat};
+    use super::{DirEntry, DirStream, InnerReadDir, ReadDir};
+    use crate::ffi::{CStr, CString};
+    use crate::io;
+    use crate::path::{Path, PathBuf};
+    use crate::sys::fd::FileDesc;
+    use crate::sys::{cvt, cvt_r};
+    use libc::c_int;
+
+    fn openat_nofollow_dironly(parent_fd: Option<c_int>, p: &CStr) -> io::Result<FileDesc> {
+        let fd = cvt_r(|| unsafe {
+            openat(
+                parent_fd.unwrap_or(libc::AT_FDCWD),
+                p.as_ptr(),
+                libc::O_CLOEXEC | libc::O_RDONLY | libc::O_NOFOLLOW | libc::O_DIRECTORY,
+                0,
+            )
+        })?;
+        Ok(FileDesc::new(fd))
+    }
+
+    fn fdreaddir(dir_fd: FileDesc) -> io::Result<(ReadDir, c_int)> {
+        let ptr = unsafe { fdopendir(dir_fd.raw()) };
+        if ptr.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let dirp = DirStream(ptr);
+        // The file descriptor is closed by `closedir` now, so give up ownership.
+        let fd = dir_fd.into_raw();
+        // A valid root is not needed, as nothing here uses the full path of the
+        // entries.
+        Ok((ReadDir::new(InnerReadDir { dirp, root: PathBuf::new() }), fd))
+    }
+
+    #[cfg(any(
+        target_os = "solaris",
+        target_os = "illumos",
+        target_os = "haiku",
+        target_os = "fuchsia"
+    ))]
+    fn is_dir(_ent: &DirEntry) -> Option<bool> {
+        None
+    }
+
+    #[cfg(not(any(
+        target_os = "solaris",
+        target_os = "illumos",
+        target_os = "haiku",
+        target_os = "fuchsia"
+    )))]
+    fn is_dir(ent: &DirEntry) -> Option<bool> {
+        match ent.entry.d_type {
+            libc::DT_UNKNOWN => None,
+            libc::DT_DIR => Some(true),
+            _ => Some(false),
+        }
+    }
+
+    fn remove_dir_all_recursive(parent_fd: Option<c_int>, path: &CStr) -> io::Result<()> {
+        // The entry is expected to be a directory; if it has been replaced by
+        // a symlink or a file in the meantime, opening it fails. Below the
+        // root, remove the replacement instead of aborting the whole removal.
+        let fd = match openat_nofollow_dironly(parent_fd, path) {
+            Err(err)
+                if parent_fd.is_some()
+                    && (err.raw_os_error() == Some(libc::ELOOP)
+                        || err.raw_os_error() == Some(libc::ENOTDIR)) =>
+            {
+                let parent_fd = parent_fd.unwrap();
+                return cvt(unsafe { unlinkat(parent_fd, path.as_ptr(), 0) }).map(drop);
+            }
+            result => result?,
+        };
+        let (dir, fd) = fdreaddir(fd)?;
+        for child in dir {
+            let child = child?;
+            let name = CString::new(child.name_bytes())?;
+            match is_dir(&child) {
+                Some(true) => remove_dir_all_recursive(Some(fd), &name)?,
+                Some(false) => cvt(unsafe { unlinkat(fd, name.as_ptr(), 0) }).map(drop)?,
+                // The type is unknown, so try to unlink it first. This fails
+                // with `EISDIR` on Linux and `EPERM` elsewhere for directories.
+                None => match cvt(unsafe { unlinkat(fd, name.as_ptr(), 0) }) {
+                    Err(err)
+                        if err.raw_os_error() == Some(libc::EISDIR)
+                            || err.raw_os_error() == Some(libc::EPERM) =>
+                    {
+                        remove_dir_all_recursive(Some(fd), &name)?
+                    }
+                    result => result.map(drop)?,
+                },
+            }
+        }
+
+        // Remove the directory itself once it's empty. If it has been swapped
+        // for something else after it was emptied, remove the replacement.
+        match cvt(unsafe {
+            unlinkat(parent_fd.unwrap_or(libc::AT_FDCWD), path.as_ptr(), libc::AT_REMOVEDIR)
+        }) {
+            Err(err) if parent_fd.is_some() && err.raw_os_error() == Some(libc::ENOTDIR) => {
+                let parent_fd = parent_fd.unwrap();
+                cvt(unsafe { unlinkat(parent_fd, path.as_ptr(), 0) }).map(drop)
+            }
+            result => result.map(drop),
+        }
+    }
+
+    pub fn remove_dir_all(p: &Path) -> io::Result<()> {
+        #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+        if !super::macos_weak::available() {
+            return crate::sys_common::fs::remove_dir_all(p);
+        }
+
+        // A symlink passed in directly is removed rather than traversed. There's
+        // no race to worry about here, as the recursive part never follows
+        // symlinks either.
+        let attr = lstat(p)?;
+        if attr.file_type().is_symlink() {
+            crate::fs::remove_file(p)
+        } else {
+            remove_dir_all_recursive(None, &cstr(p)?)
+        }
+    }
+}
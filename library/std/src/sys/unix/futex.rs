@@ -9,26 +9,58 @@
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use crate::ptr::null;
 use crate::sync::atomic::AtomicI32;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::sync::atomic::Ordering::Relaxed;
 use crate::time::Duration;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::time::Instant;
 
+/// Waits for a `futex_wake` operation to wake us.
+///
+/// Returns directly if the futex doesn't hold the expected value.
+///
+/// Returns false on timeout, and true in all other cases.
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) {
-    let timespec = timeout.and_then(|d| {
-        Some(libc::timespec {
-            // Sleep forever if the timeout is longer than fits in a timespec.
-            tv_sec: d.as_secs().try_into().ok()?,
-            // This conversion never truncates, as subsec_nanos is always <1e9.
-            tv_nsec: d.subsec_nanos() as _,
-        })
-    });
-    unsafe {
-        libc::syscall(
-            libc::SYS_futex,
-            futex as *const AtomicI32,
-            libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
-            expected,
-            timespec.as_ref().map_or(null(), |d| d as *const libc::timespec),
-        );
+pub fn futex_wait(futex: &AtomicI32, expected: i32, timeout: Option<Duration>) -> bool {
+    // Sleep forever if the deadline is further away than an `Instant` can represent.
+    let deadline = timeout.and_then(|d| Instant::now().checked_add(d));
+
+    loop {
+        // No need to wait if the value already changed.
+        if futex.load(Relaxed) != expected {
+            return true;
+        }
+
+        // Interrupted waits are restarted below, so the remaining time is recomputed
+        // every time to keep them from extending the total timeout.
+        let timespec = deadline.and_then(|deadline| {
+            let d = deadline.saturating_duration_since(Instant::now());
+            Some(libc::timespec {
+                // Sleep forever if the timeout is longer than fits in a timespec.
+                tv_sec: d.as_secs().try_into().ok()?,
+                // This conversion never truncates, as subsec_nanos is always <1e9.
+                tv_nsec: d.subsec_nanos() as _,
+            })
+        });
+
+        let r = unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                futex as *const AtomicI32,
+                libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+                expected,
+                timespec.as_ref().map_or(null(), |d| d as *const libc::timespec),
+            )
+        };
+
+        if r < 0 {
+            match super::os::errno() {
+                libc::ETIMEDOUT => return false,
+                libc::EINTR => continue,
+                _ => {}
+            }
+        }
+        return true;
     }
 }
 
@@ -53,14 +85,31 @@ fn emscripten_futex_wait(
     }
 }
 
+/// Wakes up one thread that's blocked on `futex_wait` on this futex.
+///
+/// Returns true if this actually woke up such a thread,
+/// or false if no thread was waiting on this futex.
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub fn futex_wake(futex: &AtomicI32) {
+pub fn futex_wake(futex: &AtomicI32) -> bool {
     unsafe {
         libc::syscall(
             libc::SYS_futex,
             futex as *const AtomicI32,
             libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
             1,
+        ) > 0
+    }
+}
+
+/// Wakes up all threads that are waiting on `futex_wait` on this futex.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn futex_wake_all(futex: &AtomicI32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex as *const AtomicI32,
+            libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+            i32::MAX,
         );
     }
 }
@@ -17,6 +17,8 @@
 //! Once it has obtained all necessary pieces and brought any wrapper types into a state where they
 //! can be safely bypassed it will attempt to use the `copy_file_range(2)`,
 //! `sendfile(2)` or `splice(2)` syscalls to move data directly between file descriptors.
+//! `splice` requires one side to be a pipe, so reading from a socket into something that isn't
+//! a pipe (e.g. another socket) splices through an intermediate pipe instead.
 //! Since those syscalls have requirements that cannot be fully checked in advance and
 //! gathering additional information about file descriptors would require additional syscalls
 //! anyway it simply attempts to use them one after another (guided by inaccurate hints) to
@@ -49,8 +51,8 @@
 use crate::fs::{File, Metadata};
 use crate::io::copy::generic_copy;
 use crate::io::{
-    BufRead, BufReader, BufWriter, Error, Read, Result, StderrLock, StdinLock, StdoutLock, Take,
-    Write,
+    BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Result, StderrLock, StdinLock,
+    StdoutLock, Take, Write,
 };
 use crate::mem::ManuallyDrop;
 use crate::net::TcpStream;
@@ -61,6 +63,8 @@
 use crate::ptr;
 use crate::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use crate::sys::cvt;
+use crate::sys::pipe::anon_pipe;
+use crate::sys_common::io::DEFAULT_BUF_SIZE;
 
 #[cfg(test)]
 mod tests;
@@ -100,6 +104,15 @@ fn maybe_fifo(&self) -> bool {
         }
     }
 
+    fn maybe_socket(&self) -> bool {
+        match self {
+            FdMeta::Metadata(meta) => meta.file_type().is_socket(),
+            FdMeta::Socket => true,
+            FdMeta::Pipe => false,
+            FdMeta::NoneObtained => false,
+        }
+    }
+
     fn potential_sendfile_source(&self) -> bool {
         match self {
             // procfs erronously shows 0 length on non-empty readable files.
@@ -205,6 +218,19 @@ fn copy(self) -> Result<u64> {
                     }
                 }
             }
+
+            // neither side is a pipe, or splicing directly between them did not work.
+            // Sockets can still be spliced from if the data takes a detour through a pipe.
+            if input_meta.maybe_socket() {
+                let result = splice_through_pipe(readfd, writefd, max_write);
+                result.update_take(reader);
+
+                match result {
+                    CopyResult::Ended(bytes_copied) => return Ok(bytes_copied + written),
+                    CopyResult::Error(e, _) => return Err(e),
+                    CopyResult::Fallback(bytes) => written += bytes,
+                }
+            }
         }
 
         // fallback if none of the more specialized syscalls wants to work with these file descriptors
@@ -365,6 +391,12 @@ fn properties(&self) -> CopyParams {
     }
 }
 
+impl CopyWrite for &ChildStdin {
+    fn properties(&self) -> CopyParams {
+        CopyParams(FdMeta::Pipe, Some(self.as_raw_fd()))
+    }
+}
+
 impl CopyRead for ChildStdout {
     fn properties(&self) -> CopyParams {
         CopyParams(FdMeta::Pipe, Some(self.as_raw_fd()))
@@ -597,6 +629,19 @@ fn copy_file_range(
     CopyResult::Ended(written)
 }
 
+static HAS_SPLICE: AtomicBool = AtomicBool::new(true);
+
+syscall! {
+    fn splice(
+        srcfd: libc::c_int,
+        src_offset: *const i64,
+        dstfd: libc::c_int,
+        dst_offset: *const i64,
+        len: libc::size_t,
+        flags: libc::c_int
+    ) -> libc::ssize_t
+}
+
 #[derive(PartialEq)]
 enum SpliceMode {
     Sendfile,
@@ -607,18 +652,6 @@ enum SpliceMode {
 /// Does _not_ fall back to a generic copy loop.
 fn sendfile_splice(mode: SpliceMode, reader: RawFd, writer: RawFd, len: u64) -> CopyResult {
     static HAS_SENDFILE: AtomicBool = AtomicBool::new(true);
-    static HAS_SPLICE: AtomicBool = AtomicBool::new(true);
-
-    syscall! {
-        fn splice(
-            srcfd: libc::c_int,
-            src_offset: *const i64,
-            dstfd: libc::c_int,
-            dst_offset: *const i64,
-            len: libc::size_t,
-            flags: libc::c_int
-        ) -> libc::ssize_t
-    }
 
     match mode {
         SpliceMode::Sendfile if !HAS_SENDFILE.load(Ordering::Relaxed) => {
@@ -674,3 +707,94 @@ fn splice(
     }
     CopyResult::Ended(written)
 }
+
+/// Splices from `reader` into a freshly created pipe and from there into `writer`.
+///
+/// This extends `splice` to pairs of file descriptors where neither side is a pipe,
+/// such as socket to socket copies. Does _not_ fall back to a generic copy loop, but
+/// if `writer` turns out not to support splicing the bytes already moved into the pipe
+/// are written to it with regular `read`/`write` calls and reported as `Fallback`.
+fn splice_through_pipe(reader: RawFd, writer: RawFd, len: u64) -> CopyResult {
+    if !HAS_SPLICE.load(Ordering::Relaxed) {
+        return CopyResult::Fallback(0);
+    }
+
+    let (pipe_out, pipe_in) = match anon_pipe() {
+        Ok(pipe) => pipe,
+        Err(_) => return CopyResult::Fallback(0),
+    };
+    let (pipe_reader, pipe_writer) = (pipe_out.fd().raw(), pipe_in.fd().raw());
+
+    let mut written = 0u64;
+    while written < len {
+        // the pipe is always empty at this point, so the kernel will never block on it
+        let chunk_size = min(len - written, 0x7ffff000_u64) as usize;
+
+        let filled = match cvt(unsafe {
+            splice(reader, ptr::null_mut(), pipe_writer, ptr::null_mut(), chunk_size, 0)
+        }) {
+            Ok(0) => break, // EOF
+            Ok(ret) => ret as usize,
+            Err(err) => {
+                return match err.raw_os_error() {
+                    Some(libc::ENOSYS | libc::EPERM) => {
+                        // syscall not supported (ENOSYS)
+                        // syscall is disallowed, e.g. by seccomp (EPERM)
+                        HAS_SPLICE.store(false, Ordering::Relaxed);
+                        assert_eq!(written, 0);
+                        CopyResult::Fallback(0)
+                    }
+                    Some(libc::EINVAL) => {
+                        // splice does not support reading from this file descriptor (EINVAL)
+                        assert_eq!(written, 0);
+                        CopyResult::Fallback(0)
+                    }
+                    _ => CopyResult::Error(err, written),
+                };
+            }
+        };
+
+        let mut drained = 0;
+        while drained < filled {
+            match cvt(unsafe {
+                splice(pipe_reader, ptr::null_mut(), writer, ptr::null_mut(), filled - drained, 0)
+            }) {
+                Ok(0) => {
+                    let err = Error::new(ErrorKind::WriteZero, "failed to write whole buffer");
+                    return CopyResult::Error(err, written + drained as u64);
+                }
+                Ok(ret) => drained += ret as usize,
+                Err(err) if written == 0 && drained == 0 => {
+                    if !matches!(err.raw_os_error(), Some(libc::EINVAL)) {
+                        return CopyResult::Error(err, 0);
+                    }
+                    // splice does not support writing to this file descriptor (EINVAL).
+                    // The data has already been taken out of `reader`, so it must not stay
+                    // behind in the pipe.
+                    return match drain_pipe(pipe_reader, writer, filled) {
+                        Ok(()) => CopyResult::Fallback(filled as u64),
+                        Err(err) => CopyResult::Error(err, 0),
+                    };
+                }
+                Err(err) => return CopyResult::Error(err, written + drained as u64),
+            }
+        }
+
+        written += filled as u64;
+    }
+    CopyResult::Ended(written)
+}
+
+/// Moves `len` bytes that are waiting in a pipe to `writer` with plain `read`/`write` calls.
+fn drain_pipe(pipe: RawFd, writer: RawFd, mut len: usize) -> Result<()> {
+    let mut pipe = ManuallyDrop::new(unsafe { File::from_raw_fd(pipe) });
+    let mut writer = ManuallyDrop::new(unsafe { File::from_raw_fd(writer) });
+    let mut buf = [0u8; DEFAULT_BUF_SIZE];
+    while len > 0 {
+        let chunk = min(len, buf.len());
+        pipe.read_exact(&mut buf[..chunk])?;
+        writer.write_all(&buf[..chunk])?;
+        len -= chunk;
+    }
+    Ok(())
+}
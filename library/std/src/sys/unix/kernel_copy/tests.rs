@@ -2,13 +2,18 @@
 use crate::io;
 use crate::io::Result;
 use crate::io::SeekFrom;
-use crate::io::{BufRead, Read, Seek, Write};
+use crate::io::{BufRead, BufReader, Read, Seek, Write};
+use crate::net::{TcpListener, TcpStream};
 use crate::os::unix::io::AsRawFd;
+use crate::os::unix::net::UnixStream;
+use crate::process::{ChildStdin, ChildStdout};
 use crate::sys_common::io::test::tmpdir;
+use crate::sys_common::FromInner;
+use crate::thread;
 
 #[test]
 fn copy_specialization() -> Result<()> {
-    use crate::io::{BufReader, BufWriter};
+    use crate::io::BufWriter;
 
     let tmp_path = tmpdir();
     let source_path = tmp_path.join("copy-spec.source");
@@ -65,6 +70,152 @@ fn copy_specialization() -> Result<()> {
     result.and(rm1).and(rm2)
 }
 
+/// Size of the test payloads, large enough to need more than one pipe buffer.
+const PAYLOAD_LEN: usize = 256 * 1024;
+
+fn payload() -> Vec<u8> {
+    (0..PAYLOAD_LEN).map(|i| (i % 251) as u8).collect()
+}
+
+fn tcp_pair() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("localhost:0").unwrap();
+    let connected = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let accepted = listener.accept().unwrap().0;
+    (connected, accepted)
+}
+
+fn pipe_pair() -> (ChildStdout, ChildStdin) {
+    let (read_end, write_end) = crate::sys::pipe::anon_pipe().unwrap();
+    (ChildStdout::from_inner(read_end), ChildStdin::from_inner(write_end))
+}
+
+/// Writes the payload to `feeder` on another thread and closes it afterwards.
+fn feed(mut feeder: impl Write + Send + 'static) -> thread::JoinHandle<()> {
+    thread::spawn(move || feeder.write_all(&payload()).unwrap())
+}
+
+/// Copies from `reader` to `writer` while another thread collects everything that comes out
+/// at `drainer`, then checks that all of `expected` made it through.
+fn assert_copies<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    mut drainer: impl Read + Send + 'static,
+    expected: &[u8],
+) {
+    let drain = thread::spawn(move || {
+        let mut received = Vec::new();
+        drainer.read_to_end(&mut received).unwrap();
+        received
+    });
+
+    let copied = io::copy(&mut reader, &mut writer).unwrap();
+    // close the sink so that the drainer sees EOF
+    drop(writer);
+
+    assert_eq!(copied, expected.len() as u64);
+    let received = drain.join().unwrap();
+    assert!(received == expected, "copied data differs from the source");
+}
+
+#[test]
+fn copy_from_tcp_stream() {
+    let expected = payload();
+
+    let ((reader, feeder), (writer, drainer)) = (tcp_pair(), tcp_pair());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+
+    let ((reader, feeder), (writer, drainer)) = (tcp_pair(), UnixStream::pair().unwrap());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+
+    let ((reader, feeder), (drainer, writer)) = (tcp_pair(), pipe_pair());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+}
+
+#[test]
+fn copy_from_unix_stream() {
+    let expected = payload();
+
+    let ((reader, feeder), (writer, drainer)) = (UnixStream::pair().unwrap(), tcp_pair());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+
+    let ((reader, feeder), (writer, drainer)) =
+        (UnixStream::pair().unwrap(), UnixStream::pair().unwrap());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+
+    let ((reader, feeder), (drainer, writer)) = (UnixStream::pair().unwrap(), pipe_pair());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+}
+
+#[test]
+fn copy_from_child_stdout() {
+    let expected = payload();
+
+    let ((reader, feeder), (writer, drainer)) = (pipe_pair(), tcp_pair());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+
+    let ((reader, feeder), (writer, drainer)) = (pipe_pair(), UnixStream::pair().unwrap());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+
+    let ((reader, feeder), (drainer, writer)) = (pipe_pair(), pipe_pair());
+    let fed = feed(feeder);
+    assert_copies(reader, writer, drainer, &expected);
+    fed.join().unwrap();
+}
+
+#[test]
+fn copy_from_buffered_socket() {
+    let expected = payload();
+    let (reader, mut feeder) = UnixStream::pair().unwrap();
+    let (writer, drainer) = tcp_pair();
+
+    // fill the BufReader so that its buffer has to be drained before splicing
+    feeder.write_all(&expected[..100]).unwrap();
+    let mut reader = BufReader::with_capacity(64, reader);
+    assert_eq!(reader.fill_buf().unwrap(), &expected[..64]);
+
+    let fed = thread::spawn(move || feeder.write_all(&payload()[100..]).unwrap());
+    let limit = PAYLOAD_LEN - 10;
+    assert_copies(reader.take(limit as u64), writer, drainer, &expected[..limit]);
+    fed.join().unwrap();
+}
+
+#[test]
+fn copy_between_file_and_socket() {
+    let expected = payload();
+    let tmp_path = tmpdir();
+    let source_path = tmp_path.join("copy-socket.source");
+    let sink_path = tmp_path.join("copy-socket.sink");
+
+    crate::fs::write(&source_path, &expected).unwrap();
+    let (writer, drainer) = tcp_pair();
+    assert_copies(crate::fs::File::open(&source_path).unwrap(), writer, drainer, &expected);
+
+    // splicing into a file opened for appending fails with EINVAL after the data has already
+    // been taken from the socket, so this also exercises the fallback
+    let (reader, feeder) = UnixStream::pair().unwrap();
+    let sink = OpenOptions::new().create(true).append(true).open(&sink_path).unwrap();
+    let fed = feed(feeder);
+    assert_eq!(io::copy(&mut &reader, &mut &sink).unwrap(), PAYLOAD_LEN as u64);
+    fed.join().unwrap();
+    assert!(crate::fs::read(&sink_path).unwrap() == expected);
+}
+
 #[bench]
 fn bench_file_to_file_copy(b: &mut test::Bencher) {
     const BYTES: usize = 128 * 1024;
@@ -162,8 +313,6 @@ fn bench_file_to_uds_copy(b: &mut test::Bencher) {
 fn bench_socket_pipe_socket_copy(b: &mut test::Bencher) {
     use super::CopyResult;
     use crate::io::ErrorKind;
-    use crate::process::{ChildStdin, ChildStdout};
-    use crate::sys_common::FromInner;
 
     let (read_end, write_end) = crate::sys::pipe::anon_pipe().unwrap();
 
@@ -8,8 +8,8 @@ pub mod net {
     #![allow(warnings)]
     use crate::convert::TryFrom;
     use crate::fmt;
-    use crate::io::{self, IoSlice, IoSliceMut};
-    use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
+    use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
+    use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketOptions};
     use crate::sys::fd::FileDesc;
     use crate::sys_common::{AsInner, FromInner, IntoInner};
     use crate::time::Duration;
@@ -139,6 +139,13 @@ pub fn connect(_: io::Result<&SocketAddr>) -> io::Result<TcpStream> {
             unimpl!();
         }
 
+        pub fn connect_with(
+            _: io::Result<&SocketAddr>,
+            _: &SocketOptions,
+        ) -> io::Result<TcpStream> {
+            unimpl!();
+        }
+
         pub fn connect_timeout(_: &SocketAddr, _: Duration) -> io::Result<TcpStream> {
             unimpl!();
         }
@@ -175,6 +182,10 @@ pub fn read(&self, _: &mut [u8]) -> io::Result<usize> {
             unimpl!();
         }
 
+        pub fn read_buf(&self, _: &mut ReadBuf<'_>) -> io::Result<()> {
+            unimpl!();
+        }
+
         pub fn read_vectored(&self, _: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
             unimpl!();
         }
@@ -257,6 +268,10 @@ pub fn bind(_: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
             unimpl!();
         }
 
+        pub fn bind_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<TcpListener> {
+            unimpl!();
+        }
+
         pub fn socket(&self) -> &Socket {
             &self.inner
         }
@@ -323,6 +338,10 @@ pub fn bind(_: io::Result<&SocketAddr>) -> io::Result<UdpSocket> {
             unimpl!();
         }
 
+        pub fn bind_with(_: io::Result<&SocketAddr>, _: &SocketOptions) -> io::Result<UdpSocket> {
+            unimpl!();
+        }
+
         pub fn socket(&self) -> &Socket {
             &self.inner
         }
@@ -44,7 +44,6 @@
 pub mod android;
 pub mod args;
 pub mod cmath;
-pub mod condvar;
 pub mod env;
 pub mod ext;
 pub mod fd;
@@ -56,7 +55,6 @@
 #[cfg(target_os = "l4re")]
 mod l4re;
 pub mod memchr;
-pub mod mutex;
 #[cfg(not(target_os = "l4re"))]
 pub mod net;
 #[cfg(target_os = "l4re")]
@@ -66,7 +64,6 @@
 pub mod pipe;
 pub mod process;
 pub mod rand;
-pub mod rwlock;
 pub mod stack_overflow;
 pub mod stdio;
 pub mod thread;
@@ -74,6 +71,32 @@
 pub mod thread_local_key;
 pub mod time;
 
+cfg_if::cfg_if! {
+    if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        // The locks are implemented directly on futexes here, which needs neither an
+        // allocation nor any initialization.
+        #[path = "futex_condvar.rs"]
+        pub mod condvar;
+        #[path = "futex_mutex.rs"]
+        pub mod mutex;
+        #[path = "futex_rwlock.rs"]
+        pub mod rwlock;
+        // Reentrant mutexes still use pthread, which already tracks the owning thread.
+        #[path = "mutex.rs"]
+        #[allow(dead_code)]
+        pub(crate) mod pthread_mutex;
+        // The pthread rwlock is only kept as a baseline for the futex one in benchmarks.
+        #[cfg(test)]
+        #[path = "rwlock.rs"]
+        #[allow(dead_code)]
+        pub(crate) mod pthread_rwlock;
+    } else {
+        pub mod condvar;
+        pub mod mutex;
+        pub mod rwlock;
+    }
+}
+
 pub use crate::sys_common::os_str_bytes as os_str;
 
 #[cfg(not(test))]
@@ -1,6 +1,6 @@
 use crate::cmp;
 use crate::ffi::CStr;
-use crate::io::{self, IoSlice, IoSliceMut};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
 use crate::net::{Shutdown, SocketAddr};
 use crate::str;
@@ -237,6 +237,24 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        let ret = cvt(unsafe {
+            libc::recv(
+                self.0.raw(),
+                buf.unfilled_mut().as_mut_ptr() as *mut c_void,
+                buf.remaining(),
+                0,
+            )
+        })?;
+
+        // SAFETY: `ret` bytes were written to the unfilled portion of the buffer
+        unsafe {
+            buf.assume_init(ret as usize);
+        }
+        buf.add_filled(ret as usize);
+        Ok(())
+    }
+
     pub fn peek(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, MSG_PEEK)
     }
@@ -379,6 +397,93 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    #[cfg(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "macos",
+        target_os = "ios",
+    ))]
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        setsockopt(self, libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as c_int)
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "macos",
+        target_os = "ios",
+    )))]
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other, "SO_REUSEPORT is not supported on this platform"))
+    }
+
+    /// Sets the idle time before the first keepalive probe and the interval
+    /// between probes. Both are given in whole seconds to the kernel.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "macos",
+        target_os = "ios",
+    ))]
+    pub fn set_keepalive_params(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+    ) -> io::Result<()> {
+        // Apple platforms call the idle time option `TCP_KEEPALIVE`.
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        use libc::TCP_KEEPALIVE as TCP_KEEPIDLE;
+        #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+        use libc::TCP_KEEPIDLE;
+
+        fn secs(dur: Duration) -> c_int {
+            let secs = dur.as_secs().saturating_add((dur.subsec_nanos() > 0) as u64);
+            cmp::max(cmp::min(secs, c_int::MAX as u64), 1) as c_int
+        }
+
+        if let Some(time) = time {
+            setsockopt(self, libc::IPPROTO_TCP, TCP_KEEPIDLE, secs(time))?;
+        }
+        if let Some(interval) = interval {
+            setsockopt(self, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL, secs(interval))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "macos",
+        target_os = "ios",
+    )))]
+    pub fn set_keepalive_params(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+    ) -> io::Result<()> {
+        if time.is_none() && interval.is_none() {
+            return Ok(());
+        }
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "setting the keepalive time or interval is not supported on this platform",
+        ))
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux",))]
     pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
         setsockopt(self, libc::SOL_SOCKET, libc::SO_PASSCRED, passcred as libc::c_int)
@@ -1,7 +1,9 @@
+use crate::cmp;
 use crate::io::{self, IoSlice, IoSliceMut};
 use crate::mem;
+use crate::sys::cvt;
 use crate::sys::fd::FileDesc;
-use crate::sys::{cvt, cvt_r};
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -74,37 +76,79 @@ pub fn into_fd(self) -> FileDesc {
 }
 
 pub fn read2(p1: AnonPipe, v1: &mut Vec<u8>, p2: AnonPipe, v2: &mut Vec<u8>) -> io::Result<()> {
+    read2_until(Some(p1), v1, Some(p2), v2, None).map(drop)
+}
+
+/// Reads both pipes to EOF like `read2`, but gives up once `deadline` has
+/// passed. Either pipe may be missing, in which case its buffer is left alone.
+///
+/// Returns `false` if the deadline was reached first; the buffers then hold
+/// whatever was read up to that point.
+pub fn read2_until(
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+    deadline: Option<Instant>,
+) -> io::Result<bool> {
     // Set both pipes into nonblocking mode as we're gonna be reading from both
     // in the `select` loop below, and we wouldn't want one to block the other!
-    let p1 = p1.into_fd();
-    let p2 = p2.into_fd();
-    p1.set_nonblocking(true)?;
-    p2.set_nonblocking(true)?;
+    let p1 = p1.map(AnonPipe::into_fd);
+    let p2 = p2.map(AnonPipe::into_fd);
+    for p in p1.iter().chain(p2.iter()) {
+        p.set_nonblocking(true)?;
+    }
 
+    // `poll` ignores entries with a negative fd, which is how missing and
+    // finished pipes are taken out of the loop.
     let mut fds: [libc::pollfd; 2] = unsafe { mem::zeroed() };
-    fds[0].fd = p1.raw();
+    fds[0].fd = p1.as_ref().map_or(-1, |p| p.raw());
     fds[0].events = libc::POLLIN;
-    fds[1].fd = p2.raw();
+    fds[1].fd = p2.as_ref().map_or(-1, |p| p.raw());
     fds[1].events = libc::POLLIN;
     loop {
-        // wait for either pipe to become readable using `poll`
-        cvt_r(|| unsafe { libc::poll(fds.as_mut_ptr(), 2, -1) })?;
+        if fds[0].fd < 0 && fds[1].fd < 0 {
+            return Ok(true);
+        }
+
+        let timeout = match deadline {
+            None => -1,
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(false);
+                }
+                // Round up so that we don't spin on sub-millisecond leftovers.
+                let ms = (deadline - now).as_nanos().saturating_add(999_999) / 1_000_000;
+                cmp::min(ms, libc::c_int::MAX as u128) as libc::c_int
+            }
+        };
+
+        // wait for either pipe to become readable using `poll`. The timeout
+        // is recomputed after an interruption rather than restarted.
+        if let Err(e) = cvt(unsafe { libc::poll(fds.as_mut_ptr(), 2, timeout) }) {
+            if e.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(e);
+        }
 
-        if fds[0].revents != 0 && read(&p1, v1)? {
-            p2.set_nonblocking(false)?;
-            return p2.read_to_end(v2).map(drop);
+        if let Some(ref p1) = p1 {
+            if fds[0].revents != 0 && read(p1, v1)? {
+                fds[0].fd = -1;
+            }
         }
-        if fds[1].revents != 0 && read(&p2, v2)? {
-            p1.set_nonblocking(false)?;
-            return p1.read_to_end(v1).map(drop);
+        if let Some(ref p2) = p2 {
+            if fds[1].revents != 0 && read(p2, v2)? {
+                fds[1].fd = -1;
+            }
         }
     }
 
     // Read as much as we can from each pipe, ignoring EWOULDBLOCK or
     // EAGAIN. If we hit EOF, then this will happen because the underlying
-    // reader will return Ok(0), in which case we'll see `Ok` ourselves. In
-    // this case we flip the other fd back into blocking mode and read
-    // whatever's leftover on that file descriptor.
+    // reader will return Ok(0), in which case we'll see `Ok` ourselves and
+    // stop polling that file descriptor.
     fn read(fd: &FileDesc, dst: &mut Vec<u8>) -> Result<bool, io::Error> {
         match fd.read_to_end(dst) {
             Ok(_) => Ok(true),
@@ -28,6 +28,17 @@ pub fn spawn(
             ));
         }
 
+        if self.get_pgroup().is_some()
+            || self.get_setsid()
+            || self.get_controlling_tty()
+            || self.get_foreground()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "process groups, sessions and terminals are not supported on this platform",
+            ));
+        }
+
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
         let process_handle = unsafe { self.do_exec(theirs, envp.as_ref())? };
@@ -6,6 +6,11 @@
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
 
+#[cfg(target_os = "linux")]
+use crate::os::linux::process::PidFd;
+#[cfg(target_os = "linux")]
+use crate::sys_common::{AsInner, FromInner};
+
 #[cfg(target_os = "vxworks")]
 use libc::RTP_ID as pid_t;
 
@@ -32,7 +37,8 @@ pub fn spawn(
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
-        if let Some(ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+        if let Some(mut ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+            self.open_pidfd(&mut ret);
             return Ok((ret, ours));
         }
 
@@ -78,14 +84,17 @@ pub fn spawn(
             }
         };
 
-        let mut p = Process { pid, status: None };
+        let mut p = Process::new(pid);
         drop(output);
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
         loop {
             match input.read(&mut bytes) {
-                Ok(0) => return Ok((p, ours)),
+                Ok(0) => {
+                    self.open_pidfd(&mut p);
+                    return Ok((p, ours));
+                }
                 Ok(8) => {
                     let (errno, footer) = bytes.split_at(4);
                     assert_eq!(
@@ -111,6 +120,29 @@ pub fn spawn(
         }
     }
 
+    // Attaches a pidfd to a freshly spawned child if one was requested.
+    //
+    // The child hasn't been reaped at this point, so its PID can't have been
+    // reused yet and `pidfd_open` is just as reliable as getting the pidfd from
+    // `clone3` atomically. Spawning through a raw `clone3` would bypass libc's
+    // fork handling, leaving its cached thread state stale in the child.
+    //
+    // Failing to create the pidfd doesn't fail the spawn, as the child is
+    // running already; the error is kept and reported by the pidfd accessors
+    // instead.
+    #[cfg(target_os = "linux")]
+    fn open_pidfd(&self, p: &mut Process) {
+        if self.get_create_pidfd() {
+            match pidfd::open(p.pid) {
+                Ok(fd) => p.pidfd = Some(PidFd::from_inner(fd)),
+                Err(err) => p.pidfd_error = Some(err),
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn open_pidfd(&self, _p: &mut Process) {}
+
     pub fn exec(&mut self, default: Stdio) -> io::Error {
         let envp = self.capture_env();
 
@@ -181,6 +213,35 @@ unsafe fn do_exec(
             cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO))?;
         }
 
+        // Process groups and sessions are set up before the credentials are
+        // changed, as the child may lose the permission to do so afterwards.
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
+        if let Some(pgroup) = self.get_pgroup() {
+            cvt(libc::setpgid(0, pgroup))?;
+        }
+        if self.get_controlling_tty() {
+            set_controlling_tty()?;
+        }
+        // emscripten has no signal support to keep the child from being stopped.
+        #[cfg(target_os = "emscripten")]
+        if self.get_foreground() {
+            return Err(io::Error::from_raw_os_error(libc::ENOSYS));
+        }
+        #[cfg(not(target_os = "emscripten"))]
+        if self.get_foreground() {
+            use crate::mem::MaybeUninit;
+            // Changing the foreground process group from the background sends
+            // `SIGTTOU`, which would stop the child. The signal mask is reset
+            // below anyway.
+            let mut set = MaybeUninit::<libc::sigset_t>::uninit();
+            cvt(sigemptyset(set.as_mut_ptr()))?;
+            cvt(sigaddset(set.as_mut_ptr(), libc::SIGTTOU))?;
+            cvt(libc::pthread_sigmask(libc::SIG_BLOCK, set.as_ptr(), ptr::null_mut()))?;
+            cvt(libc::tcsetpgrp(libc::STDIN_FILENO, libc::getpgrp()))?;
+        }
+
         #[cfg(not(target_os = "l4re"))]
         {
             if let Some(_g) = self.get_groups() {
@@ -289,11 +350,16 @@ fn posix_spawn(
         use crate::mem::MaybeUninit;
         use crate::sys::{self, cvt_nz};
 
+        // `posix_spawn` can put the child into a process group, but it can't
+        // create a new session or hand over a terminal portably.
         if self.get_gid().is_some()
             || self.get_uid().is_some()
             || (self.env_saw_path() && !self.program_is_path())
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
+            || self.get_setsid()
+            || self.get_controlling_tty()
+            || self.get_foreground()
         {
             return Ok(None);
         }
@@ -338,7 +404,7 @@ fn posix_spawn_file_actions_addchdir_np(
             None => None,
         };
 
-        let mut p = Process { pid: 0, status: None };
+        let mut p = Process::new(0);
 
         struct PosixSpawnFileActions<'a>(&'a mut MaybeUninit<libc::posix_spawn_file_actions_t>);
 
@@ -400,7 +466,11 @@ fn drop(&mut self) {
             cvt(sigaddset(set.as_mut_ptr(), libc::SIGPIPE))?;
             cvt_nz(libc::posix_spawnattr_setsigdefault(attrs.0.as_mut_ptr(), set.as_ptr()))?;
 
-            let flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            let mut flags = libc::POSIX_SPAWN_SETSIGDEF | libc::POSIX_SPAWN_SETSIGMASK;
+            if let Some(pgroup) = self.get_pgroup() {
+                cvt_nz(libc::posix_spawnattr_setpgroup(attrs.0.as_mut_ptr(), pgroup))?;
+                flags |= libc::POSIX_SPAWN_SETPGROUP;
+            }
             cvt_nz(libc::posix_spawnattr_setflags(attrs.0.as_mut_ptr(), flags as _))?;
 
             // Make sure we synchronize access to the global `environ` resource
@@ -419,6 +489,40 @@ fn drop(&mut self) {
     }
 }
 
+// Makes the terminal on the child's standard input its controlling terminal.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+))]
+unsafe fn set_controlling_tty() -> io::Result<()> {
+    cvt(libc::ioctl(libc::STDIN_FILENO, libc::TIOCSCTTY as _, 0)).map(drop)
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "solaris",
+    target_os = "illumos",
+)))]
+unsafe fn set_controlling_tty() -> io::Result<()> {
+    // This runs after `fork`, so the error can't allocate.
+    Err(io::Error::from_raw_os_error(libc::ENOSYS))
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Processes
 ////////////////////////////////////////////////////////////////////////////////
@@ -427,13 +531,53 @@ fn drop(&mut self) {
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    // A pidfd for the child, if one was requested. It is used in place of the
+    // PID where possible, as it can't ever refer to another process.
+    #[cfg(target_os = "linux")]
+    pidfd: Option<PidFd>,
+    // Why no pidfd is available even though one was requested.
+    #[cfg(target_os = "linux")]
+    pidfd_error: Option<io::Error>,
 }
 
 impl Process {
+    fn new(pid: pid_t) -> Process {
+        Process {
+            pid,
+            status: None,
+            #[cfg(target_os = "linux")]
+            pidfd: None,
+            #[cfg(target_os = "linux")]
+            pidfd_error: None,
+        }
+    }
+
     pub fn id(&self) -> u32 {
         self.pid as u32
     }
 
+    #[cfg(target_os = "linux")]
+    pub fn pidfd(&self) -> io::Result<&PidFd> {
+        self.pidfd.as_ref().ok_or_else(|| self.no_pidfd())
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn take_pidfd(&mut self) -> io::Result<PidFd> {
+        self.pidfd.take().ok_or_else(|| self.no_pidfd())
+    }
+
+    #[cfg(target_os = "linux")]
+    fn no_pidfd(&self) -> io::Error {
+        match &self.pidfd_error {
+            // `pidfd::open` only fails with OS errors, which can be copied.
+            Some(err) => match err.raw_os_error() {
+                Some(code) => io::Error::from_raw_os_error(code),
+                None => io::Error::new(err.kind(), "failed to create a pidfd for this child"),
+            },
+            None => io::Error::new(io::ErrorKind::Other, "no pidfd was created for this child"),
+        }
+    }
+
     pub fn kill(&mut self) -> io::Result<()> {
         // If we've already waited on this process then the pid can be recycled
         // and used for another process, and we probably shouldn't be killing
@@ -444,6 +588,10 @@ pub fn kill(&mut self) -> io::Result<()> {
                 "invalid argument: can't kill an exited process",
             ))
         } else {
+            #[cfg(target_os = "linux")]
+            if let Some(pidfd) = &self.pidfd {
+                return pidfd::send_signal(pidfd.as_inner(), libc::SIGKILL);
+            }
             cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
         }
     }
@@ -453,6 +601,13 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
         if let Some(status) = self.status {
             return Ok(status);
         }
+        #[cfg(target_os = "linux")]
+        if let Some(pidfd) = &self.pidfd {
+            let status = pidfd::waitid(pidfd.as_inner(), libc::WEXITED)?
+                .expect("waitid without WNOHANG returned no status");
+            self.status = Some(status);
+            return Ok(status);
+        }
         let mut status = 0 as c_int;
         cvt_r(|| unsafe { libc::waitpid(self.pid, &mut status, 0) })?;
         self.status = Some(ExitStatus::new(status));
@@ -463,6 +618,12 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         if let Some(status) = self.status {
             return Ok(Some(status));
         }
+        #[cfg(target_os = "linux")]
+        if let Some(pidfd) = &self.pidfd {
+            let status = pidfd::waitid(pidfd.as_inner(), libc::WEXITED | libc::WNOHANG)?;
+            self.status = status;
+            return Ok(status);
+        }
         let mut status = 0 as c_int;
         let pid = cvt(unsafe { libc::waitpid(self.pid, &mut status, libc::WNOHANG) })?;
         if pid == 0 {
@@ -533,3 +694,75 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 }
+
+#[cfg(target_os = "linux")]
+mod pidfd {
+    use super::ExitStatus;
+    use crate::io;
+    use crate::mem;
+    use crate::ptr;
+    use crate::sys::fd::FileDesc;
+    use crate::sys::{cvt, cvt_r};
+    use libc::{c_int, c_long, pid_t, uid_t};
+
+    // Not yet exposed by `libc`.
+    const P_PIDFD: libc::idtype_t = 3;
+
+    pub fn open(pid: pid_t) -> io::Result<FileDesc> {
+        let fd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) })?;
+        let fd = FileDesc::new(fd as c_int);
+        // `waitid` only accepts pidfds since Linux 5.4, one release after
+        // `pidfd_open` was added, so check for that before relying on it.
+        // `WNOWAIT` leaves the child unreaped if it has exited already.
+        waitid(&fd, libc::WEXITED | libc::WNOHANG | libc::WNOWAIT)?;
+        Ok(fd)
+    }
+
+    pub fn send_signal(fd: &FileDesc, signal: c_int) -> io::Result<()> {
+        cvt(unsafe {
+            libc::syscall(
+                libc::SYS_pidfd_send_signal,
+                fd.raw(),
+                signal,
+                ptr::null::<libc::siginfo_t>(),
+                0,
+            )
+        })
+        .map(drop)
+    }
+
+    // Returns `None` if `WNOHANG` was passed and the child hasn't exited yet.
+    pub fn waitid(fd: &FileDesc, options: c_int) -> io::Result<Option<ExitStatus>> {
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        cvt_r(|| unsafe { libc::waitid(P_PIDFD, fd.raw() as libc::id_t, &mut info, options) })?;
+        let sigchld = unsafe { &(*(&info as *const libc::siginfo_t as *const Siginfo)).sigchld };
+        if sigchld.si_pid == 0 {
+            return Ok(None);
+        }
+        // Reassemble the status that `waitpid` would have reported.
+        let status = match info.si_code {
+            libc::CLD_EXITED => (sigchld.si_status & 0xff) << 8,
+            libc::CLD_DUMPED => sigchld.si_status | 0x80,
+            _ => sigchld.si_status,
+        };
+        Ok(Some(ExitStatus::new(status)))
+    }
+
+    // The layout of `siginfo_t` for `SIGCHLD`, whose fields `libc` doesn't
+    // expose yet. The `c_long` fields give the union the same alignment as in
+    // the kernel's definition.
+    #[repr(C)]
+    struct Siginfo {
+        _head: [c_int; 3],
+        sigchld: Sigchld,
+    }
+
+    #[repr(C)]
+    struct Sigchld {
+        si_pid: pid_t,
+        _si_uid: uid_t,
+        si_status: c_int,
+        _si_utime: c_long,
+        _si_stime: c_long,
+    }
+}
@@ -30,7 +30,13 @@ macro_rules! weak {
     (fn $name:ident($($t:ty),*) -> $ret:ty) => (
         static $name: crate::sys::weak::Weak<unsafe extern "C" fn($($t),*) -> $ret> =
             crate::sys::weak::Weak::new(concat!(stringify!($name), '\0'));
-    )
+    );
+    // For symbols whose name differs from the function's, e.g. because of
+    // symbol versioning on macOS.
+    (fn $name:ident($($t:ty),*) -> $ret:ty, $sym:literal) => (
+        static $name: crate::sys::weak::Weak<unsafe extern "C" fn($($t),*) -> $ret> =
+            crate::sys::weak::Weak::new(concat!($sym, '\0'));
+    );
 }
 
 pub struct Weak<F> {
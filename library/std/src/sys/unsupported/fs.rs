@@ -1,7 +1,7 @@
 use crate::ffi::OsString;
 use crate::fmt;
 use crate::hash::{Hash, Hasher};
-use crate::io::{self, IoSlice, IoSliceMut, SeekFrom};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf, SeekFrom};
 use crate::path::{Path, PathBuf};
 use crate::sys::time::SystemTime;
 use crate::sys::{unsupported, Void};
@@ -14,6 +14,8 @@
 
 pub struct DirEntry(Void);
 
+pub struct Dir(Void);
+
 #[derive(Clone, Debug)]
 pub struct OpenOptions {}
 
@@ -202,6 +204,10 @@ pub fn read_vectored(&self, _bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         match self.0 {}
     }
 
+    pub fn read_buf(&self, _buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        match self.0 {}
+    }
+
     pub fn is_read_vectored(&self) -> bool {
         match self.0 {}
     }
@@ -245,6 +251,42 @@ pub fn mkdir(&self, _p: &Path) -> io::Result<()> {
     }
 }
 
+impl Dir {
+    pub fn open(_p: &Path) -> io::Result<Dir> {
+        unsupported()
+    }
+
+    pub fn open_file(&self, _p: &Path, _opts: &OpenOptions) -> io::Result<File> {
+        match self.0 {}
+    }
+
+    pub fn create_dir(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn remove_file(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &Dir, _to: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn metadata(&self, _p: &Path) -> io::Result<FileAttr> {
+        match self.0 {}
+    }
+
+    pub fn read_dir(&self, _p: &Path) -> io::Result<ReadDir> {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
 impl fmt::Debug for File {
     fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {}
@@ -1,5 +1,7 @@
 use crate::cell::Cell;
 
+pub type MovableRWLock = RWLock;
+
 pub struct RWLock {
     // This platform has no threads, so we can use a Cell here.
     mode: Cell<isize>,
@@ -24,6 +24,17 @@ pub fn spawn(
         if self.saw_nul() {
             return Err(io::Error::new(ErrorKind::InvalidInput, "nul byte found in provided data"));
         }
+
+        if self.get_pgroup().is_some()
+            || self.get_setsid()
+            || self.get_controlling_tty()
+            || self.get_foreground()
+        {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                "process groups, sessions and terminals are not supported on this platform",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -2,7 +2,7 @@
 
 use crate::ffi::{CStr, CString, OsStr, OsString};
 use crate::fmt;
-use crate::io::{self, IoSlice, IoSliceMut, SeekFrom};
+use crate::io::{self, IoSlice, IoSliceMut, ReadBuf, SeekFrom};
 use crate::iter;
 use crate::mem::{self, ManuallyDrop};
 use crate::os::wasi::ffi::{OsStrExt, OsStringExt};
@@ -11,7 +11,7 @@
 use crate::sync::Arc;
 use crate::sys::fd::WasiFd;
 use crate::sys::time::SystemTime;
-use crate::sys::unsupported;
+use crate::sys::{unsupported, Void};
 use crate::sys_common::FromInner;
 
 pub use crate::sys_common::fs::remove_dir_all;
@@ -69,6 +69,10 @@ pub struct FileType {
 #[derive(Debug)]
 pub struct DirBuilder {}
 
+// WASI's file operations are all relative to a directory already, but handles
+// for arbitrary directories aren't exposed yet.
+pub struct Dir(Void);
+
 impl FileAttr {
     pub fn size(&self) -> u64 {
         self.meta.size
@@ -404,6 +408,10 @@ pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.fd.read(bufs)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        crate::io::default_read_buf(|buf| self.read(buf), buf)
+    }
+
     #[inline]
     pub fn is_read_vectored(&self) -> bool {
         true
@@ -471,6 +479,42 @@ pub fn mkdir(&self, p: &Path) -> io::Result<()> {
     }
 }
 
+impl Dir {
+    pub fn open(_p: &Path) -> io::Result<Dir> {
+        unsupported()
+    }
+
+    pub fn open_file(&self, _p: &Path, _opts: &OpenOptions) -> io::Result<File> {
+        match self.0 {}
+    }
+
+    pub fn create_dir(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn remove_file(&self, _p: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn rename(&self, _from: &Path, _to_dir: &Dir, _to: &Path) -> io::Result<()> {
+        match self.0 {}
+    }
+
+    pub fn metadata(&self, _p: &Path) -> io::Result<FileAttr> {
+        match self.0 {}
+    }
+
+    pub fn read_dir(&self, _p: &Path) -> io::Result<ReadDir> {
+        match self.0 {}
+    }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {}
+    }
+}
+
 impl fmt::Debug for File {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("File").field("fd", &self.fd.as_raw()).finish()
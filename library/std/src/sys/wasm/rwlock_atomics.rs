@@ -2,6 +2,8 @@
 use crate::sys::condvar::Condvar;
 use crate::sys::mutex::Mutex;
 
+pub type MovableRWLock = RWLock;
+
 pub struct RWLock {
     lock: Mutex,
     cond: Condvar,
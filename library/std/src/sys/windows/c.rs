@@ -124,6 +124,7 @@ fn clone(&self) -> Self {
 
 pub const WSA_FLAG_OVERLAPPED: DWORD = 0x01;
 pub const WSA_FLAG_NO_HANDLE_INHERIT: DWORD = 0x80;
+pub const SIO_KEEPALIVE_VALS: DWORD = 0x98000004;
 
 pub const WSADESCRIPTION_LEN: usize = 256;
 pub const WSASYS_STATUS_LEN: usize = 128;
@@ -232,6 +233,10 @@ fn clone(&self) -> Self {
 pub const SOL_SOCKET: c_int = 0xffff;
 pub const SO_RCVTIMEO: c_int = 0x1006;
 pub const SO_SNDTIMEO: c_int = 0x1005;
+pub const SO_REUSEADDR: c_int = 0x0004;
+pub const SO_KEEPALIVE: c_int = 0x0008;
+pub const SO_SNDBUF: c_int = 0x1001;
+pub const SO_RCVBUF: c_int = 0x1002;
 pub const IPPROTO_IP: c_int = 0;
 pub const IPPROTO_TCP: c_int = 6;
 pub const IPPROTO_IPV6: c_int = 41;
@@ -319,6 +324,13 @@ pub struct WSABUF {
     pub buf: *mut CHAR,
 }
 
+#[repr(C)]
+pub struct tcp_keepalive {
+    pub onoff: ULONG,
+    pub keepalivetime: ULONG,
+    pub keepaliveinterval: ULONG,
+}
+
 #[repr(C)]
 pub struct WSAPROTOCOL_INFO {
     pub dwServiceFlags1: DWORD,
@@ -792,6 +804,17 @@ pub fn WSASocketW(
         g: GROUP,
         dwFlags: DWORD,
     ) -> SOCKET;
+    pub fn WSAIoctl(
+        s: SOCKET,
+        dwIoControlCode: DWORD,
+        lpvInBuffer: LPVOID,
+        cbInBuffer: DWORD,
+        lpvOutBuffer: LPVOID,
+        cbOutBuffer: DWORD,
+        lpcbBytesReturned: LPDWORD,
+        lpOverlapped: LPWSAOVERLAPPED,
+        lpCompletionRoutine: LPWSAOVERLAPPED_COMPLETION_ROUTINE,
+    ) -> c_int;
     pub fn ioctlsocket(s: SOCKET, cmd: c_long, argp: *mut c_ulong) -> c_int;
     pub fn InitializeCriticalSection(CriticalSection: *mut CRITICAL_SECTION);
     pub fn EnterCriticalSection(CriticalSection: *mut CRITICAL_SECTION);
@@ -1,7 +1,7 @@
 #![unstable(issue = "none", feature = "windows_net")]
 
 use crate::cmp;
-use crate::io::{self, IoSlice, IoSliceMut, Read};
+use crate::io::{self, IoSlice, IoSliceMut, Read, ReadBuf};
 use crate::mem;
 use crate::net::{Shutdown, SocketAddr};
 use crate::ptr;
@@ -242,6 +242,25 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.recv_with_flags(buf, 0)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        // See `recv_with_flags` for why a shut down socket is treated as EOF.
+        let len = cmp::min(buf.remaining(), i32::MAX as usize) as i32;
+        let ret =
+            unsafe { c::recv(self.0, buf.unfilled_mut().as_mut_ptr() as *mut c_void, len, 0) };
+        match ret {
+            -1 if unsafe { c::WSAGetLastError() } == c::WSAESHUTDOWN => Ok(()),
+            -1 => Err(last_error()),
+            n => {
+                // SAFETY: `n` bytes were written to the unfilled portion of the buffer
+                unsafe {
+                    buf.assume_init(n as usize);
+                }
+                buf.add_filled(n as usize);
+                Ok(())
+            }
+        }
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         // On unix when a socket is shut down all further reads return 0, so we
         // do the same on windows to map a shut down socket to returning EOF.
@@ -398,6 +417,44 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, _reuse: bool) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other, "SO_REUSEPORT is not supported on Windows"))
+    }
+
+    pub fn set_keepalive_params(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+    ) -> io::Result<()> {
+        if time.is_none() && interval.is_none() {
+            return Ok(());
+        }
+
+        // Windows only takes both values at once, so fill in its documented
+        // defaults (two hours and one second) for the missing one. This also
+        // turns keepalive on.
+        let mut vals = c::tcp_keepalive {
+            onoff: 1,
+            keepalivetime: time.map_or(2 * 60 * 60 * 1000, super::dur2timeout),
+            keepaliveinterval: interval.map_or(1000, super::dur2timeout),
+        };
+        let mut returned = 0;
+        let r = unsafe {
+            c::WSAIoctl(
+                self.0,
+                c::SIO_KEEPALIVE_VALS,
+                &mut vals as *mut _ as c::LPVOID,
+                mem::size_of_val(&vals) as c::DWORD,
+                ptr::null_mut(),
+                0,
+                &mut returned,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        if r == 0 { Ok(()) } else { Err(last_error()) }
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = net::getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)?;
         if raw == 0 { Ok(None) } else { Ok(Some(io::Error::from_raw_os_error(raw as i32))) }
@@ -12,6 +12,7 @@
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::hashmap_random_keys;
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -202,35 +203,61 @@ pub fn is_write_vectored(&self) -> bool {
 }
 
 pub fn read2(p1: AnonPipe, v1: &mut Vec<u8>, p2: AnonPipe, v2: &mut Vec<u8>) -> io::Result<()> {
-    let p1 = p1.into_handle();
-    let p2 = p2.into_handle();
+    read2_until(Some(p1), v1, Some(p2), v2, None).map(drop)
+}
 
-    let mut p1 = AsyncPipe::new(p1, v1)?;
-    let mut p2 = AsyncPipe::new(p2, v2)?;
-    let objs = [p1.event.raw(), p2.event.raw()];
+/// Reads both pipes to EOF like `read2`, but gives up once `deadline` has
+/// passed. Either pipe may be missing, in which case its buffer is left alone.
+///
+/// Returns `false` if the deadline was reached first; the buffers then hold
+/// whatever was read up to that point.
+pub fn read2_until(
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+    deadline: Option<Instant>,
+) -> io::Result<bool> {
+    let mut pipes = Vec::with_capacity(2);
+    if let Some(p1) = p1 {
+        pipes.push(AsyncPipe::new(p1.into_handle(), v1)?);
+    }
+    if let Some(p2) = p2 {
+        pipes.push(AsyncPipe::new(p2.into_handle(), v2)?);
+    }
 
-    // In a loop we wait for either pipe's scheduled read operation to complete.
+    // In a loop we wait for any pipe's scheduled read operation to complete.
     // If the operation completes with 0 bytes, that means EOF was reached, in
-    // which case we just finish out the other pipe entirely.
+    // which case we stop waiting on that pipe. If the deadline passes first,
+    // the pending reads are cancelled as the remaining pipes are dropped.
     //
     // Note that overlapped I/O is in general super unsafe because we have to
     // be careful to ensure that all pointers in play are valid for the entire
     // duration of the I/O operation (where tons of operations can also fail).
     // The destructor for `AsyncPipe` ends up taking care of most of this.
-    loop {
-        let res = unsafe { c::WaitForMultipleObjects(2, objs.as_ptr(), c::FALSE, c::INFINITE) };
-        if res == c::WAIT_OBJECT_0 {
-            if !p1.result()? || !p1.schedule_read()? {
-                return p2.finish();
-            }
-        } else if res == c::WAIT_OBJECT_0 + 1 {
-            if !p2.result()? || !p2.schedule_read()? {
-                return p1.finish();
+    while !pipes.is_empty() {
+        let objs = [pipes[0].event.raw(), pipes.last().unwrap().event.raw()];
+        let timeout = match deadline {
+            Some(deadline) => {
+                super::dur2timeout(deadline.saturating_duration_since(Instant::now()))
             }
-        } else {
+            None => c::INFINITE,
+        };
+        let res = unsafe {
+            c::WaitForMultipleObjects(pipes.len() as c::DWORD, objs.as_ptr(), c::FALSE, timeout)
+        };
+        if res == c::WAIT_TIMEOUT {
+            return Ok(false);
+        }
+        let i = res.wrapping_sub(c::WAIT_OBJECT_0) as usize;
+        if i >= pipes.len() {
             return Err(io::Error::last_os_error());
         }
+        if !pipes[i].result()? || !pipes[i].schedule_read()? {
+            pipes.remove(i);
+        }
     }
+    Ok(true)
 }
 
 struct AsyncPipe<'a> {
@@ -318,17 +345,6 @@ fn result(&mut self) -> io::Result<bool> {
         }
         Ok(amt != 0)
     }
-
-    /// Finishes out reading this pipe entirely.
-    ///
-    /// Waits for any pending and schedule read, and then calls `read_to_end`
-    /// if necessary to read all the remaining information.
-    fn finish(&mut self) -> io::Result<()> {
-        while self.result()? && self.schedule_read()? {
-            // ...
-        }
-        Ok(())
-    }
 }
 
 impl<'a> Drop for AsyncPipe<'a> {
@@ -1,6 +1,8 @@
 use crate::cell::UnsafeCell;
 use crate::sys::c;
 
+pub type MovableRWLock = RWLock;
+
 pub struct RWLock {
     inner: UnsafeCell<c::SRWLOCK>,
 }
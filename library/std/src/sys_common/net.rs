@@ -5,9 +5,9 @@
 use crate::convert::{TryFrom, TryInto};
 use crate::ffi::CString;
 use crate::fmt;
-use crate::io::{self, Error, ErrorKind, IoSlice, IoSliceMut};
+use crate::io::{self, Error, ErrorKind, IoSlice, IoSliceMut, ReadBuf};
 use crate::mem;
-use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr};
+use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, SocketOptions};
 use crate::ptr;
 use crate::sys::net::netc as c;
 use crate::sys::net::{cvt, cvt_gai, cvt_r, init, wrlen_t, Socket};
@@ -122,6 +122,35 @@ fn to_ipv6mr_interface(value: u32) -> libc::c_uint {
     value as libc::c_uint
 }
 
+/// Applies the options of a `TcpBuilder` or `UdpBuilder` to a socket that was
+/// just created for `addr`, before it is bound or connected.
+fn set_socket_options(sock: &Socket, addr: &SocketAddr, opts: &SocketOptions) -> io::Result<()> {
+    fn buffer_size(size: usize) -> c_int {
+        cmp::min(size, c_int::MAX as usize) as c_int
+    }
+
+    if let Some(reuse) = opts.reuse_address {
+        setsockopt(sock, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int)?;
+    }
+    if opts.reuse_port {
+        sock.set_reuse_port(true)?;
+    }
+    if let Some(size) = opts.send_buffer_size {
+        setsockopt(sock, c::SOL_SOCKET, c::SO_SNDBUF, buffer_size(size))?;
+    }
+    if let Some(size) = opts.recv_buffer_size {
+        setsockopt(sock, c::SOL_SOCKET, c::SO_RCVBUF, buffer_size(size))?;
+    }
+    if let (SocketAddr::V6(_), Some(only_v6)) = (addr, opts.only_v6) {
+        setsockopt(sock, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int)?;
+    }
+    if opts.keepalive || opts.keepalive_time.is_some() || opts.keepalive_interval.is_some() {
+        setsockopt(sock, c::SOL_SOCKET, c::SO_KEEPALIVE, 1 as c_int)?;
+        sock.set_keepalive_params(opts.keepalive_time, opts.keepalive_interval)?;
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // get_host_addresses
 ////////////////////////////////////////////////////////////////////////////////
@@ -221,6 +250,27 @@ pub fn connect(addr: io::Result<&SocketAddr>) -> io::Result<TcpStream> {
         Ok(TcpStream { inner: sock })
     }
 
+    pub fn connect_with(
+        addr: io::Result<&SocketAddr>,
+        opts: &SocketOptions,
+    ) -> io::Result<TcpStream> {
+        let addr = addr?;
+
+        init();
+
+        let sock = Socket::new(addr, c::SOCK_STREAM)?;
+        set_socket_options(&sock, addr, opts)?;
+
+        if let Some(ref local) = opts.local_addr {
+            let (addrp, len) = local.into_inner();
+            cvt(unsafe { c::bind(*sock.as_inner(), addrp, len as _) })?;
+        }
+
+        let (addrp, len) = addr.into_inner();
+        cvt_r(|| unsafe { c::connect(*sock.as_inner(), addrp, len) })?;
+        Ok(TcpStream { inner: sock })
+    }
+
     pub fn connect_timeout(addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
         init();
 
@@ -261,6 +311,10 @@ pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
 
+    pub fn read_buf(&self, buf: &mut ReadBuf<'_>) -> io::Result<()> {
+        self.inner.read_buf(buf)
+    }
+
     pub fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
         self.inner.read_vectored(bufs)
     }
@@ -362,6 +416,13 @@ pub struct TcpListener {
 
 impl TcpListener {
     pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
+        TcpListener::bind_with(addr, &SocketOptions::default())
+    }
+
+    pub fn bind_with(
+        addr: io::Result<&SocketAddr>,
+        opts: &SocketOptions,
+    ) -> io::Result<TcpListener> {
         let addr = addr?;
 
         init();
@@ -376,14 +437,18 @@ pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<TcpListener> {
         // which allows “socket hijacking”, so we explicitly don't set it here.
         // https://docs.microsoft.com/en-us/windows/win32/winsock/using-so-reuseaddr-and-so-exclusiveaddruse
         #[cfg(not(windows))]
-        setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, 1 as c_int)?;
+        if opts.reuse_address.is_none() {
+            setsockopt(&sock, c::SOL_SOCKET, c::SO_REUSEADDR, 1 as c_int)?;
+        }
+        set_socket_options(&sock, addr, opts)?;
 
         // Bind our new socket
         let (addrp, len) = addr.into_inner();
         cvt(unsafe { c::bind(*sock.as_inner(), addrp, len as _) })?;
 
         // Start listening
-        cvt(unsafe { c::listen(*sock.as_inner(), 128) })?;
+        let backlog = opts.backlog.map_or(128, |backlog| cmp::min(backlog, c_int::MAX as u32));
+        cvt(unsafe { c::listen(*sock.as_inner(), backlog as c_int) })?;
         Ok(TcpListener { inner: sock })
     }
 
@@ -467,11 +532,16 @@ pub struct UdpSocket {
 
 impl UdpSocket {
     pub fn bind(addr: io::Result<&SocketAddr>) -> io::Result<UdpSocket> {
+        UdpSocket::bind_with(addr, &SocketOptions::default())
+    }
+
+    pub fn bind_with(addr: io::Result<&SocketAddr>, opts: &SocketOptions) -> io::Result<UdpSocket> {
         let addr = addr?;
 
         init();
 
         let sock = Socket::new(addr, c::SOCK_DGRAM)?;
+        set_socket_options(&sock, addr, opts)?;
         let (addrp, len) = addr.into_inner();
         cvt(unsafe { c::bind(*sock.as_inner(), addrp, len as _) })?;
         Ok(UdpSocket { inner: sock })
@@ -260,3 +260,51 @@ fn test_thread_id_not_equal() {
 
 // NOTE: the corresponding test for stderr is in ui/thread-stderr, due
 // to the test harness apparently interfering with stderr configuration.
+
+#[test]
+fn test_scoped_threads_borrow_from_stack() {
+    let mut counts = [0usize; 4];
+    let data = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    thread::scope(|s| {
+        for (count, chunk) in counts.iter_mut().zip(data.chunks(2)) {
+            s.spawn(move || *count = chunk.iter().sum());
+        }
+    });
+    assert_eq!(counts, [3, 7, 11, 15]);
+}
+
+#[test]
+fn test_scoped_threads_join_returns_value_and_panic() {
+    thread::scope(|s| {
+        let value = s.spawn(|| 42);
+        let panicked = s.spawn(|| panic!("oh no"));
+        assert_eq!(value.join().unwrap(), 42);
+        assert!(panicked.join().is_err());
+    });
+}
+
+#[test]
+fn test_scoped_threads_propagate_unjoined_panic() {
+    let result = crate::panic::catch_unwind(|| {
+        thread::scope(|s| {
+            s.spawn(|| panic!("oh no"));
+        })
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_scoped_threads_are_joined_before_scope_returns() {
+    use crate::sync::atomic::{AtomicBool, Ordering};
+
+    let finished = AtomicBool::new(false);
+    thread::scope(|s| {
+        s.spawn(|| {
+            thread::sleep(Duration::from_millis(100));
+            finished.store(true, Ordering::Relaxed);
+        });
+        // Nested threads can be spawned through the scope, too.
+        s.spawn(move || s.spawn(|| thread::sleep(Duration::from_millis(10))));
+    });
+    assert!(finished.load(Ordering::Relaxed));
+}
@@ -2,9 +2,10 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use super::helpers::isatty;
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
+use super::options::{ColorConfig, Options, OutputFormat, RunIgnored, Shard};
 use super::time::TestTimeOptions;
 
 #[derive(Debug)]
@@ -13,6 +14,7 @@ pub struct TestOpts {
     pub filter: Option<String>,
     pub filter_exact: bool,
     pub force_run_in_process: bool,
+    pub isolate: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
     pub run_tests: bool,
@@ -23,7 +25,14 @@ pub struct TestOpts {
     pub format: OutputFormat,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
+    pub shard: Option<Shard>,
     pub time_options: Option<TestTimeOptions>,
+    pub test_timeout: Option<Duration>,
+    pub retries: usize,
+    pub shuffle: bool,
+    pub shuffle_seed: Option<u64>,
+    pub save_baseline: Option<String>,
+    pub baseline: Option<String>,
     pub options: Options,
 }
 
@@ -47,6 +56,14 @@ fn optgroups() -> getopts::Options {
     opts.optflag("", "include-ignored", "Run ignored and not ignored tests")
         .optflag("", "ignored", "Run only ignored tests")
         .optflag("", "force-run-in-process", "Forces tests to run in-process when panic=abort")
+        .optflag(
+            "",
+            "isolate",
+            "Run each test in its own subprocess, so that a test that crashes \
+             or exits the process is reported as a failure. Only static tests \
+             are isolated; dynamic tests, such as doctests and tests from \
+             custom harnesses, still run in-process",
+        )
         .optflag("", "exclude-should-panic", "Excludes tests marked as should_panic")
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
@@ -79,6 +96,20 @@ fn optgroups() -> getopts::Options {
              be used multiple times)",
             "FILTER",
         )
+        .optopt(
+            "",
+            "shard-index",
+            "Only run the tests of shard INDEX (counting from 0) out of the
+            --shard-count shards",
+            "INDEX",
+        )
+        .optopt(
+            "",
+            "shard-count",
+            "Partition the tests into COUNT shards and only run one of them,
+            selected by --shard-index",
+            "COUNT",
+        )
         .optflag(
             "q",
             "quiet",
@@ -101,8 +132,9 @@ fn optgroups() -> getopts::Options {
             "Configure formatting of output:
             pretty = Print verbose output;
             terse  = Display one character per test;
-            json   = Output a json document",
-            "pretty|terse|json",
+            json   = Output a json document
+            junit  = Output a JUnit document",
+            "pretty|terse|json|junit",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -143,6 +175,47 @@ fn optgroups() -> getopts::Options {
 
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
+        )
+        .optopt(
+            "",
+            "test-timeout",
+            "Kill tests that are still running after SECS seconds and
+            report them as failed. Tests can override this value with
+            the `#[test_timeout = \"SECS\"]` attribute.
+
+            Only enforced when tests run in subprocesses (panic=abort or --isolate).",
+            "SECS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failed tests up to N more times. Tests that pass on
+            a later attempt are reported as flaky instead of failed.
+
+            Only static tests are retried; dynamic tests, such as
+            doctests and tests from custom harnesses, run once.",
+            "N",
+        )
+        .optflag("", "shuffle", "Run tests in random order")
+        .optopt(
+            "",
+            "shuffle-seed",
+            "Run tests in random order; seed the random number generator with SEED",
+            "SEED",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the results of this benchmark run under NAME, for later
+            comparison with --baseline",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the results of this benchmark run against the baseline
+            previously saved under NAME",
+            "NAME",
         );
     opts
 }
@@ -163,6 +236,21 @@ fn usage(binary: &str, options: &getopts::Options) {
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
 
+By default, tests are run in alphabetical order. Passing --shuffle (or setting
+RUST_TEST_SHUFFLE to a value other than "0") runs them in a random order
+instead; the seed used is printed when the run starts, and passing it back via
+--shuffle-seed (or RUST_TEST_SHUFFLE_SEED) reproduces the same order.
+
+To split a test suite across several processes or machines, pass
+--shard-count N and a different --shard-index from 0 to N-1 to each of them.
+Tests are assigned to shards by a stable hash of their name, so the shards are
+disjoint and together run every test that would have run without sharding.
+
+When running benchmarks, --save-baseline NAME stores the measured samples next
+to the test binary (in the `bench-baselines` directory of the target directory).
+A later run with --baseline NAME reports the change of each benchmark's median
+relative to that baseline, and whether the change is statistically significant.
+
 Test Attributes:
 
     `#[test]`        - Indicates a function is a test to be run. This function
@@ -229,14 +317,27 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     // Unstable flags
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
+    let isolate = unstable_optflag!(matches, allow_unstable, "isolate");
+    if isolate && force_run_in_process {
+        return Err(
+            "the options --isolate and --force-run-in-process are mutually exclusive".into()
+        );
+    }
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let include_ignored = unstable_optflag!(matches, allow_unstable, "include-ignored");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let test_timeout = get_test_timeout(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let shuffle = get_shuffle(&matches, allow_unstable)?;
+    let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let save_baseline = get_baseline_name(&matches, allow_unstable, "save-baseline")?;
+    let baseline = get_baseline_name(&matches, allow_unstable, "baseline")?;
 
     let quiet = matches.opt_present("quiet");
     let exact = matches.opt_present("exact");
     let list = matches.opt_present("list");
     let skip = matches.opt_strs("skip");
+    let shard = get_shard(&matches, allow_unstable)?;
 
     let bench_benchmarks = matches.opt_present("bench");
     let run_tests = !bench_benchmarks || matches.opt_present("test");
@@ -256,6 +357,7 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         filter,
         filter_exact: exact,
         force_run_in_process,
+        isolate,
         exclude_should_panic,
         run_ignored,
         run_tests,
@@ -266,7 +368,14 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         format,
         test_threads,
         skip,
+        shard,
         time_options,
+        test_timeout,
+        retries,
+        shuffle,
+        shuffle_seed,
+        save_baseline,
+        baseline,
         options,
     };
 
@@ -307,6 +416,172 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_test_timeout(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<Option<Duration>> {
+    let test_timeout = match matches.opt_str("test-timeout") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(0) => return Err("argument for --test-timeout must not be 0".to_string()),
+            Ok(n) => Some(Duration::from_secs(n)),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --test-timeout must be a number > 0 \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    if !allow_unstable && test_timeout.is_some() {
+        return Err("The \"test-timeout\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    Ok(test_timeout)
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    let retries = match matches.opt_str("retries") {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => n,
+            Err(e) => {
+                return Err(format!(
+                    "argument for --retries must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => return Ok(0),
+    };
+
+    if !allow_unstable {
+        return Err("The \"retries\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    Ok(retries)
+}
+
+fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
+    let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
+    if !shuffle && allow_unstable {
+        shuffle = match env::var("RUST_TEST_SHUFFLE") {
+            Ok(val) => &val != "0",
+            Err(_) => false,
+        };
+    }
+
+    Ok(shuffle)
+}
+
+fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<u64>> {
+    let mut shuffle_seed = match matches.opt_str("shuffle-seed") {
+        Some(n_str) => match n_str.parse::<u64>() {
+            Ok(n) => Some(n),
+            Err(e) => {
+                return Err(format!(
+                    "argument for --shuffle-seed must be a number \
+                     (error: {})",
+                    e
+                ));
+            }
+        },
+        None => None,
+    };
+
+    if shuffle_seed.is_none() && allow_unstable {
+        shuffle_seed = match env::var("RUST_TEST_SHUFFLE_SEED") {
+            Ok(val) => match val.parse::<u64>() {
+                Ok(n) => Some(n),
+                Err(e) => {
+                    return Err(format!(
+                        "RUST_TEST_SHUFFLE_SEED must be a number \
+                         (error: {})",
+                        e
+                    ));
+                }
+            },
+            Err(_) => None,
+        };
+    }
+
+    if !allow_unstable && shuffle_seed.is_some() {
+        return Err("The \"shuffle-seed\" flag is only accepted on the nightly compiler with \
+                    -Z unstable-options"
+            .into());
+    }
+
+    Ok(shuffle_seed)
+}
+
+fn get_shard(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<Shard>> {
+    let parse = |option_name: &str| match matches.opt_str(option_name) {
+        Some(n_str) => match n_str.parse::<usize>() {
+            Ok(n) => Ok(Some(n)),
+            Err(e) => {
+                Err(format!("argument for --{} must be a number (error: {})", option_name, e))
+            }
+        },
+        None => Ok(None),
+    };
+
+    let shard = match (parse("shard-index")?, parse("shard-count")?) {
+        (Some(index), Some(count)) => {
+            if index >= count {
+                return Err(format!(
+                    "argument for --shard-index must be less than --shard-count ({})",
+                    count
+                ));
+            }
+            Shard { index, count }
+        }
+        (None, None) => return Ok(None),
+        _ => return Err("the options --shard-index and --shard-count must be used together".into()),
+    };
+
+    if !allow_unstable {
+        return Err("The \"shard-index\" and \"shard-count\" flags are only accepted on the \
+                    nightly compiler with -Z unstable-options"
+            .into());
+    }
+
+    Ok(Some(shard))
+}
+
+fn get_baseline_name(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+    option_name: &str,
+) -> OptPartRes<Option<String>> {
+    let name = match matches.opt_str(option_name) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    if !allow_unstable {
+        return Err(format!(
+            "The \"{}\" flag is only accepted on the nightly compiler with -Z unstable-options",
+            option_name
+        ));
+    }
+
+    let is_valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(|c: char| std::path::is_separator(c) || c.is_control());
+    if !is_valid {
+        return Err(format!("argument for --{} must be a valid file name", option_name));
+    }
+
+    Ok(Some(name))
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -341,10 +616,16 @@ fn get_format(
             }
             OutputFormat::Json
         }
+        Some("junit") => {
+            if !allow_unstable {
+                return Err("The \"junit\" format is only accepted on the nightly compiler".into());
+            }
+            OutputFormat::Junit
+        }
 
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, or json (was \
+                "argument for --format must be pretty, terse, json or junit (was \
                  {})",
                 v
             ));
@@ -6,13 +6,17 @@
 use std::time::Instant;
 
 use super::{
-    bench::fmt_bench_samples,
+    bench::{fmt_bench_samples, BenchSamples},
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
-    helpers::{concurrency::get_concurrency, metrics::MetricMap},
-    options::{Options, OutputFormat},
+    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
+    helpers::{
+        baseline::{Baseline, BaselineChange},
+        concurrency::get_concurrency,
+        metrics::MetricMap,
+    },
+    options::{Options, OutputFormat, Shard},
     run_tests,
     test_result::TestResult,
     time::{TestExecTime, TestSuiteExecTime},
@@ -48,6 +52,7 @@ pub struct ConsoleTestState {
     pub failed: usize,
     pub ignored: usize,
     pub allowed_fail: usize,
+    pub flaky: usize,
     pub filtered_out: usize,
     pub measured: usize,
     pub exec_time: Option<TestSuiteExecTime>,
@@ -55,6 +60,10 @@ pub struct ConsoleTestState {
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flaky_tests: Vec<(TestDesc, Vec<u8>)>,
+    pub baseline: Option<Baseline>,
+    pub bench_samples: Baseline,
+    pub shard: Option<Shard>,
     pub options: Options,
 }
 
@@ -72,6 +81,7 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             failed: 0,
             ignored: 0,
             allowed_fail: 0,
+            flaky: 0,
             filtered_out: 0,
             measured: 0,
             exec_time: None,
@@ -79,6 +89,10 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             failures: Vec::new(),
             not_failures: Vec::new(),
             time_failures: Vec::new(),
+            flaky_tests: Vec::new(),
+            baseline: None,
+            bench_samples: Baseline::new(),
+            shard: opts.shard,
             options: opts.options,
         })
     }
@@ -115,6 +129,8 @@ pub fn write_log_result(
                     TestResult::TrAllowedFail => "failed (allowed)".to_owned(),
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrTimedOut => "failed (timed out)".to_owned(),
+                    TestResult::TrFlaky(failures) => format!("flaky (failed {} times)", failures),
                 },
                 test.name,
             )
@@ -125,8 +141,13 @@ pub fn write_log_result(
         self.write_log(|| "\n")
     }
 
+    /// Compares the result of a benchmark against the baseline passed via `--baseline`.
+    pub fn baseline_change(&self, test: &TestDesc, bs: &BenchSamples) -> Option<BaselineChange> {
+        self.baseline.as_ref()?.compare(test.name.as_slice(), &bs.samples)
+    }
+
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured + self.allowed_fail
+        self.passed + self.failed + self.ignored + self.measured + self.allowed_fail + self.flaky
     }
 }
 
@@ -198,6 +219,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.bench_samples.insert(test.name.as_slice(), bs.samples);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -214,6 +236,16 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrTimedOut => {
+            st.failed += 1;
+            let mut stdout = stdout;
+            stdout.extend_from_slice(b"note: test did not finish in time and was killed");
+            st.failures.push((test, stdout));
+        }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flaky_tests.push((test, stdout));
+        }
     }
 }
 
@@ -225,9 +257,9 @@ fn on_test_event(
     out: &mut dyn OutputFormatter,
 ) -> io::Result<()> {
     match (*event).clone() {
-        TestEvent::TeFiltered(ref filtered_tests) => {
+        TestEvent::TeFiltered(ref filtered_tests, shuffle_seed) => {
             st.total = filtered_tests.len();
-            out.write_run_start(filtered_tests.len())?;
+            out.write_run_start(filtered_tests.len(), shuffle_seed)?;
         }
         TestEvent::TeFilteredOut(filtered_out) => {
             st.filtered_out = filtered_out;
@@ -277,8 +309,12 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
             Box::new(TerseFormatter::new(output, opts.use_color(), max_name_len, is_multithreaded))
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
+        OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
+    if let Some(ref name) = opts.baseline {
+        st.baseline = Some(Baseline::load(name)?);
+    }
 
     // Prevent the usage of `Instant` in some cases:
     // - It's currently not supported for wasm targets.
@@ -291,6 +327,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
 
     assert!(st.current_test_count() == st.total);
 
+    // Don't clobber a saved baseline with the results of a run that didn't
+    // measure anything, e.g. because `--bench` was forgotten.
+    if let Some(ref name) = opts.save_baseline {
+        if !st.bench_samples.is_empty() {
+            st.bench_samples.save(name)?;
+        }
+    }
+
     out.write_run_finish(&st)
 }
 
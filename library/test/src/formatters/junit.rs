@@ -0,0 +1,216 @@
+use std::{io, io::prelude::Write};
+
+use super::OutputFormatter;
+use crate::{
+    console::{ConsoleTestState, OutputLocation},
+    test_result::TestResult,
+    time,
+    types::{TestDesc, TestType},
+};
+
+pub(crate) struct JunitFormatter<T> {
+    out: OutputLocation<T>,
+    results: Vec<(TestDesc, TestResult, Option<time::TestExecTime>, Vec<u8>)>,
+}
+
+impl<T: Write> JunitFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, results: Vec::new() }
+    }
+
+    #[cfg(test)]
+    pub fn output_location(&self) -> &OutputLocation<T> {
+        &self.out
+    }
+
+    fn writeln_message(&mut self, s: &str) -> io::Result<()> {
+        self.out.write_all(s.as_ref())?;
+        self.out.write_all(b"\n")
+    }
+
+    fn write_testcase(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: Option<&str>,
+    ) -> io::Result<()> {
+        let (class_name, test_name) = parse_class_name(desc);
+        let time_attr = match exec_time {
+            Some(exec_time) => format!(r#" time="{}""#, exec_time.0.as_secs_f64()),
+            None => String::new(),
+        };
+        let failure = match *result {
+            TestResult::TrFailed => Some(r#"<failure type="assert"/>"#.to_owned()),
+            TestResult::TrFailedMsg(ref m) => {
+                Some(format!(r#"<failure type="assert" message="{}"/>"#, EscapedString(m)))
+            }
+            TestResult::TrTimedFail => {
+                Some(r#"<failure type="timeout" message="time limit exceeded"/>"#.to_owned())
+            }
+            TestResult::TrTimedOut => {
+                Some(r#"<failure type="timeout" message="timed out"/>"#.to_owned())
+            }
+            TestResult::TrIgnored => Some("<skipped/>".to_owned()),
+            TestResult::TrOk
+            | TestResult::TrAllowedFail
+            | TestResult::TrFlaky(_)
+            | TestResult::TrBench(_) => None,
+        };
+
+        let open_tag = format!(
+            r#"    <testcase classname="{}" name="{}"{}"#,
+            EscapedString(class_name),
+            EscapedString(test_name),
+            time_attr
+        );
+
+        if failure.is_none() && stdout.is_none() {
+            return self.writeln_message(&format!("{}/>", open_tag));
+        }
+
+        self.writeln_message(&format!("{}>", open_tag))?;
+        if let Some(failure) = failure {
+            self.writeln_message(&format!("      {}", failure))?;
+        }
+        if let Some(stdout) = stdout {
+            self.writeln_message(&format!(
+                "      <system-out>{}</system-out>",
+                EscapedString(stdout)
+            ))?;
+        }
+        self.writeln_message("    </testcase>")
+    }
+}
+
+impl<T: Write> OutputFormatter for JunitFormatter<T> {
+    fn write_run_start(
+        &mut self,
+        _test_count: usize,
+        _shuffle_seed: Option<u64>,
+    ) -> io::Result<()> {
+        // We write the xml header on run start, everything else has to wait
+        // until the counts in the `testsuite` element are known.
+        self.writeln_message(r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test start.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // We do not output anything on test timeout.
+        Ok(())
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        _state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        // Because the `testsuite` element holds some of the information as attributes,
+        // we can't write it until all of the tests have run. Instead of writing every
+        // result as it comes in, we buffer them and write them all out at the end.
+        self.results.push((desc.clone(), result.clone(), exec_time.cloned(), stdout.to_vec()));
+        Ok(())
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        self.writeln_message("<testsuites>")?;
+
+        let time_attr = match state.exec_time {
+            Some(ref exec_time) => format!(r#" time="{}""#, exec_time.0.as_secs_f64()),
+            None => String::new(),
+        };
+        self.writeln_message(&format!(
+            "  <testsuite name=\"test\" package=\"test\" id=\"0\" errors=\"0\" \
+             failures=\"{}\" tests=\"{}\" skipped=\"{}\"{}>",
+            state.failed, state.total, state.ignored, time_attr
+        ))?;
+
+        for (desc, result, exec_time, stdout) in std::mem::take(&mut self.results) {
+            let display_stdout = state.options.display_output || result != TestResult::TrOk;
+            let stdout = if display_stdout && !stdout.is_empty() {
+                Some(String::from_utf8_lossy(&stdout).into_owned())
+            } else {
+                None
+            };
+            self.write_testcase(&desc, &result, exec_time.as_ref(), stdout.as_deref())?;
+        }
+
+        self.writeln_message("  </testsuite>")?;
+        self.writeln_message("</testsuites>")?;
+
+        Ok(state.failed == 0)
+    }
+}
+
+/// Splits the name of a test into the `classname` and `name` attributes
+/// of a JUnit `testcase` element.
+fn parse_class_name(desc: &TestDesc) -> (&str, &str) {
+    let name = desc.name.as_slice();
+    match desc.test_type {
+        // Module path => classname
+        // Function name => name
+        TestType::UnitTest => match name.rsplit_once("::") {
+            Some((path, test)) => (path, test),
+            None => ("crate", name),
+        },
+        // File path => classname
+        // Item path and line number => name
+        TestType::DocTest => match name.split_once(" - ") {
+            Some((file, test)) => (file.trim(), test.trim()),
+            None => ("doctest", name),
+        },
+        TestType::IntegrationTest => ("integration", name),
+        TestType::Unknown => ("unknown", name),
+    }
+}
+
+/// A formatting utility used to print strings with characters that have
+/// special meaning in XML attributes and text.
+struct EscapedString<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> std::fmt::Display for EscapedString<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut start = 0;
+
+        for (i, byte) in self.0.as_ref().bytes().enumerate() {
+            let escaped = match byte {
+                b'"' => "&quot;",
+                b'&' => "&amp;",
+                b'\'' => "&apos;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                // Encode whitespace explicitly so that it survives attribute
+                // value normalization.
+                b'\t' => "&#9;",
+                b'\n' => "&#10;",
+                b'\r' => "&#13;",
+                // Other control characters aren't allowed in XML 1.0 at all.
+                b'\x00'..=b'\x1f' => "\u{fffd}",
+                _ => {
+                    continue;
+                }
+            };
+
+            if start < i {
+                f.write_str(&self.0.as_ref()[start..i])?;
+            }
+
+            f.write_str(escaped)?;
+
+            start = i + 1;
+        }
+
+        if start != self.0.as_ref().len() {
+            f.write_str(&self.0.as_ref()[start..])?;
+        }
+
+        Ok(())
+    }
+}
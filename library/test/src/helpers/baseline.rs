@@ -0,0 +1,141 @@
+//! Persistence of benchmark results, for comparison between runs.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, prelude::*};
+use std::path::PathBuf;
+
+use crate::stats::{self, Stats};
+
+const HEADER: &str = "libtest-baseline 1";
+
+/// The samples of every benchmark of a run, keyed by benchmark name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline(BTreeMap<String, Vec<f64>>);
+
+/// The change of a benchmark relative to its baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BaselineChange {
+    /// Change of the median, as a percentage of the baseline median.
+    pub pct: f64,
+    /// Whether the change is significant at the 5% level.
+    pub significant: bool,
+}
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline(BTreeMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn insert(&mut self, name: &str, samples: Vec<f64>) {
+        self.0.insert(name.to_owned(), samples);
+    }
+
+    /// Compares `samples` against the baseline samples of the benchmark `name`,
+    /// or returns `None` if the baseline has no usable samples for it.
+    pub fn compare(&self, name: &str, samples: &[f64]) -> Option<BaselineChange> {
+        let old = self.0.get(name)?;
+        let old_median = old.median();
+        if old_median <= 0.0 || samples.is_empty() {
+            return None;
+        }
+
+        let pct = (samples.median() - old_median) / old_median * 100.0;
+        let significant = stats::mann_whitney_z(old, samples).abs() > 1.96;
+        Some(BaselineChange { pct, significant })
+    }
+
+    /// Loads the baseline saved under `name` for the running test binary.
+    pub fn load(name: &str) -> io::Result<Baseline> {
+        let path = baseline_path(name)?;
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("could not read baseline `{}` from {}: {}", name, path.display(), e),
+            )
+        })?;
+        Baseline::parse(&contents).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("baseline file {} is malformed", path.display()),
+            )
+        })
+    }
+
+    /// Saves the baseline under `name` for the running test binary,
+    /// replacing any baseline previously saved under that name.
+    pub fn save(&self, name: &str) -> io::Result<()> {
+        let path = baseline_path(name)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut out = io::BufWriter::new(fs::File::create(&path)?);
+        self.write_to(&mut out)?;
+        out.flush()
+    }
+
+    pub fn write_to(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", HEADER)?;
+        // Names come last on each line so they may contain spaces; names that
+        // would break the line-based format are not worth supporting.
+        for (name, samples) in self.0.iter().filter(|(name, _)| !name.contains('\n')) {
+            for sample in samples {
+                write!(out, "{} ", sample)?;
+            }
+            writeln!(out, "\t{}", name)?;
+        }
+        Ok(())
+    }
+
+    pub fn parse(contents: &str) -> Option<Baseline> {
+        let mut lines = contents.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+
+        let mut baseline = Baseline::new();
+        for line in lines {
+            let (samples, name) = line.split_once('\t')?;
+            let samples = samples
+                .split_whitespace()
+                .map(|s| s.parse::<f64>().ok())
+                .collect::<Option<Vec<_>>>()?;
+            baseline.insert(name, samples);
+        }
+        Some(baseline)
+    }
+}
+
+/// Baselines are stored per test binary in the `bench-baselines` directory
+/// next to the directory holding it, which for Cargo builds is the profile
+/// directory inside the target directory.
+fn baseline_path(name: &str) -> io::Result<PathBuf> {
+    let exe = env::current_exe()?;
+    let binary = exe.file_stem().unwrap_or_default();
+    let mut dir = exe.parent().map(PathBuf::from).unwrap_or_default();
+    if dir.file_name().map_or(false, |d| d == "deps") {
+        dir.pop();
+    }
+    dir.push("bench-baselines");
+    dir.push(name);
+    dir.push(binary);
+    Ok(dir)
+}
+
+impl std::fmt::Display for BaselineChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = if !self.significant {
+            "no significant change"
+        } else if self.pct > 0.0 {
+            "regressed"
+        } else {
+            "improved"
+        };
+        write!(f, "{:+.2}%, {}", self.pct, verdict)
+    }
+}
@@ -0,0 +1,87 @@
+//! Helper module for the `--retries` option: runs failed tests again and
+//! reports the ones that only passed on a later attempt as flaky.
+
+use std::collections::{hash_map::DefaultHasher, HashMap};
+use std::hash::BuildHasherDefault;
+
+use crate::event::CompletedTest;
+use crate::test_result::TestResult;
+use crate::types::{TestDesc, TestDescAndFn, TestFn};
+
+/// Added to the output of failed tests that `--retries` can't run again.
+const NOT_RETRIED_NOTE: &str = "note: not retried, only static tests can be run more than once\n";
+
+struct RetryState {
+    testfn: fn(),
+    /// How often the test failed so far.
+    failures: usize,
+    /// Output of the last failed run.
+    stdout: Vec<u8>,
+}
+
+/// Keeps track of the tests that may be run again.
+///
+/// Only static tests can be retried, as dynamic test functions can
+/// only be called once.
+pub struct Retries {
+    max_retries: usize,
+    tests: HashMap<TestDesc, RetryState, BuildHasherDefault<DefaultHasher>>,
+}
+
+impl Retries {
+    pub fn new(max_retries: usize, tests: &[TestDescAndFn]) -> Retries {
+        let mut retries = Retries { max_retries, tests: HashMap::default() };
+        if max_retries > 0 {
+            for test in tests {
+                if let TestFn::StaticTestFn(testfn) = test.testfn {
+                    let state = RetryState { testfn, failures: 0, stdout: Vec::new() };
+                    retries.tests.insert(test.desc.clone(), state);
+                }
+            }
+        }
+        retries
+    }
+
+    /// Whether the test is being run again after it failed.
+    pub fn is_retry(&self, desc: &TestDesc) -> bool {
+        self.tests.get(desc).map_or(false, |state| state.failures > 0)
+    }
+
+    /// Handles a finished test: if it failed and may be retried, it is pushed onto
+    /// `remaining` and `None` is returned. Otherwise returns the result to report,
+    /// which is `TrFlaky` for tests that passed after failing before.
+    pub fn on_completed(
+        &mut self,
+        mut completed_test: CompletedTest,
+        remaining: &mut Vec<TestDescAndFn>,
+    ) -> Option<CompletedTest> {
+        let failed = matches!(
+            completed_test.result,
+            TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedOut
+        );
+        let state = match self.tests.get_mut(&completed_test.desc) {
+            Some(state) => state,
+            None => {
+                if failed && self.max_retries > 0 {
+                    completed_test.stdout.extend_from_slice(NOT_RETRIED_NOTE.as_bytes());
+                }
+                return Some(completed_test);
+            }
+        };
+
+        if failed && state.failures < self.max_retries {
+            state.failures += 1;
+            state.stdout = completed_test.stdout;
+            let testfn = TestFn::StaticTestFn(state.testfn);
+            remaining.push(TestDescAndFn { desc: completed_test.desc, testfn });
+            return None;
+        }
+
+        let state = self.tests.remove(&completed_test.desc).unwrap();
+        if completed_test.result == TestResult::TrOk && state.failures > 0 {
+            completed_test.result = TestResult::TrFlaky(state.failures);
+            completed_test.stdout = state.stdout;
+        }
+        Some(completed_test)
+    }
+}
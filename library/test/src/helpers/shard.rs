@@ -0,0 +1,23 @@
+//! Helper module for the `--shard-index`/`--shard-count` options.
+
+use crate::options::Shard;
+use crate::types::TestName;
+
+/// Returns whether the test `name` belongs to `shard`.
+///
+/// Every test belongs to exactly one of the shards, and which one only
+/// depends on its name, so separate processes (possibly on different
+/// machines, built by different compilers) agree on the partition.
+pub fn is_in_shard(name: &TestName, shard: Shard) -> bool {
+    (fnv1a(name.as_slice().as_bytes()) % shard.count as u64) as usize == shard.index
+}
+
+// Unlike `DefaultHasher`, FNV-1a is guaranteed to stay the same forever.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
@@ -35,7 +35,7 @@
 // Public reexports
 pub use self::bench::{black_box, Bencher};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, Shard, ShouldPanic};
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
@@ -63,9 +63,10 @@ pub mod test {
 use std::{
     env, io,
     io::prelude::Write,
+    mem,
     panic::{self, catch_unwind, AssertUnwindSafe, PanicInfo},
     process::{self, Command, Termination},
-    sync::mpsc::{channel, Sender},
+    sync::mpsc::{channel, Receiver, Sender},
     sync::{Arc, Mutex},
     thread,
     time::{Duration, Instant},
@@ -89,6 +90,9 @@ pub mod test {
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
 use helpers::exit_code::get_exit_code;
+use helpers::retry::Retries;
+use helpers::shard::is_in_shard;
+use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::{Concurrent, RunStrategy};
 use test_result::*;
 use time::TestExecTime;
@@ -135,6 +139,10 @@ pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Opt
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=unwind.
 pub fn test_main_static(tests: &[&TestDescAndFn]) {
+    // If we're being run in SpawnedSecondary mode (because of `--isolate`),
+    // run the test here. run_test will then exit the process.
+    run_spawned_secondary_test(tests, false);
+
     let args = env::args().collect::<Vec<_>>();
     let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
     test_main(&args, owned_tests, None)
@@ -151,6 +159,17 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
     // If we're being run in SpawnedSecondary mode, run the test here. run_test
     // will then exit the process.
+    run_spawned_secondary_test(tests, true);
+
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
+}
+
+/// Runs the test named by `SECONDARY_TEST_INVOKER_VAR` and exits the process,
+/// if this process has been spawned by a test runner using
+/// `RunStrategy::SpawnPrimary`. Returns without doing anything otherwise.
+fn run_spawned_secondary_test(tests: &[&TestDescAndFn], panic_abort: bool) {
     if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
         env::remove_var(SECONDARY_TEST_INVOKER_VAR);
         let test = tests
@@ -164,12 +183,8 @@ pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
             StaticTestFn(f) => f,
             _ => panic!("only static tests are supported"),
         };
-        run_test_in_spawned_subprocess(desc, Box::new(testfn));
+        run_test_in_spawned_subprocess(desc, Box::new(testfn), panic_abort);
     }
-
-    let args = env::args().collect::<Vec<_>>();
-    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
 }
 
 /// Clones static values for putting into a dynamic vector, which test_main()
@@ -205,12 +220,17 @@ pub fn run_tests<F>(
 where
     F: FnMut(TestEvent) -> io::Result<()>,
 {
-    use std::collections::{self, HashMap};
+    use std::collections::{self, HashMap, HashSet};
     use std::hash::BuildHasherDefault;
     use std::sync::mpsc::RecvTimeoutError;
     // Use a deterministic hasher
     type TestMap =
         HashMap<TestDesc, Instant, BuildHasherDefault<collections::hash_map::DefaultHasher>>;
+    type TestSet = HashSet<TestDesc, BuildHasherDefault<collections::hash_map::DefaultHasher>>;
+
+    /// Added to the output of dynamic tests that `--isolate` runs in-process.
+    const NOT_ISOLATED_NOTE: &str =
+        "note: not isolated, only static tests can be run in a subprocess\n";
 
     let tests_len = tests.len();
 
@@ -234,7 +254,9 @@ pub fn run_tests<F>(
 
     let filtered_descs = filtered_tests.iter().map(|t| t.desc.clone()).collect();
 
-    let event = TestEvent::TeFiltered(filtered_descs);
+    let shuffle_seed = get_shuffle_seed(opts);
+
+    let event = TestEvent::TeFiltered(filtered_descs, shuffle_seed);
     notify_about_test_event(event)?;
 
     let (filtered_tests, filtered_benchs): (Vec<_>, _) = filtered_tests
@@ -244,16 +266,38 @@ pub fn run_tests<F>(
     let concurrency = opts.test_threads.unwrap_or_else(get_concurrency);
 
     let mut remaining = filtered_tests;
-    remaining.reverse();
+    if let Some(shuffle_seed) = shuffle_seed {
+        shuffle_tests(shuffle_seed, &mut remaining);
+    } else {
+        remaining.reverse();
+    }
+    let mut retries = Retries::new(opts.retries, &remaining);
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
-    let run_strategy = if opts.options.panic_abort && !opts.force_run_in_process {
+    let run_strategy = if (opts.options.panic_abort || opts.isolate) && !opts.force_run_in_process {
         RunStrategy::SpawnPrimary
     } else {
         RunStrategy::InProcess
     };
 
+    // Dynamic tests can't be looked up by name from a subprocess, so
+    // `run_test` falls back to running them in-process for `--isolate`.
+    let not_isolated: TestSet = if opts.isolate && !opts.options.panic_abort {
+        remaining
+            .iter()
+            .filter(|test| matches!(test.testfn, DynTestFn(_)))
+            .map(|test| test.desc.clone())
+            .collect()
+    } else {
+        TestSet::default()
+    };
+    let note_not_isolated = |completed_test: &mut CompletedTest| {
+        if not_isolated.contains(&completed_test.desc) {
+            completed_test.stdout.extend_from_slice(NOT_ISOLATED_NOTE.as_bytes());
+        }
+    };
+
     let mut running_tests: TestMap = HashMap::default();
 
     fn get_timed_out_tests(running_tests: &mut TestMap) -> Vec<TestDesc> {
@@ -278,13 +322,18 @@ fn calc_timeout(running_tests: &TestMap) -> Option<Duration> {
     if concurrency == 1 {
         while !remaining.is_empty() {
             let test = remaining.pop().unwrap();
-            let event = TestEvent::TeWait(test.desc.clone());
-            notify_about_test_event(event)?;
+            if !retries.is_retry(&test.desc) {
+                let event = TestEvent::TeWait(test.desc.clone());
+                notify_about_test_event(event)?;
+            }
             run_test(opts, !opts.run_tests, test, run_strategy, tx.clone(), Concurrent::No);
-            let completed_test = rx.recv().unwrap();
+            let mut completed_test = rx.recv().unwrap();
+            note_not_isolated(&mut completed_test);
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
+            if let Some(completed_test) = retries.on_completed(completed_test, &mut remaining) {
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+            }
         }
     } else {
         while pending > 0 || !remaining.is_empty() {
@@ -293,8 +342,10 @@ fn calc_timeout(running_tests: &TestMap) -> Option<Duration> {
                 let timeout = time::get_default_test_timeout();
                 running_tests.insert(test.desc.clone(), timeout);
 
-                let event = TestEvent::TeWait(test.desc.clone());
-                notify_about_test_event(event)?; //here no pad
+                if !retries.is_retry(&test.desc) {
+                    let event = TestEvent::TeWait(test.desc.clone());
+                    notify_about_test_event(event)?; //here no pad
+                }
                 run_test(opts, !opts.run_tests, test, run_strategy, tx.clone(), Concurrent::Yes);
                 pending += 1;
             }
@@ -323,12 +374,15 @@ fn calc_timeout(running_tests: &TestMap) -> Option<Duration> {
                 }
             }
 
-            let completed_test = res.unwrap();
+            let mut completed_test = res.unwrap();
             running_tests.remove(&completed_test.desc);
-
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
             pending -= 1;
+            note_not_isolated(&mut completed_test);
+
+            if let Some(completed_test) = retries.on_completed(completed_test, &mut remaining) {
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+            }
         }
     }
 
@@ -383,6 +437,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         RunIgnored::No => {}
     }
 
+    // Only keep the tests of our shard
+    if let Some(shard) = opts.shard {
+        filtered.retain(|test| is_in_shard(&test.desc.name, shard));
+    }
+
     // Sort the tests alphabetically
     filtered.sort_by(|t1, t2| t1.desc.name.as_slice().cmp(t2.desc.name.as_slice()));
 
@@ -434,6 +493,7 @@ struct TestRunOpts {
         pub nocapture: bool,
         pub concurrency: Concurrent,
         pub time: Option<time::TestTimeOptions>,
+        pub timeout: Option<Duration>,
     }
 
     fn run_test_inner(
@@ -460,6 +520,7 @@ fn run_test_inner(
                 opts.time.is_some(),
                 monitor_ch,
                 opts.time,
+                opts.timeout,
             ),
         };
 
@@ -475,8 +536,13 @@ fn run_test_inner(
         }
     }
 
-    let test_run_opts =
-        TestRunOpts { strategy, nocapture: opts.nocapture, concurrency, time: opts.time_options };
+    let test_run_opts = TestRunOpts {
+        strategy,
+        nocapture: opts.nocapture,
+        concurrency,
+        time: opts.time_options,
+        timeout: time::get_test_timeout(&desc, opts.test_timeout),
+    };
 
     match testfn {
         DynBenchFn(bencher) => {
@@ -490,8 +556,13 @@ fn run_test_inner(
             crate::bench::benchmark(desc, monitor_ch, opts.nocapture, benchfn);
         }
         DynTestFn(f) => {
-            match strategy {
-                RunStrategy::InProcess => (),
+            // Dynamic tests can't be looked up by name from a subprocess. That's
+            // fine for `--isolate`, which can just run them in-process instead.
+            let test_run_opts = match strategy {
+                RunStrategy::InProcess => test_run_opts,
+                _ if !opts.options.panic_abort => {
+                    TestRunOpts { strategy: RunStrategy::InProcess, ..test_run_opts }
+                }
                 _ => panic!("Cannot run dynamic test fn out-of-process"),
             };
             run_test_inner(
@@ -558,6 +629,7 @@ fn spawn_test_subprocess(
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
+    timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -568,10 +640,19 @@ fn spawn_test_subprocess(
         if nocapture {
             command.stdout(process::Stdio::inherit());
             command.stderr(process::Stdio::inherit());
+        } else {
+            command.stdout(process::Stdio::piped());
+            command.stderr(process::Stdio::piped());
         }
 
         let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let output = match timeout {
+            Some(timeout) => {
+                command.spawn().and_then(|child| wait_with_output_timeout(child, timeout))
+            }
+            None => command.output().map(|output| (output, false)),
+        };
+        let (output, timed_out) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -588,6 +669,10 @@ fn spawn_test_subprocess(
         formatters::write_stderr_delimiter(&mut test_output, &desc.name);
         test_output.extend_from_slice(&stderr);
 
+        if timed_out {
+            return (TrTimedOut, test_output, exec_time);
+        }
+
         let result = match (|| -> Result<TestResult, String> {
             let exit_code = get_exit_code(status)?;
             Ok(get_result_from_exit_code(&desc, exit_code, &time_opts, &exec_time))
@@ -606,7 +691,115 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
-fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Send>) -> ! {
+/// Waits for `child` to exit while collecting its output, like
+/// `Child::wait_with_output`, but kills it once `timeout` has elapsed.
+/// The returned flag tells whether the child had to be killed.
+fn wait_with_output_timeout(
+    mut child: process::Child,
+    timeout: Duration,
+) -> io::Result<(process::Output, bool)> {
+    /// How long to keep draining the pipes after killing the child.
+    const KILL_GRACE: Duration = Duration::from_millis(100);
+
+    struct Reader {
+        buf: Arc<Mutex<Vec<u8>>>,
+        done: Receiver<io::Result<()>>,
+    }
+
+    fn read_in_background<R: io::Read + Send + 'static>(pipe: Option<R>) -> Reader {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let (tx, done) = channel();
+        let shared = buf.clone();
+        thread::spawn(move || {
+            let result = (|| {
+                if let Some(mut pipe) = pipe {
+                    let mut chunk = [0; 4096];
+                    loop {
+                        match pipe.read(&mut chunk) {
+                            Ok(0) => break,
+                            Ok(n) => shared.lock().unwrap().extend_from_slice(&chunk[..n]),
+                            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                            Err(e) => return Err(e),
+                        }
+                    }
+                }
+                Ok(())
+            })();
+            let _ = tx.send(result);
+        });
+        Reader { buf, done }
+    }
+
+    // A grandchild that inherited the pipes can keep them open after a killed
+    // child is gone, so don't wait for EOF past `until` if there is one.
+    // Whatever has been read by then is returned and the reader is left to
+    // finish on its own.
+    fn collect(reader: Reader, until: Option<Instant>) -> io::Result<Vec<u8>> {
+        let result = match until {
+            Some(until) => {
+                reader.done.recv_timeout(until.saturating_duration_since(Instant::now())).ok()
+            }
+            None => reader.done.recv().ok(),
+        };
+        if let Some(result) = result {
+            result?;
+        }
+        Ok(mem::take(&mut *reader.buf.lock().unwrap()))
+    }
+
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    // A timeout too large to represent is as good as none.
+    let deadline = Instant::now().checked_add(timeout);
+    let (status, timed_out) = loop {
+        if let Some(status) = child.try_wait()? {
+            break (status, false);
+        }
+        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            // The child may have exited in the meantime, in which case
+            // there's nothing left to kill.
+            let _ = child.kill();
+            break (child.wait()?, true);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    // Only cap the drain for a killed child; one that exited on its own gets
+    // all of its output.
+    let until = if timed_out { Some(Instant::now() + KILL_GRACE) } else { None };
+    let stdout = collect(stdout, until)?;
+    let stderr = collect(stderr, until)?;
+    Ok((process::Output { status, stdout, stderr }, timed_out))
+}
+
+fn run_test_in_spawned_subprocess(
+    desc: TestDesc,
+    testfn: Box<dyn FnOnce() + Send>,
+    panic_abort: bool,
+) -> ! {
+    fn exit_with(test_result: &TestResult) -> ! {
+        if let TrOk = test_result {
+            process::exit(test_result::TR_OK);
+        } else {
+            process::exit(test_result::TR_FAILED);
+        }
+    }
+
+    if !panic_abort {
+        // Panics that the test catches itself must not end the process, so
+        // only the outcome of the test function as a whole is recorded, just
+        // like `run_test_in_process` does.
+        let test_result = match catch_unwind(AssertUnwindSafe(testfn)) {
+            Ok(()) => calc_result(&desc, Ok(()), &None, &None),
+            Err(e) => calc_result(&desc, Err(e.as_ref()), &None, &None),
+        };
+        if let TrFailedMsg(msg) = &test_result {
+            eprintln!("{}", msg);
+        }
+        exit_with(&test_result);
+    }
+
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
         let test_result = match panic_info {
@@ -624,15 +817,11 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, testfn: Box<dyn FnOnce() + Sen
             builtin_panic_hook(info);
         }
 
-        if let TrOk = test_result {
-            process::exit(test_result::TR_OK);
-        } else {
-            process::exit(test_result::TR_FAILED);
-        }
+        exit_with(&test_result);
     });
     let record_result2 = record_result.clone();
     panic::set_hook(Box::new(move |info| record_result2(Some(&info))));
     testfn();
     record_result(None);
-    unreachable!("panic=abort callback should have exited the process")
+    unreachable!("panic hook should have exited the process")
 }
@@ -301,3 +301,47 @@ pub fn winsorize(samples: &mut [f64], pct: f64) {
         }
     }
 }
+
+/// Mann-Whitney U test: a non-parametric test of whether the samples in `a` and `b` were drawn
+/// from the same distribution. Returns the standardized `z` score of the `U` statistic of `b`
+/// (using the normal approximation with a correction for ties), which is positive if the values
+/// in `b` tend to be larger than those in `a`.
+///
+/// At the usual 5% significance level, a difference is significant if `z.abs() > 1.96`.
+///
+/// See: <https://en.wikipedia.org/wiki/Mann%E2%80%93Whitney_U_test>
+pub fn mann_whitney_z(a: &[f64], b: &[f64]) -> f64 {
+    assert!(!a.is_empty() && !b.is_empty());
+    let n1 = a.len() as f64;
+    let n2 = b.len() as f64;
+    let n = n1 + n2;
+
+    let mut all: Vec<(f64, bool)> =
+        a.iter().map(|&x| (x, false)).chain(b.iter().map(|&x| (x, true))).collect();
+    all.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Tied values all get the average of the ranks they span.
+    let mut rank_sum_b = 0.0;
+    let mut tie_correction = 0.0;
+    let mut i = 0;
+    while i < all.len() {
+        let mut j = i + 1;
+        while j < all.len() && all[j].0 == all[i].0 {
+            j += 1;
+        }
+        let ties = (j - i) as f64;
+        let rank = (i + j + 1) as f64 / 2.0;
+        rank_sum_b += rank * all[i..j].iter().filter(|x| x.1).count() as f64;
+        tie_correction += ties * ties * ties - ties;
+        i = j;
+    }
+
+    let u = rank_sum_b - n2 * (n2 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let var = n1 * n2 / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+    if var <= 0.0 {
+        // Every sample has the same value.
+        return 0.0;
+    }
+    (u - mean) / var.sqrt()
+}
@@ -570,6 +570,18 @@ fn test_sum_f64s() {
 fn test_sum_f64_between_ints_that_sum_to_0() {
     assert_eq!([1e30f64, 1.2f64, -1e30f64].sum(), 1.2);
 }
+#[test]
+fn test_mann_whitney_z() {
+    let lo = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let hi = [6.0, 7.0, 8.0, 9.0, 10.0];
+    assert_approx_eq!(mann_whitney_z(&lo, &hi), 2.6111648);
+    assert_approx_eq!(mann_whitney_z(&hi, &lo), -2.6111648);
+
+    // Ties are ranked together and shrink the variance.
+    let tied = [1.0, 1.0, 2.0, 2.0];
+    assert_approx_eq!(mann_whitney_z(&tied, &[1.0, 2.0, 2.0, 3.0]), 0.9486833);
+    assert_eq!(mann_whitney_z(&[4.0, 4.0], &[4.0, 4.0, 4.0]), 0.0);
+}
 
 #[bench]
 pub fn sum_three_items(b: &mut Bencher) {
@@ -22,6 +22,9 @@ pub enum TestResult {
     TrAllowedFail,
     TrBench(BenchSamples),
     TrTimedFail,
+    TrTimedOut,
+    /// The test passed after failing the given number of times.
+    TrFlaky(usize),
 }
 
 unsafe impl Send for TestResult {}
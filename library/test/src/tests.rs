@@ -3,8 +3,9 @@
 use crate::{
     bench::Bencher,
     console::OutputLocation,
-    formatters::PrettyFormatter,
-    options::OutputFormat,
+    formatters::{JunitFormatter, OutputFormatter, PrettyFormatter},
+    helpers::baseline::Baseline,
+    options::{OutputFormat, Shard},
     test::{
         filter_tests,
         parse_opts,
@@ -38,6 +39,7 @@ fn new() -> TestOpts {
             filter: None,
             filter_exact: false,
             force_run_in_process: false,
+            isolate: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
             run_tests: false,
@@ -48,7 +50,14 @@ fn new() -> TestOpts {
             format: OutputFormat::Pretty,
             test_threads: None,
             skip: vec![],
+            shard: None,
             time_options: None,
+            test_timeout: None,
+            retries: 0,
+            shuffle: false,
+            shuffle_seed: None,
+            save_baseline: None,
+            baseline: None,
             options: Options::new(),
         }
     }
@@ -57,23 +66,11 @@ fn new() -> TestOpts {
 fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
     vec![
         TestDescAndFn {
-            desc: TestDesc {
-                name: StaticTestName("1"),
-                ignore: true,
-                should_panic: ShouldPanic::No,
-                allow_fail: false,
-                test_type: TestType::Unknown,
-            },
+            desc: TestDesc { ignore: true, ..test_desc(StaticTestName("1")) },
             testfn: DynTestFn(Box::new(move || {})),
         },
         TestDescAndFn {
-            desc: TestDesc {
-                name: StaticTestName("2"),
-                ignore: false,
-                should_panic: ShouldPanic::No,
-                allow_fail: false,
-                test_type: TestType::Unknown,
-            },
+            desc: test_desc(StaticTestName("2")),
             testfn: DynTestFn(Box::new(move || {})),
         },
     ]
@@ -85,13 +82,7 @@ fn f() {
         panic!();
     }
     let desc = TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: true,
-            should_panic: ShouldPanic::No,
-            allow_fail: false,
-            test_type: TestType::Unknown,
-        },
+        desc: TestDesc { ignore: true, ..test_desc(StaticTestName("whatever")) },
         testfn: DynTestFn(Box::new(f)),
     };
     let (tx, rx) = channel();
@@ -104,13 +95,7 @@ fn f() {
 pub fn ignored_tests_result_in_ignored() {
     fn f() {}
     let desc = TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: true,
-            should_panic: ShouldPanic::No,
-            allow_fail: false,
-            test_type: TestType::Unknown,
-        },
+        desc: TestDesc { ignore: true, ..test_desc(StaticTestName("whatever")) },
         testfn: DynTestFn(Box::new(f)),
     };
     let (tx, rx) = channel();
@@ -127,13 +112,7 @@ fn f() {
         panic!();
     }
     let desc = TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
-            should_panic: ShouldPanic::Yes,
-            allow_fail: false,
-            test_type: TestType::Unknown,
-        },
+        desc: TestDesc { should_panic: ShouldPanic::Yes, ..test_desc(StaticTestName("whatever")) },
         testfn: DynTestFn(Box::new(f)),
     };
     let (tx, rx) = channel();
@@ -151,11 +130,8 @@ fn f() {
     }
     let desc = TestDescAndFn {
         desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
             should_panic: ShouldPanic::YesWithMessage("error message"),
-            allow_fail: false,
-            test_type: TestType::Unknown,
+            ..test_desc(StaticTestName("whatever"))
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -179,11 +155,8 @@ fn f() {
  expected substring: `"foobar"`"#;
     let desc = TestDescAndFn {
         desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
             should_panic: ShouldPanic::YesWithMessage(expected),
-            allow_fail: false,
-            test_type: TestType::Unknown,
+            ..test_desc(StaticTestName("whatever"))
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -210,11 +183,8 @@ fn f() {
     );
     let desc = TestDescAndFn {
         desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
             should_panic: ShouldPanic::YesWithMessage(expected),
-            allow_fail: false,
-            test_type: TestType::Unknown,
+            ..test_desc(StaticTestName("whatever"))
         },
         testfn: DynTestFn(Box::new(f)),
     };
@@ -230,13 +200,7 @@ fn f() {
 fn test_should_panic_but_succeeds() {
     fn f() {}
     let desc = TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
-            should_panic: ShouldPanic::Yes,
-            allow_fail: false,
-            test_type: TestType::Unknown,
-        },
+        desc: TestDesc { should_panic: ShouldPanic::Yes, ..test_desc(StaticTestName("whatever")) },
         testfn: DynTestFn(Box::new(f)),
     };
     let (tx, rx) = channel();
@@ -248,13 +212,7 @@ fn f() {}
 fn report_time_test_template(report_time: bool) -> Option<TestExecTime> {
     fn f() {}
     let desc = TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
-            should_panic: ShouldPanic::No,
-            allow_fail: false,
-            test_type: TestType::Unknown,
-        },
+        desc: test_desc(StaticTestName("whatever")),
         testfn: DynTestFn(Box::new(f)),
     };
     let time_options = if report_time { Some(TestTimeOptions::default()) } else { None };
@@ -280,16 +238,7 @@ fn test_should_report_time() {
 
 fn time_test_failure_template(test_type: TestType) -> TestResult {
     fn f() {}
-    let desc = TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("whatever"),
-            ignore: false,
-            should_panic: ShouldPanic::No,
-            allow_fail: false,
-            test_type,
-        },
-        testfn: DynTestFn(Box::new(f)),
-    };
+    let desc = TestDescAndFn { desc: typed_test_desc(test_type), testfn: DynTestFn(Box::new(f)) };
     // `Default` will initialize all the thresholds to 0 milliseconds.
     let mut time_options = TestTimeOptions::default();
     time_options.error_on_excess = true;
@@ -317,16 +266,24 @@ fn test_error_on_exceed() {
     assert_eq!(result, TestResult::TrOk);
 }
 
-fn typed_test_desc(test_type: TestType) -> TestDesc {
+/// Describes a test called `name`, with the defaults for everything else, so
+/// that fixtures only need to spell out what they care about.
+fn test_desc(name: TestName) -> TestDesc {
     TestDesc {
-        name: StaticTestName("whatever"),
+        name,
         ignore: false,
         should_panic: ShouldPanic::No,
         allow_fail: false,
-        test_type,
+        #[cfg(not(bootstrap))]
+        timeout: None,
+        test_type: TestType::Unknown,
     }
 }
 
+fn typed_test_desc(test_type: TestType) -> TestDesc {
+    TestDesc { test_type, ..test_desc(StaticTestName("whatever")) }
+}
+
 fn test_exec_time(millis: u64) -> TestExecTime {
     TestExecTime(Duration::from_millis(millis))
 }
@@ -392,6 +349,181 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_test_timeout_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "30".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.test_timeout, Some(Duration::from_secs(30)));
+
+    let args = vec!["progname".to_string(), "--test-timeout".to_string(), "30".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--test-timeout".to_string(),
+        "0".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+#[cfg(not(bootstrap))]
+fn test_timeout_attribute_overrides_cli() {
+    let cli_timeout = Some(Duration::from_secs(30));
+
+    let desc = typed_test_desc(TestType::UnitTest);
+    assert_eq!(time::get_test_timeout(&desc, cli_timeout), cli_timeout);
+    assert_eq!(time::get_test_timeout(&desc, None), None);
+
+    let desc = TestDesc { timeout: Some(5), ..typed_test_desc(TestType::UnitTest) };
+    assert_eq!(time::get_test_timeout(&desc, cli_timeout), Some(Duration::from_secs(5)));
+}
+
+#[test]
+#[cfg(unix)]
+fn timeout_does_not_wait_for_inherited_pipes() {
+    // The backgrounded `sleep` keeps stdout open after the shell is killed.
+    let child = Command::new("sh")
+        .args(&["-c", "echo out; sleep 10 & sleep 10"])
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let start = Instant::now();
+    let (output, timed_out) = wait_with_output_timeout(child, Duration::from_millis(200)).unwrap();
+    assert!(timed_out);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(output.stdout, b"out\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn exited_child_output_is_not_truncated() {
+    // Exiting on its own must not cut the output short, however close to the
+    // deadline, and an unrepresentable timeout means none at all.
+    let child = Command::new("sh")
+        .args(&["-c", "head -c 100000 /dev/zero"])
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let (output, timed_out) =
+        wait_with_output_timeout(child, Duration::from_secs(u64::MAX)).unwrap();
+    assert!(!timed_out);
+    assert_eq!(output.stdout.len(), 100000);
+}
+
+#[test]
+fn isolate_runs_dynamic_tests_in_process() {
+    let desc = TestDesc { name: StaticTestName("dynamic"), ..typed_test_desc(TestType::UnitTest) };
+    let tests = vec![TestDescAndFn { desc, testfn: DynTestFn(Box::new(|| panic!())) }];
+    let opts =
+        TestOpts { run_tests: true, isolate: true, test_threads: Some(1), ..TestOpts::new() };
+
+    let mut results = Vec::new();
+    run_tests(&opts, tests, |event| {
+        if let TestEvent::TeResult(completed_test) = event {
+            results.push((completed_test.result, completed_test.stdout));
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(results.len(), 1);
+    let (result, stdout) = &results[0];
+    assert_eq!(*result, TrFailed);
+    // Running it in-process instead of in a subprocess is pointed out in its output.
+    assert!(String::from_utf8_lossy(stdout).contains("not isolated"));
+}
+
+#[test]
+fn retries_report_flaky_tests() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+    static BROKEN_RUNS: AtomicUsize = AtomicUsize::new(0);
+    fn flaky() {
+        assert!(FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) > 0);
+    }
+    fn broken() {
+        BROKEN_RUNS.fetch_add(1, Ordering::SeqCst);
+        panic!();
+    }
+    fn desc(name: &'static str) -> TestDesc {
+        TestDesc { name: StaticTestName(name), ..typed_test_desc(TestType::UnitTest) }
+    }
+
+    let tests = vec![
+        TestDescAndFn { desc: desc("broken"), testfn: StaticTestFn(broken) },
+        TestDescAndFn { desc: desc("dynamic"), testfn: DynTestFn(Box::new(|| panic!())) },
+        TestDescAndFn { desc: desc("flaky"), testfn: StaticTestFn(flaky) },
+    ];
+    let opts = TestOpts { run_tests: true, retries: 2, test_threads: Some(1), ..TestOpts::new() };
+
+    let mut results = Vec::new();
+    let mut dynamic_stdout = Vec::new();
+    run_tests(&opts, tests, |event| {
+        if let TestEvent::TeResult(completed_test) = event {
+            if completed_test.desc.name.as_slice() == "dynamic" {
+                dynamic_stdout = completed_test.stdout.clone();
+            }
+            results.push((completed_test.desc.name.to_string(), completed_test.result));
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            ("broken".to_string(), TrFailed),
+            ("dynamic".to_string(), TrFailed),
+            ("flaky".to_string(), TrFlaky(1)),
+        ]
+    );
+    assert_eq!(BROKEN_RUNS.load(Ordering::SeqCst), 3);
+    assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 2);
+    // The dynamic test can't be run again, which is pointed out in its output.
+    assert!(String::from_utf8_lossy(&dynamic_stdout).contains("not retried"));
+}
+
+#[test]
+fn parse_retries_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--retries".to_string(),
+        "3".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 3);
+
+    let args = vec!["progname".to_string(), "--retries".to_string(), "3".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_isolate_flag() {
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--isolate".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.isolate);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate".to_string(),
+        "--force-run-in-process".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -434,13 +566,7 @@ pub fn exclude_should_panic_option() {
 
     let mut tests = one_ignored_one_unignored_test();
     tests.push(TestDescAndFn {
-        desc: TestDesc {
-            name: StaticTestName("3"),
-            ignore: false,
-            should_panic: ShouldPanic::Yes,
-            allow_fail: false,
-            test_type: TestType::Unknown,
-        },
+        desc: TestDesc { should_panic: ShouldPanic::Yes, ..test_desc(StaticTestName("3")) },
         testfn: DynTestFn(Box::new(move || {})),
     });
 
@@ -456,13 +582,7 @@ fn tests() -> Vec<TestDescAndFn> {
         vec!["base", "base::test", "base::test1", "base::test2"]
             .into_iter()
             .map(|name| TestDescAndFn {
-                desc: TestDesc {
-                    name: StaticTestName(name),
-                    ignore: false,
-                    should_panic: ShouldPanic::No,
-                    allow_fail: false,
-                    test_type: TestType::Unknown,
-                },
+                desc: test_desc(StaticTestName(name)),
                 testfn: DynTestFn(Box::new(move || {})),
             })
             .collect()
@@ -508,6 +628,56 @@ fn tests() -> Vec<TestDescAndFn> {
     assert_eq!(exact.len(), 1);
 }
 
+#[test]
+pub fn shards_partition_tests() {
+    fn tests() -> Vec<TestDescAndFn> {
+        (0..100)
+            .map(|i| TestDescAndFn {
+                desc: test_desc(DynTestName(format!("mod{}::test{}", i % 7, i))),
+                testfn: DynTestFn(Box::new(move || {})),
+            })
+            .collect()
+    }
+    let names = |tests: Vec<TestDescAndFn>| -> Vec<String> {
+        tests.into_iter().map(|test| test.desc.name.as_slice().to_owned()).collect()
+    };
+
+    let mut all = Vec::new();
+    for index in 0..3 {
+        let opts = TestOpts { shard: Some(Shard { index, count: 3 }), ..TestOpts::new() };
+        let shard = names(filter_tests(&opts, tests()));
+        assert!(!shard.is_empty());
+        assert_eq!(shard, names(filter_tests(&opts, tests())));
+        all.extend(shard);
+    }
+    all.sort();
+    assert_eq!(all, names(filter_tests(&TestOpts::new(), tests())));
+}
+
+#[test]
+fn parse_shard_flags() {
+    let args = |extra: &[&str]| -> Vec<String> {
+        let mut args = vec!["progname".to_string(), "-Zunstable-options".to_string()];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        args
+    };
+
+    let opts = parse_opts(&args(&["--shard-index", "1", "--shard-count", "4"])).unwrap().unwrap();
+    assert_eq!(opts.shard, Some(Shard { index: 1, count: 4 }));
+
+    assert!(parse_opts(&args(&["--shard-index", "4", "--shard-count", "4"])).unwrap().is_err());
+    assert!(parse_opts(&args(&["--shard-count", "4"])).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "--shard-index".to_string(),
+        "0".to_string(),
+        "--shard-count".to_string(),
+        "2".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
 #[test]
 pub fn sort_tests() {
     let mut opts = TestOpts::new();
@@ -531,13 +701,7 @@ fn testfn() {}
         let mut tests = Vec::new();
         for name in &names {
             let test = TestDescAndFn {
-                desc: TestDesc {
-                    name: DynTestName((*name).clone()),
-                    ignore: false,
-                    should_panic: ShouldPanic::No,
-                    allow_fail: false,
-                    test_type: TestType::Unknown,
-                },
+                desc: test_desc(DynTestName((*name).clone())),
                 testfn: DynTestFn(Box::new(testfn)),
             };
             tests.push(test);
@@ -565,6 +729,103 @@ fn testfn() {}
     }
 }
 
+#[test]
+pub fn shuffle_tests_is_deterministic() {
+    let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+    let tests = || -> Vec<TestDescAndFn> {
+        names
+            .iter()
+            .map(|name| TestDescAndFn {
+                desc: test_desc(StaticTestName(name)),
+                testfn: DynTestFn(Box::new(move || {})),
+            })
+            .collect()
+    };
+    let order = |seed| {
+        let mut tests = tests();
+        helpers::shuffle::shuffle_tests(seed, &mut tests);
+        tests.into_iter().map(|t| t.desc.name.to_string()).collect::<Vec<_>>()
+    };
+
+    let first = order(42);
+    assert_eq!(first, order(42));
+
+    let mut sorted = first.clone();
+    sorted.sort();
+    assert_eq!(sorted, names);
+
+    assert!((0..10).map(order).any(|o| o != first));
+}
+
+#[test]
+fn parse_shuffle_seed_flag() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shuffle-seed".to_string(),
+        "123".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shuffle_seed, Some(123));
+
+    let args = vec!["progname".to_string(), "--shuffle-seed".to_string(), "123".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_baseline_flags() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--bench".to_string(),
+        "--baseline".to_string(),
+        "main".to_string(),
+        "--save-baseline".to_string(),
+        "feature".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.baseline.as_deref(), Some("main"));
+    assert_eq!(opts.save_baseline.as_deref(), Some("feature"));
+
+    let args = vec!["progname".to_string(), "--baseline".to_string(), "main".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    for name in &["", "..", "a/b"] {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            "--save-baseline".to_string(),
+            name.to_string(),
+        ];
+        assert!(parse_opts(&args).unwrap().is_err());
+    }
+}
+
+#[test]
+fn baseline_roundtrip_and_compare() {
+    let mut baseline = Baseline::new();
+    baseline.insert("bench a", vec![10.0, 11.0, 12.0, 10.5, 11.5]);
+    baseline.insert("b::zero", vec![0.0]);
+
+    let mut saved = Vec::new();
+    baseline.write_to(&mut saved).unwrap();
+    let saved = String::from_utf8(saved).unwrap();
+    assert_eq!(Baseline::parse(&saved), Some(baseline.clone()));
+    assert_eq!(Baseline::parse("10 \tno header"), None);
+
+    let slower = baseline.compare("bench a", &[20.0, 22.0, 24.0, 21.0, 23.0]).unwrap();
+    assert_eq!(slower.pct, 100.0);
+    assert!(slower.significant);
+    assert_eq!(slower.to_string(), "+100.00%, regressed");
+
+    let same = baseline.compare("bench a", &[11.0, 10.0, 12.0, 11.5, 10.5]).unwrap();
+    assert!(!same.significant);
+    assert_eq!(same.to_string(), "+0.00%, no significant change");
+
+    assert_eq!(baseline.compare("b::zero", &[1.0]), None);
+    assert_eq!(baseline.compare("missing", &[1.0]), None);
+}
+
 #[test]
 pub fn test_metricmap_compare() {
     let mut m1 = MetricMap::new();
@@ -608,13 +869,7 @@ fn f(_: &mut Bencher) {}
 
     let (tx, rx) = channel();
 
-    let desc = TestDesc {
-        name: StaticTestName("f"),
-        ignore: false,
-        should_panic: ShouldPanic::No,
-        allow_fail: false,
-        test_type: TestType::Unknown,
-    };
+    let desc = test_desc(StaticTestName("f"));
 
     crate::bench::benchmark(desc, tx, true, f);
     rx.recv().unwrap();
@@ -628,13 +883,7 @@ fn f(b: &mut Bencher) {
 
     let (tx, rx) = channel();
 
-    let desc = TestDesc {
-        name: StaticTestName("f"),
-        ignore: false,
-        should_panic: ShouldPanic::No,
-        allow_fail: false,
-        test_type: TestType::Unknown,
-    };
+    let desc = test_desc(StaticTestName("f"));
 
     crate::bench::benchmark(desc, tx, true, f);
     rx.recv().unwrap();
@@ -642,21 +891,9 @@ fn f(b: &mut Bencher) {
 
 #[test]
 fn should_sort_failures_before_printing_them() {
-    let test_a = TestDesc {
-        name: StaticTestName("a"),
-        ignore: false,
-        should_panic: ShouldPanic::No,
-        allow_fail: false,
-        test_type: TestType::Unknown,
-    };
+    let test_a = test_desc(StaticTestName("a"));
 
-    let test_b = TestDesc {
-        name: StaticTestName("b"),
-        ignore: false,
-        should_panic: ShouldPanic::No,
-        allow_fail: false,
-        test_type: TestType::Unknown,
-    };
+    let test_b = test_desc(StaticTestName("b"));
 
     let mut out = PrettyFormatter::new(OutputLocation::Raw(Vec::new()), false, 10, false, None);
 
@@ -667,6 +904,7 @@ fn should_sort_failures_before_printing_them() {
         failed: 0,
         ignored: 0,
         allowed_fail: 0,
+        flaky: 0,
         filtered_out: 0,
         measured: 0,
         exec_time: None,
@@ -675,6 +913,10 @@ fn should_sort_failures_before_printing_them() {
         options: Options::new(),
         not_failures: Vec::new(),
         time_failures: Vec::new(),
+        flaky_tests: Vec::new(),
+        baseline: None,
+        bench_samples: Baseline::new(),
+        shard: None,
     };
 
     out.write_failures(&st).unwrap();
@@ -687,3 +929,60 @@ fn should_sort_failures_before_printing_them() {
     let bpos = s.find("b").unwrap();
     assert!(apos < bpos);
 }
+
+#[test]
+fn junit_formatter_escapes_and_reports_failures() {
+    let test_ok =
+        TestDesc { test_type: TestType::UnitTest, ..test_desc(StaticTestName("module::passes")) };
+
+    let test_failed =
+        TestDesc { test_type: TestType::UnitTest, ..test_desc(StaticTestName("module::fails")) };
+
+    let st = console::ConsoleTestState {
+        log_out: None,
+        total: 2,
+        passed: 1,
+        failed: 1,
+        ignored: 0,
+        allowed_fail: 0,
+        flaky: 0,
+        filtered_out: 0,
+        measured: 0,
+        exec_time: None,
+        metrics: MetricMap::new(),
+        failures: Vec::new(),
+        options: Options::new(),
+        not_failures: Vec::new(),
+        time_failures: Vec::new(),
+        flaky_tests: Vec::new(),
+        baseline: None,
+        bench_samples: Baseline::new(),
+        shard: None,
+    };
+
+    let mut out = JunitFormatter::new(OutputLocation::Raw(Vec::new()));
+    out.write_run_start(2, None).unwrap();
+    out.write_result(&test_ok, &TrOk, None, b"ignored output", &st).unwrap();
+    out.write_result(
+        &test_failed,
+        &TestResult::TrFailedMsg("expected <a> & \"b\"".to_string()),
+        Some(&TestExecTime(Duration::from_millis(1500))),
+        b"line 1\nline 2",
+        &st,
+    )
+    .unwrap();
+    assert!(!out.write_run_finish(&st).unwrap());
+
+    let s = match out.output_location() {
+        &OutputLocation::Raw(ref m) => String::from_utf8_lossy(&m[..]).into_owned(),
+        &OutputLocation::Pretty(_) => unreachable!(),
+    };
+
+    assert!(s.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(s.contains(r#"failures="1" tests="2" skipped="0">"#));
+    assert!(s.contains(r#"<testcase classname="module" name="passes"/>"#));
+    assert!(s.contains(r#"<testcase classname="module" name="fails" time="1.5">"#));
+    assert!(s.contains(r#"message="expected &lt;a&gt; &amp; &quot;b&quot;""#));
+    assert!(s.contains("<system-out>line 1&#10;line 2</system-out>"));
+    assert!(!s.contains("ignored output"));
+}
@@ -61,6 +61,20 @@ pub fn get_default_test_timeout() -> Instant {
     Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S)
 }
 
+/// Returns the time after which a test running in a subprocess is killed,
+/// preferring the limit set on the test itself over the command line one.
+pub fn get_test_timeout(desc: &TestDesc, default: Option<Duration>) -> Option<Duration> {
+    #[cfg(not(bootstrap))]
+    let timeout = desc.timeout.map(Duration::from_secs);
+    #[cfg(bootstrap)]
+    let timeout = {
+        let _ = desc;
+        None
+    };
+
+    timeout.or(default)
+}
+
 /// The measured execution time of a unit test.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestExecTime(pub Duration);
@@ -120,6 +120,10 @@ pub struct TestDesc {
     pub ignore: bool,
     pub should_panic: options::ShouldPanic,
     pub allow_fail: bool,
+    /// Number of seconds after which the test is killed, set through
+    /// `#[test_timeout = "..."]`. Takes precedence over `--test-timeout`.
+    #[cfg(not(bootstrap))]
+    pub timeout: Option<u64>,
     pub test_type: TestType,
 }
 
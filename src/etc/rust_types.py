@@ -33,7 +33,7 @@ class RustType(object):
     STD_REF_CELL = "StdRefCell"
 
 
-STD_STRING_REGEX = re.compile(r"^(alloc::(\w+::)+)String$")
+STD_STRING_REGEX = re.compile(r"^(alloc::(\w+::)+)String(<.+>)?$")
 STD_STR_REGEX = re.compile(r"^&str$")
 STD_SLICE_REGEX = re.compile(r"^&\[.+\]$")
 STD_OS_STRING_REGEX = re.compile(r"^(std::ffi::(\w+::)+)OsString$")
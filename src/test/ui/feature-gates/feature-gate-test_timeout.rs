@@ -0,0 +1,6 @@
+// check that #[test_timeout] is feature-gated
+
+#[test_timeout = "10"] //~ ERROR the `#[test_timeout]` attribute is an experimental feature
+fn times_out() {}
+
+fn main() {}
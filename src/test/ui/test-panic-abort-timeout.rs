@@ -0,0 +1,35 @@
+// no-prefer-dynamic
+// compile-flags: --test -Cpanic=abort -Zpanic_abort_tests
+// run-flags: --test-threads=1 -Zunstable-options --test-timeout=1
+// run-fail
+// check-run-results
+// exec-env:RUST_BACKTRACE=0
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+// ignore-wasm no panic or subprocess support
+// ignore-emscripten no panic or subprocess support
+// ignore-sgx no subprocess support
+
+#![cfg(test)]
+#![feature(test_timeout)]
+
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn it_hangs() {
+    loop {
+        thread::park();
+    }
+}
+
+#[test]
+#[test_timeout = "60"]
+fn it_is_slow_but_allowed_to_be() {
+    thread::sleep(Duration::from_secs(2));
+}
+
+#[test]
+fn it_works() {
+    assert_eq!(1 + 1, 2);
+}
@@ -479,10 +479,18 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         color: config.color,
         test_threads: None,
         skip: vec![],
+        shard: None,
         list: false,
         options: test::Options::new(),
         time_options: None,
+        test_timeout: None,
+        retries: 0,
         force_run_in_process: false,
+        isolate: false,
+        shuffle: false,
+        shuffle_seed: None,
+        save_baseline: None,
+        baseline: None,
     }
 }
 
@@ -641,6 +649,8 @@ fn make_test(config: &Config, testpaths: &TestPaths, inputs: &Stamp) -> Vec<test
                     ignore,
                     should_panic,
                     allow_fail: false,
+                    #[cfg(not(bootstrap))]
+                    timeout: None,
                     test_type: test::TestType::Unknown,
                 },
                 testfn: make_test_closure(config, testpaths, revision),